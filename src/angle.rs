@@ -1,20 +1,55 @@
 use core::marker::PhantomData;
-use crate::scalar::TrigScalar;
+use crate::scalar::{sealed, TrigScalar};
 
 pub enum Rad {}
 pub enum Deg {}
 
+/// Angle unit marker; implemented only for [`Rad`] and [`Deg`].
+pub trait AngleUnit: sealed::Sealed {
+    /// Unit tag used by serialized forms.
+    const NAME: &'static str;
+}
+
+impl sealed::Sealed for Rad {}
+impl sealed::Sealed for Deg {}
+
+impl AngleUnit for Rad { const NAME: &'static str = "rad"; }
+impl AngleUnit for Deg { const NAME: &'static str = "deg"; }
+
 pub struct Angle<T: TrigScalar, U> {
-    value: T,
+    pub(crate) value: T,
     _unit: PhantomData<U>,
 }
 
 pub type RadAngle<T> = Angle<T, Rad>;
 pub type DegAngle<T> = Angle<T, Deg>;
 
+// Manual impls: derives would demand the same traits of the unit marker.
+impl<T: TrigScalar, U> Clone for Angle<T, U> {
+    fn clone(&self) -> Self { *self }
+}
+
+impl<T: TrigScalar, U> Copy for Angle<T, U> {}
+
+impl<T: TrigScalar, U> PartialEq for Angle<T, U> {
+    fn eq(&self, other: &Self) -> bool { self.value == other.value }
+}
+
+impl<T: TrigScalar + core::fmt::Debug, U: AngleUnit> core::fmt::Debug for Angle<T, U> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Angle({:?} {})", self.value, U::NAME)
+    }
+}
+
+impl<T: TrigScalar, U> Angle<T, U> {
+    pub(crate) fn from_value(value: T) -> Self {
+        Self { value, _unit: PhantomData }
+    }
+}
+
 impl<T: TrigScalar> RadAngle<T> {
     pub fn from_radians(r: T) -> Self {
-        Self { value: r, _unit: PhantomData }
+        Self::from_value(r)
     }
 
     pub fn to_radians(self) -> T { self.value }
@@ -26,7 +61,7 @@ impl<T: TrigScalar> RadAngle<T> {
 
 impl<T: TrigScalar> DegAngle<T> {
     pub fn from_degrees(d: T) -> Self {
-        Self { value: d, _unit: PhantomData }
+        Self::from_value(d)
     }
 
     pub fn to_degrees(self) -> T { self.value }
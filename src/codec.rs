@@ -0,0 +1,391 @@
+//! Canonical binary encoding for lockstep and replay protocols.
+//!
+//! Every value is written as a two-byte header followed by a fixed-size
+//! little-endian payload, so [`Codec::ENCODED_LEN`] is a compile-time constant
+//! for each type:
+//!
+//! | offset | content                                   |
+//! |--------|-------------------------------------------|
+//! | 0      | format version, [`CODEC_VERSION`]         |
+//! | 1      | type tag                                  |
+//! | 2..    | payload, `ENCODED_LEN - 2` bytes          |
+//!
+//! The low nibble of a tag names the scalar backend and the high nibble the
+//! container (`0` for a bare scalar), so a `Vec3<DFix64>` can never be decoded
+//! as a `Vec3<F32Det>` or a `Point3<DFix64>`. Composite payloads are their
+//! scalar payloads back to back without nested headers. Records with no scalar
+//! component, such as [`Prng`] snapshots, use a bare container nibble.
+//!
+//! Decoding is total: any byte sequence yields either a value or a
+//! [`DecodeError`], never a panic. Accepted input is canonical, meaning
+//! re-encoding a decoded value reproduces the consumed bytes exactly. On error
+//! the buffer position is unspecified.
+
+use core::fmt;
+
+use bytes::{Buf, BufMut};
+
+use crate::scalar::{DFix128, DFix32, DFix64, F32Det, F64Det, Scalar};
+use crate::{Direction3, Mat4, Point3, Prng, Quat, Vec3, PRNG_ALGO_VERSION};
+
+/// Version byte written at the start of every encoded value.
+///
+/// Bump only when an existing layout changes; new types get new tags instead.
+pub const CODEC_VERSION: u8 = 1;
+
+/// Reasons a byte sequence is rejected by [`Codec::decode`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum DecodeError {
+    /// The buffer holds fewer bytes than the type's fixed encoded length.
+    UnexpectedEnd { needed: usize, remaining: usize },
+    /// The version byte does not match [`CODEC_VERSION`].
+    UnsupportedVersion(u8),
+    /// The tag byte names a different type.
+    UnexpectedTag { expected: u8, found: u8 },
+    /// The payload is well-formed but not a canonical value, e.g. a NaN.
+    NonCanonical,
+    /// A [`Prng`] snapshot was taken under a different [`PRNG_ALGO_VERSION`]
+    /// and would not replay the same sequence.
+    UnsupportedAlgoVersion(u32),
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::UnexpectedEnd { needed, remaining } => {
+                write!(f, "need {needed} bytes, {remaining} remaining")
+            }
+            Self::UnsupportedVersion(v) => write!(f, "unsupported codec version {v}"),
+            Self::UnexpectedTag { expected, found } => {
+                write!(f, "expected tag {expected:#04x}, found {found:#04x}")
+            }
+            Self::NonCanonical => f.write_str("non-canonical payload"),
+            Self::UnsupportedAlgoVersion(v) => write!(f, "unsupported PRNG algorithm version {v}"),
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+/// Fixed-layout binary encoding; see the [module docs](self) for the format.
+pub trait Codec: Sized {
+    /// Type tag written after the version byte.
+    const TAG: u8;
+    /// Payload size in bytes, excluding the two-byte header.
+    const PAYLOAD_LEN: usize;
+    /// Total encoded size in bytes.
+    const ENCODED_LEN: usize = 2 + Self::PAYLOAD_LEN;
+
+    /// Writes exactly `PAYLOAD_LEN` bytes.
+    fn encode_payload(&self, buf: &mut impl BufMut);
+
+    /// Reads exactly `PAYLOAD_LEN` bytes; the caller has checked they exist.
+    fn decode_payload(buf: &mut impl Buf) -> Result<Self, DecodeError>;
+
+    /// Writes the header and payload.
+    fn encode(&self, buf: &mut impl BufMut) {
+        buf.put_u8(CODEC_VERSION);
+        buf.put_u8(Self::TAG);
+        self.encode_payload(buf);
+    }
+
+    /// Reads one value, validating length, version, tag and payload.
+    fn decode(buf: &mut impl Buf) -> Result<Self, DecodeError> {
+        let remaining = buf.remaining();
+        if remaining < Self::ENCODED_LEN {
+            return Err(DecodeError::UnexpectedEnd { needed: Self::ENCODED_LEN, remaining });
+        }
+        let version = buf.get_u8();
+        if version != CODEC_VERSION {
+            return Err(DecodeError::UnsupportedVersion(version));
+        }
+        let tag = buf.get_u8();
+        if tag != Self::TAG {
+            return Err(DecodeError::UnexpectedTag { expected: Self::TAG, found: tag });
+        }
+        Self::decode_payload(buf)
+    }
+}
+
+const VEC3_KIND: u8 = 0x10;
+const POINT3_KIND: u8 = 0x20;
+const DIRECTION3_KIND: u8 = 0x30;
+const QUAT_KIND: u8 = 0x40;
+const MAT4_KIND: u8 = 0x50;
+const PRNG_KIND: u8 = 0x60;
+
+macro_rules! float_codec {
+    ($($ty:ty, $tag:expr, $len:expr, $put:ident, $get:ident, $wrap:expr, $unwrap:expr;)*) => {$(
+        /// NaN payloads are rejected so every accepted encoding is canonical.
+        impl Codec for $ty {
+            const TAG: u8 = $tag;
+            const PAYLOAD_LEN: usize = $len;
+
+            fn encode_payload(&self, buf: &mut impl BufMut) {
+                buf.$put(($unwrap)(*self));
+            }
+
+            fn decode_payload(buf: &mut impl Buf) -> Result<Self, DecodeError> {
+                let v = buf.$get();
+                if v.is_nan() {
+                    return Err(DecodeError::NonCanonical);
+                }
+                Ok(($wrap)(v))
+            }
+        }
+    )*};
+}
+
+float_codec! {
+    f32, 0x01, 4, put_f32_le, get_f32_le, |v| v, |v| v;
+    f64, 0x02, 8, put_f64_le, get_f64_le, |v| v, |v| v;
+    F32Det, 0x03, 4, put_f32_le, get_f32_le, F32Det, |v: F32Det| v.0;
+    F64Det, 0x04, 8, put_f64_le, get_f64_le, F64Det, |v: F64Det| v.0;
+}
+
+macro_rules! fixed_codec {
+    ($($ty:ident, $tag:expr, $len:expr, $put:ident, $get:ident;)*) => {$(
+        impl Codec for $ty {
+            const TAG: u8 = $tag;
+            const PAYLOAD_LEN: usize = $len;
+
+            fn encode_payload(&self, buf: &mut impl BufMut) {
+                buf.$put(self.0);
+            }
+
+            fn decode_payload(buf: &mut impl Buf) -> Result<Self, DecodeError> {
+                Ok($ty(buf.$get()))
+            }
+        }
+    )*};
+}
+
+fixed_codec! {
+    DFix32, 0x05, 4, put_i32_le, get_i32_le;
+    DFix64, 0x06, 8, put_i64_le, get_i64_le;
+    DFix128, 0x07, 16, put_i128_le, get_i128_le;
+}
+
+impl<T: Scalar + Codec> Codec for Vec3<T> {
+    const TAG: u8 = VEC3_KIND | T::TAG;
+    const PAYLOAD_LEN: usize = 3 * T::PAYLOAD_LEN;
+
+    fn encode_payload(&self, buf: &mut impl BufMut) {
+        self.x.encode_payload(buf);
+        self.y.encode_payload(buf);
+        self.z.encode_payload(buf);
+    }
+
+    fn decode_payload(buf: &mut impl Buf) -> Result<Self, DecodeError> {
+        let x = T::decode_payload(buf)?;
+        let y = T::decode_payload(buf)?;
+        let z = T::decode_payload(buf)?;
+        Ok(Vec3::new(x, y, z))
+    }
+}
+
+impl<T: Scalar + Codec> Codec for Point3<T> {
+    const TAG: u8 = POINT3_KIND | T::TAG;
+    const PAYLOAD_LEN: usize = Vec3::<T>::PAYLOAD_LEN;
+
+    fn encode_payload(&self, buf: &mut impl BufMut) {
+        self.0.encode_payload(buf);
+    }
+
+    fn decode_payload(buf: &mut impl Buf) -> Result<Self, DecodeError> {
+        Vec3::decode_payload(buf).map(Point3)
+    }
+}
+
+impl<T: Scalar + Codec> Codec for Direction3<T> {
+    const TAG: u8 = DIRECTION3_KIND | T::TAG;
+    const PAYLOAD_LEN: usize = Vec3::<T>::PAYLOAD_LEN;
+
+    fn encode_payload(&self, buf: &mut impl BufMut) {
+        self.0.encode_payload(buf);
+    }
+
+    fn decode_payload(buf: &mut impl Buf) -> Result<Self, DecodeError> {
+        Vec3::decode_payload(buf).map(Direction3)
+    }
+}
+
+/// Reads `N` little-endian `f32`s, rejecting non-finite components to match
+/// the finiteness contract of [`Quat::new`] and [`Mat4::new`].
+fn get_finite_f32s<const N: usize>(buf: &mut impl Buf) -> Result<[f32; N], DecodeError> {
+    let mut out = [0.0; N];
+    for v in &mut out {
+        *v = buf.get_f32_le();
+        if !v.is_finite() {
+            return Err(DecodeError::NonCanonical);
+        }
+    }
+    Ok(out)
+}
+
+impl Codec for Quat {
+    const TAG: u8 = QUAT_KIND | f32::TAG;
+    const PAYLOAD_LEN: usize = 4 * 4;
+
+    fn encode_payload(&self, buf: &mut impl BufMut) {
+        for v in self.to_array() {
+            buf.put_f32_le(v);
+        }
+    }
+
+    fn decode_payload(buf: &mut impl Buf) -> Result<Self, DecodeError> {
+        get_finite_f32s::<4>(buf).map(Quat::from)
+    }
+}
+
+impl Codec for Mat4 {
+    const TAG: u8 = MAT4_KIND | f32::TAG;
+    const PAYLOAD_LEN: usize = 16 * 4;
+
+    fn encode_payload(&self, buf: &mut impl BufMut) {
+        for v in self.to_array() {
+            buf.put_f32_le(v);
+        }
+    }
+
+    fn decode_payload(buf: &mut impl Buf) -> Result<Self, DecodeError> {
+        get_finite_f32s::<16>(buf).map(Mat4::from)
+    }
+}
+
+/// Payload: [`PRNG_ALGO_VERSION`] as `u32`, then both state words. The version
+/// is checked before the state, and the all-zero state is non-canonical.
+impl Codec for Prng {
+    const TAG: u8 = PRNG_KIND;
+    const PAYLOAD_LEN: usize = 4 + 2 * 8;
+
+    fn encode_payload(&self, buf: &mut impl BufMut) {
+        buf.put_u32_le(PRNG_ALGO_VERSION);
+        for word in self.state() {
+            buf.put_u64_le(word);
+        }
+    }
+
+    fn decode_payload(buf: &mut impl Buf) -> Result<Self, DecodeError> {
+        let version = buf.get_u32_le();
+        if version != PRNG_ALGO_VERSION {
+            return Err(DecodeError::UnsupportedAlgoVersion(version));
+        }
+        let state = [buf.get_u64_le(), buf.get_u64_le()];
+        Prng::from_state(state).ok_or(DecodeError::NonCanonical)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn to_bytes<T: Codec>(value: &T) -> Vec<u8> {
+        let mut out = Vec::new();
+        value.encode(&mut out);
+        assert_eq!(out.len(), T::ENCODED_LEN);
+        out
+    }
+
+    #[test]
+    fn golden_layouts() {
+        assert_eq!(to_bytes(&DFix64::ONE), [1, 0x06, 0, 0, 1, 0, 0, 0, 0, 0]);
+        assert_eq!(to_bytes(&F32Det(1.0)), [1, 0x03, 0, 0, 0x80, 0x3f]);
+        assert_eq!(
+            to_bytes(&Point3::new(DFix32(1), DFix32(-1), DFix32(2))),
+            [1, 0x25, 1, 0, 0, 0, 0xff, 0xff, 0xff, 0xff, 2, 0, 0, 0]
+        );
+        assert_eq!(
+            to_bytes(&Prng::from_seed(1, 0x0200)),
+            [1, 0x60, 1, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 2, 0, 0, 0, 0, 0, 0]
+        );
+        assert_eq!(Mat4::ENCODED_LEN, 66);
+        assert_eq!(Vec3::<DFix128>::ENCODED_LEN, 50);
+    }
+
+    #[test]
+    fn round_trips() {
+        let v = Vec3::new(DFix128(-5), DFix128(i128::MAX), DFix128(i128::MIN));
+        assert_eq!(Vec3::decode(&mut to_bytes(&v).as_slice()), Ok(v));
+        let q = Quat::from_axis_angle(Vec3::new(0.0, 1.0, 0.0), 0.5);
+        assert_eq!(Quat::decode(&mut to_bytes(&q).as_slice()), Ok(q));
+        let m = Mat4::rotation_from_euler(0.1, 0.2, 0.3);
+        assert_eq!(Mat4::decode(&mut to_bytes(&m).as_slice()), Ok(m));
+        let d = Direction3::new(F64Det(0.5), F64Det(-0.0), F64Det(1e300));
+        assert_eq!(Direction3::decode(&mut to_bytes(&d).as_slice()), Ok(d));
+        let mut prng = Prng::from_seed_u64(3);
+        prng.next_u64();
+        assert_eq!(Prng::decode(&mut to_bytes(&prng).as_slice()), Ok(prng));
+    }
+
+    #[test]
+    fn rejects_malformed_headers_and_payloads() {
+        let bytes = to_bytes(&Point3::new(DFix64::ONE, DFix64::ZERO, DFix64::ONE));
+        assert_eq!(
+            Point3::<DFix64>::decode(&mut &bytes[..10]),
+            Err(DecodeError::UnexpectedEnd { needed: 26, remaining: 10 })
+        );
+        assert_eq!(
+            Direction3::<DFix64>::decode(&mut bytes.as_slice()),
+            Err(DecodeError::UnexpectedTag { expected: 0x36, found: 0x26 })
+        );
+        assert_eq!(
+            Point3::<DFix32>::decode(&mut bytes.as_slice()),
+            Err(DecodeError::UnexpectedTag { expected: 0x25, found: 0x26 })
+        );
+        let mut wrong_version = bytes.clone();
+        wrong_version[0] = 2;
+        assert_eq!(
+            Point3::<DFix64>::decode(&mut wrong_version.as_slice()),
+            Err(DecodeError::UnsupportedVersion(2))
+        );
+        assert_eq!(
+            F32Det::decode(&mut to_bytes(&F32Det(f32::NAN)).as_slice()),
+            Err(DecodeError::NonCanonical)
+        );
+        let inf = Quat::from([0.0, 0.0, f32::INFINITY, 1.0]);
+        assert_eq!(Quat::decode(&mut to_bytes(&inf).as_slice()), Err(DecodeError::NonCanonical));
+
+        let mut snapshot = to_bytes(&Prng::from_seed(1, 2));
+        snapshot[2] = 2;
+        assert_eq!(Prng::decode(&mut snapshot.as_slice()), Err(DecodeError::UnsupportedAlgoVersion(2)));
+        snapshot[2] = 1;
+        snapshot[6..].fill(0);
+        assert_eq!(Prng::decode(&mut snapshot.as_slice()), Err(DecodeError::NonCanonical));
+    }
+
+    fn fuzz<T: Codec>(prng: &mut Prng, bytes: &mut [u8]) {
+        for _ in 0..2_000 {
+            let len = prng.next_int(0, bytes.len() as i32) as usize;
+            for b in bytes.iter_mut() {
+                *b = prng.next_int(0, 255) as u8;
+            }
+            // Keep most inputs past the header so payload validation is hit.
+            if prng.next_int(0, 3) != 0 && bytes.len() >= 2 {
+                bytes[0] = CODEC_VERSION;
+                bytes[1] = T::TAG;
+            }
+            let input = &bytes[..len];
+            if let Ok(value) = T::decode(&mut &input[..]) {
+                let mut out = Vec::new();
+                value.encode(&mut out);
+                assert_eq!(out, input[..T::ENCODED_LEN], "non-canonical accept");
+            }
+        }
+    }
+
+    #[test]
+    fn random_input_never_panics_and_accepts_only_canonical_bytes() {
+        let mut prng = Prng::from_seed(0xC0DE, 0xFACE);
+        let mut bytes = [0u8; 80];
+        fuzz::<F32Det>(&mut prng, &mut bytes);
+        fuzz::<F64Det>(&mut prng, &mut bytes);
+        fuzz::<DFix64>(&mut prng, &mut bytes);
+        fuzz::<Vec3<DFix128>>(&mut prng, &mut bytes);
+        fuzz::<Point3<F32Det>>(&mut prng, &mut bytes);
+        fuzz::<Direction3<f32>>(&mut prng, &mut bytes);
+        fuzz::<Quat>(&mut prng, &mut bytes);
+        fuzz::<Mat4>(&mut prng, &mut bytes);
+        fuzz::<Prng>(&mut prng, &mut bytes);
+    }
+}
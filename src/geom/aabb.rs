@@ -0,0 +1,139 @@
+use super::GeomError;
+use crate::scalar::RealScalar;
+use crate::{Mat4, Point3, Vec3};
+
+/// Axis-aligned bounding box with `min <= max` on every axis.
+///
+/// A box may be flat or a single point; it is closed, so points on its faces
+/// are contained.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Aabb<T: RealScalar> {
+    min: Point3<T>,
+    max: Point3<T>,
+}
+
+impl<T: RealScalar> Aabb<T> {
+    /// Creates a box from its corners.
+    ///
+    /// Returns [`GeomError::InvertedBounds`] if `min` exceeds `max` on any
+    /// axis.
+    pub fn new(min: Point3<T>, max: Point3<T>) -> Result<Self, GeomError> {
+        if min.0.x > max.0.x || min.0.y > max.0.y || min.0.z > max.0.z {
+            return Err(GeomError::InvertedBounds);
+        }
+        Ok(Self { min, max })
+    }
+
+    /// Box around `center` extending `half_extents` along each axis.
+    ///
+    /// Returns [`GeomError::InvertedBounds`] for a negative extent.
+    pub fn from_center_half_extents(center: Point3<T>, half_extents: Vec3<T>) -> Result<Self, GeomError> {
+        Self::new(Point3(center.0 - half_extents), Point3(center.0 + half_extents))
+    }
+
+    pub fn min(&self) -> Point3<T> {
+        self.min
+    }
+
+    pub fn max(&self) -> Point3<T> {
+        self.max
+    }
+
+    pub fn center(&self) -> Point3<T> {
+        let half = T::ONE / (T::ONE + T::ONE);
+        Point3((self.min.0 + self.max.0) * half)
+    }
+
+    /// Full edge lengths along each axis.
+    pub fn size(&self) -> Vec3<T> {
+        self.max.0 - self.min.0
+    }
+
+    /// Half the edge lengths along each axis.
+    pub fn half_extents(&self) -> Vec3<T> {
+        let half = T::ONE / (T::ONE + T::ONE);
+        self.size() * half
+    }
+
+    /// Smallest box containing both `self` and `other`.
+    pub fn union(&self, other: &Self) -> Self {
+        let (a, b, c, d) = (self.min.0, other.min.0, self.max.0, other.max.0);
+        Self {
+            min: Point3::new(a.x.min(b.x), a.y.min(b.y), a.z.min(b.z)),
+            max: Point3::new(c.x.max(d.x), c.y.max(d.y), c.z.max(d.z)),
+        }
+    }
+
+    /// Returns `true` if the boxes overlap or touch; a cheaper test than
+    /// building their [`Contact`](super::Contact).
+    pub fn overlaps(&self, other: &Self) -> bool {
+        let (a_lo, a_hi, b_lo, b_hi) = (self.min.0, self.max.0, other.min.0, other.max.0);
+        a_lo.x <= b_hi.x && b_lo.x <= a_hi.x && a_lo.y <= b_hi.y && b_lo.y <= a_hi.y && a_lo.z <= b_hi.z && b_lo.z <= a_hi.z
+    }
+
+    /// Returns `true` if `point` lies inside or on the box.
+    pub fn contains_point(&self, point: Point3<T>) -> bool {
+        let (p, lo, hi) = (point.0, self.min.0, self.max.0);
+        lo.x <= p.x && p.x <= hi.x && lo.y <= p.y && p.y <= hi.y && lo.z <= p.z && p.z <= hi.z
+    }
+}
+
+impl Aabb<f32> {
+    /// Box around this one after transforming it by the affine part of `m`.
+    ///
+    /// Uses Arvo's method: each output extent sums, per input axis, the
+    /// smaller and larger of the matrix entry times the two bounds, which
+    /// gives the box around all eight transformed corners without
+    /// transforming them.
+    pub fn transformed(&self, m: &Mat4) -> Self {
+        let (data, lo, hi) = (m.to_array(), self.min.0.to_array(), self.max.0.to_array());
+        let (mut out_lo, mut out_hi) = ([data[12], data[13], data[14]], [data[12], data[13], data[14]]);
+        for row in 0..3 {
+            for col in 0..3 {
+                let (a, b) = (data[col * 4 + row] * lo[col], data[col * 4 + row] * hi[col]);
+                out_lo[row] += a.min(b);
+                out_hi[row] += a.max(b);
+            }
+        }
+        Self { min: Point3(Vec3::from(out_lo)), max: Point3(Vec3::from(out_hi)) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geom::test_support::p;
+
+    #[test]
+    fn enforces_min_le_max() {
+        let b = Aabb::new(p(-1.0, 0.0, 2.0), p(1.0, 0.0, 3.0)).unwrap();
+        assert_eq!(b.center(), p(0.0, 0.0, 2.5));
+        assert_eq!(b.half_extents(), p(1.0, 0.0, 0.5).0);
+        assert!(b.contains_point(p(1.0, 0.0, 2.0)));
+        assert!(!b.contains_point(p(0.0, 0.1, 2.5)));
+        let u = b.union(&Aabb::new(p(2.0, -1.0, 0.0), p(3.0, -1.0, 0.0)).unwrap());
+        assert_eq!((u.min(), u.max()), (p(-1.0, -1.0, 0.0), p(3.0, 0.0, 3.0)));
+        assert!(u.overlaps(&b) && b.overlaps(&Aabb::new(p(1.0, 0.0, 3.0), p(2.0, 1.0, 4.0)).unwrap()));
+        assert!(!b.overlaps(&Aabb::new(p(1.0, 0.5, 3.0), p(2.0, 1.0, 4.0)).unwrap()));
+        assert_eq!(Aabb::new(p(0.0, 1.0, 0.0), p(1.0, 0.0, 1.0)), Err(GeomError::InvertedBounds));
+        assert_eq!(Aabb::from_center_half_extents(p(0.0, 0.0, 0.0), p(1.0, -1.0, 1.0).0), Err(GeomError::InvertedBounds));
+    }
+
+    #[test]
+    fn transformed_by_mat4_is_conservative() {
+        let b = Aabb::new(Point3::new(-1.0, -2.0, 0.0), Point3::new(1.0, 2.0, 1.0)).unwrap();
+        let moved = b.transformed(&(Mat4::translation(5.0, 0.0, 0.0) * Mat4::scale(2.0, -1.0, 1.0)));
+        assert_eq!((moved.min(), moved.max()), (Point3::new(3.0, -2.0, 0.0), Point3::new(7.0, 2.0, 1.0)));
+
+        let m = Mat4::rotation_z(0.5);
+        let turned = b.transformed(&m);
+        for k in 0..8 {
+            let pick = |bit: usize, lo: f32, hi: f32| if k & bit == 0 { lo } else { hi };
+            let corner = Vec3::new(pick(1, -1.0, 1.0), pick(2, -2.0, 2.0), pick(4, 0.0, 1.0));
+            let q = m.transform_point(&corner).to_array();
+            let (lo, hi) = (turned.min().0.to_array(), turned.max().0.to_array());
+            assert!((0..3).all(|i| lo[i] - 1e-6 <= q[i] && q[i] <= hi[i] + 1e-6));
+        }
+        assert!((turned.max().0.x - (0.5f32.cos() + 2.0 * 0.5f32.sin())).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,356 @@
+//! Grid and sort-and-sweep broadphases for many boxes of similar size.
+//!
+//! Both report candidate pairs `(a, b)` with `a < b`, sorted and free of
+//! duplicates, and only for boxes that actually overlap or touch. The
+//! output is therefore the same list on every machine, whatever order the
+//! boxes were added in, and can be fed straight to a narrowphase.
+
+use core::cmp::{Ordering, Reverse};
+use std::collections::{BTreeMap, BinaryHeap};
+
+use super::{Aabb, GeomError};
+use crate::scalar::RealScalar;
+use crate::Point3;
+
+/// Integer coordinates of a grid cell.
+pub type GridCell = [i64; 3];
+
+/// Most cells a [`SpatialHash`] files one box under; larger boxes are kept
+/// on a side list instead.
+pub const GRID_MAX_CELLS_PER_BOX: usize = 64;
+
+/// Uniform grid over boxes, keyed by integer cell coordinates.
+///
+/// Each box is filed under every cell it touches. Cells live in an ordered
+/// map rather than a hash table, so iteration never depends on hashing.
+/// A box touching more than [`GRID_MAX_CELLS_PER_BOX`] cells, or lying
+/// beyond the `i64` cell range, goes on a side list that every query scans
+/// in full; pick the cell size near the typical box size so that list stays
+/// short.
+#[derive(Clone, Debug)]
+pub struct SpatialHash<T: RealScalar> {
+    cell_size: T,
+    cells: BTreeMap<GridCell, Vec<usize>>,
+    oversized: Vec<usize>,
+    boxes: Vec<Option<Aabb<T>>>,
+    free_ids: BinaryHeap<Reverse<usize>>,
+}
+
+impl<T: RealScalar> SpatialHash<T> {
+    /// Returns [`GeomError::NonPositiveCellSize`] unless `cell_size > 0`.
+    pub fn new(cell_size: T) -> Result<Self, GeomError> {
+        if cell_size <= T::ZERO {
+            return Err(GeomError::NonPositiveCellSize);
+        }
+        Ok(Self { cell_size, cells: BTreeMap::new(), oversized: Vec::new(), boxes: Vec::new(), free_ids: BinaryHeap::new() })
+    }
+
+    pub fn cell_size(&self) -> T {
+        self.cell_size
+    }
+
+    /// Cell containing `point`, or `None` if its coordinates do not fit in
+    /// an `i64`.
+    pub fn cell_of(&self, point: Point3<T>) -> Option<GridCell> {
+        let coord = |x: T| index((x / self.cell_size).floor());
+        Some([coord(point.0.x)?, coord(point.0.y)?, coord(point.0.z)?])
+    }
+
+    /// Lowest and highest cells touched by `aabb`, or `None` if it touches
+    /// more than [`GRID_MAX_CELLS_PER_BOX`] cells or leaves the cell range.
+    fn span(&self, aabb: &Aabb<T>) -> Option<(GridCell, GridCell)> {
+        let (lo, hi) = (self.cell_of(aabb.min())?, self.cell_of(aabb.max())?);
+        let mut count: u128 = 1;
+        for k in 0..3 {
+            count *= (i128::from(hi[k]) - i128::from(lo[k]) + 1) as u128;
+            if count > GRID_MAX_CELLS_PER_BOX as u128 {
+                return None;
+            }
+        }
+        Some((lo, hi))
+    }
+
+    /// Number of boxes.
+    pub fn len(&self) -> usize {
+        self.boxes.iter().filter(|b| b.is_some()).count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Box `id`, if present.
+    pub fn get(&self, id: usize) -> Option<Aabb<T>> {
+        *self.boxes.get(id)?
+    }
+
+    /// Adds a box and returns its id, reusing the smallest removed id.
+    pub fn insert(&mut self, aabb: Aabb<T>) -> usize {
+        let id = match self.free_ids.pop() {
+            Some(Reverse(id)) => id,
+            None => {
+                self.boxes.push(None);
+                self.boxes.len() - 1
+            }
+        };
+        self.file(id, aabb);
+        id
+    }
+
+    fn file(&mut self, id: usize, aabb: Aabb<T>) {
+        self.boxes[id] = Some(aabb);
+        let Some((lo, hi)) = self.span(&aabb) else {
+            if let Err(at) = self.oversized.binary_search(&id) {
+                self.oversized.insert(at, id);
+            }
+            return;
+        };
+        for cell in cells(lo, hi) {
+            let ids = self.cells.entry(cell).or_default();
+            // Keep each cell sorted so edits cannot reorder later output.
+            if let Err(at) = ids.binary_search(&id) {
+                ids.insert(at, id);
+            }
+        }
+    }
+
+    fn unfile(&mut self, id: usize) -> Option<Aabb<T>> {
+        let aabb = self.boxes.get_mut(id)?.take()?;
+        let Some((lo, hi)) = self.span(&aabb) else {
+            self.oversized.retain(|&i| i != id);
+            return Some(aabb);
+        };
+        for cell in cells(lo, hi) {
+            if let Some(ids) = self.cells.get_mut(&cell) {
+                ids.retain(|&i| i != id);
+                if ids.is_empty() {
+                    self.cells.remove(&cell);
+                }
+            }
+        }
+        Some(aabb)
+    }
+
+    /// Removes box `id`; returns `false` if it is not present.
+    pub fn remove(&mut self, id: usize) -> bool {
+        let removed = self.unfile(id).is_some();
+        if removed {
+            self.free_ids.push(Reverse(id));
+        }
+        removed
+    }
+
+    /// Moves box `id` to `aabb`; returns `false` if it is not present.
+    pub fn update(&mut self, id: usize, aabb: Aabb<T>) -> bool {
+        let present = self.unfile(id).is_some();
+        if present {
+            self.file(id, aabb);
+        }
+        present
+    }
+
+    /// Ids of the boxes overlapping or touching `aabb`, ascending.
+    ///
+    /// A query box too large for the grid scans every box instead.
+    pub fn query_aabb(&self, aabb: &Aabb<T>) -> Vec<usize> {
+        let candidates: Vec<usize> = match self.span(aabb) {
+            Some((lo, hi)) => cells(lo, hi).filter_map(|cell| self.cells.get(&cell)).flatten().chain(&self.oversized).copied().collect(),
+            None => (0..self.boxes.len()).collect(),
+        };
+        let mut ids: Vec<usize> = candidates.into_iter().filter(|&id| self.boxes[id].is_some_and(|b| b.overlaps(aabb))).collect();
+        ids.sort_unstable();
+        ids.dedup();
+        ids
+    }
+
+    /// Overlapping pairs `(a, b)` with `a < b`, sorted.
+    pub fn pairs(&self) -> Vec<(usize, usize)> {
+        let mut pairs = Vec::new();
+        let mut push = |a: usize, b: usize| {
+            if let (Some(ba), Some(bb)) = (self.boxes[a], self.boxes[b]) {
+                if a != b && ba.overlaps(&bb) {
+                    pairs.push((a.min(b), a.max(b)));
+                }
+            }
+        };
+        for ids in self.cells.values() {
+            for (k, &a) in ids.iter().enumerate() {
+                for &b in &ids[k + 1..] {
+                    push(a, b);
+                }
+            }
+        }
+        for &a in &self.oversized {
+            for b in 0..self.boxes.len() {
+                push(a, b);
+            }
+        }
+        pairs.sort_unstable();
+        pairs.dedup();
+        pairs
+    }
+}
+
+/// Every cell from `lo` to `hi` inclusive, in ascending order.
+fn cells(lo: GridCell, hi: GridCell) -> impl Iterator<Item = GridCell> {
+    (lo[0]..=hi[0]).flat_map(move |x| (lo[1]..=hi[1]).flat_map(move |y| (lo[2]..=hi[2]).map(move |z| [x, y, z])))
+}
+
+/// The integer `q` (already floored) as an `i64`, or `None` if it does not
+/// fit.
+///
+/// Bits are peeled off by exact halving, so no backend's value goes through
+/// a float conversion.
+fn index<T: RealScalar>(q: T) -> Option<i64> {
+    let two = T::ONE + T::ONE;
+    let mut n = q.abs();
+    let mut out = 0i64;
+    for bit in 0..63 {
+        if n < T::ONE {
+            break;
+        }
+        let half = (n / two).floor();
+        if n - half * two >= T::ONE {
+            out |= 1 << bit;
+        }
+        n = half;
+    }
+    if n >= T::ONE {
+        return None;
+    }
+    Some(if q < T::ZERO { -out } else { out })
+}
+
+/// Overlapping pairs among `boxes` by sort and sweep, as index pairs
+/// `(a, b)` with `a < b`, sorted.
+///
+/// The boxes are sorted by their minimum along the axis on which their
+/// centers spread widest (the lowest axis on ties), with the index breaking
+/// ties, and swept once keeping the boxes whose interval is still open.
+pub fn sweep_and_prune<T: RealScalar>(boxes: &[Aabb<T>]) -> Vec<(usize, usize)> {
+    let Some(first) = boxes.first() else {
+        return Vec::new();
+    };
+    let (mut lo, mut hi) = (first.center().0.to_array(), first.center().0.to_array());
+    for b in &boxes[1..] {
+        let c = b.center().0.to_array();
+        for k in 0..3 {
+            lo[k] = lo[k].min(c[k]);
+            hi[k] = hi[k].max(c[k]);
+        }
+    }
+    let axis = (1..3).fold(0, |best, k| if hi[k] - lo[k] > hi[best] - lo[best] { k } else { best });
+    let min_of = |i: usize| boxes[i].min().0.to_array()[axis];
+    let max_of = |i: usize| boxes[i].max().0.to_array()[axis];
+
+    let mut order: Vec<usize> = (0..boxes.len()).collect();
+    order.sort_by(|&a, &b| min_of(a).partial_cmp(&min_of(b)).unwrap_or(Ordering::Equal).then(a.cmp(&b)));
+    let mut active: Vec<usize> = Vec::new();
+    let mut pairs = Vec::new();
+    for &i in &order {
+        active.retain(|&j| max_of(j) >= min_of(i));
+        for &j in &active {
+            if boxes[i].overlaps(&boxes[j]) {
+                pairs.push((i.min(j), i.max(j)));
+            }
+        }
+        active.push(i);
+    }
+    pairs.sort_unstable();
+    pairs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geom::test_support::{fx, p};
+    use crate::scalar::{DFix128, DFix64, Scalar};
+
+    fn cube(x: f32, y: f32, z: f32, half: f32) -> Aabb<DFix64> {
+        Aabb::new(p(x - half, y - half, z - half), p(x + half, y + half, z + half)).unwrap()
+    }
+
+    /// A loose crowd: a 7 x 7 lattice with spacing 1.5 and sizes varying
+    /// around it, so neighbours sometimes overlap, sometimes just touch.
+    fn crowd() -> Vec<Aabb<DFix64>> {
+        (0..49).map(|i| cube((i % 7) as f32 * 1.5, (i / 7) as f32 * 1.5, (i % 2) as f32 * 0.25, 0.625 + (i % 5) as f32 * 0.125)).collect()
+    }
+
+    fn brute(boxes: &[Aabb<DFix64>]) -> Vec<(usize, usize)> {
+        (0..boxes.len()).flat_map(|a| (a + 1..boxes.len()).map(move |b| (a, b))).filter(|&(a, b)| boxes[a].overlaps(&boxes[b])).collect()
+    }
+
+    #[test]
+    fn grid_pairs_and_queries() {
+        let boxes = crowd();
+        let mut grid = SpatialHash::new(fx(2.0)).unwrap();
+        for b in &boxes {
+            grid.insert(*b);
+        }
+        let want = brute(&boxes);
+        assert!(!want.is_empty());
+        assert_eq!(grid.pairs(), want);
+        assert_eq!(grid.cell_of(p(-0.5, 2.0, 3.9)), Some([-1, 1, 1]));
+
+        let window = Aabb::new(p(2.0, 2.0, 0.0), p(4.0, 3.0, 0.0)).unwrap();
+        let hits: Vec<usize> = (0..boxes.len()).filter(|&i| boxes[i].overlaps(&window)).collect();
+        assert_eq!(grid.query_aabb(&window), hits);
+
+        // Inserting in reverse gives the same pairs once ids are mapped back.
+        let mut reversed = SpatialHash::new(fx(2.0)).unwrap();
+        for b in boxes.iter().rev() {
+            reversed.insert(*b);
+        }
+        let last = boxes.len() - 1;
+        let mut mapped: Vec<(usize, usize)> = reversed.pairs().into_iter().map(|(a, b)| (last - b, last - a)).collect();
+        mapped.sort_unstable();
+        assert_eq!(mapped, want);
+
+        assert!(grid.remove(8) && !grid.remove(8));
+        assert!(grid.pairs().iter().all(|&(a, b)| a != 8 && b != 8));
+        assert_eq!(grid.insert(cube(30.0, 30.0, 0.0, 0.5)), 8);
+        assert!(grid.update(8, cube(0.0, 0.0, 0.0, 0.5)));
+        assert!(grid.pairs().contains(&(0, 8)));
+        assert_eq!(SpatialHash::<DFix64>::new(fx(0.0)).map(|_| ()), Err(GeomError::NonPositiveCellSize));
+    }
+
+    #[test]
+    fn oversized_and_far_boxes_skip_the_grid() {
+        let mut boxes = crowd();
+        // Twenty thousand cells on a side: filing it cell by cell would hang.
+        boxes.push(cube(0.0, 0.0, 0.0, 1e4));
+        let mut grid = SpatialHash::new(fx(1.0)).unwrap();
+        for b in &boxes {
+            grid.insert(*b);
+        }
+        let big = boxes.len() - 1;
+        assert_eq!(grid.pairs(), brute(&boxes));
+        assert_eq!(grid.query_aabb(&cube(3.0, 3.0, 0.0, 0.1)), [16, big]);
+        assert_eq!(grid.query_aabb(&cube(0.0, 0.0, 0.0, 1e5)).len(), boxes.len());
+
+        // Moving boxes into and out of the side list keeps the pairs exact.
+        boxes[big] = cube(0.0, 0.0, 0.0, 0.5);
+        boxes[3] = cube(0.0, 0.0, 0.0, 1e4);
+        assert!(grid.update(big, boxes[big]) && grid.update(3, boxes[3]));
+        assert_eq!(grid.pairs(), brute(&boxes));
+        assert!(grid.remove(3));
+        assert!(grid.pairs().iter().all(|&(a, b)| a != 3 && b != 3));
+
+        // Cell indices past `i64` are reported as such rather than clamped.
+        let far = SpatialHash::new(DFix128::from_f32(1.0)).unwrap();
+        let at = |raw: i128| Point3::new(DFix128(raw), DFix128::ZERO, DFix128::ZERO);
+        assert_eq!(far.cell_of(at(-(1 << 94) - (1 << 31))), Some([-(1 << 62) - 1, 0, 0]));
+        assert_eq!(far.cell_of(at(1 << 102)), None);
+    }
+
+    #[test]
+    fn sweep_and_prune_matches_brute_force() {
+        let boxes = crowd();
+        assert_eq!(sweep_and_prune(&boxes), brute(&boxes));
+        assert_eq!(sweep_and_prune::<DFix64>(&[]), Vec::new());
+
+        // Touching along the sweep axis still pairs.
+        let row = [cube(0.0, 0.0, 0.0, 1.0), cube(4.0, 0.0, 0.0, 1.0), cube(2.0, 0.0, 0.0, 1.0)];
+        assert_eq!(sweep_and_prune(&row), [(0, 2), (1, 2)]);
+    }
+}
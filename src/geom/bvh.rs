@@ -0,0 +1,426 @@
+//! Bounding volume hierarchy over axis-aligned boxes.
+//!
+//! The tree is a pure function of the leaf ids, their boxes and the order of
+//! edits, never of memory addresses or hashing:
+//!
+//! * [`Bvh::build`] splits by a binned surface area heuristic, scanning
+//!   axes and bins in a fixed order with ties going to the earliest, and
+//!   partitions leaves stably by id.
+//! * Removed ids are reused smallest first.
+//! * Queries sort their results (by id, or by distance then id for rays), so
+//!   two trees holding the same leaves report the same hits in the same
+//!   order, however they were built.
+
+use core::cmp::{Ordering, Reverse};
+use std::collections::BinaryHeap;
+
+use super::closest::ClosestPoint;
+use super::raycast::RayCast;
+use super::support::SupportMap;
+use super::{Aabb, Plane, Ray3, Sphere};
+use crate::scalar::RealScalar;
+
+/// Number of centroid bins per axis tried by the build.
+pub const BVH_BINS: usize = 16;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum Kind {
+    /// Leaf holding the user id.
+    Leaf(usize),
+    Branch([usize; 2]),
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+struct Node<T: RealScalar> {
+    bounds: Aabb<T>,
+    parent: Option<usize>,
+    kind: Kind,
+}
+
+/// Dynamic bounding volume hierarchy with one [`Aabb`] per leaf.
+///
+/// Leaves are addressed by the `usize` ids returned from [`Bvh::build`]
+/// (the index of each box) and [`Bvh::insert`].
+#[derive(Clone, Debug)]
+pub struct Bvh<T: RealScalar> {
+    nodes: Vec<Node<T>>,
+    root: Option<usize>,
+    /// Node of each leaf id, `None` once removed.
+    leaves: Vec<Option<usize>>,
+    free_nodes: Vec<usize>,
+    free_ids: BinaryHeap<Reverse<usize>>,
+}
+
+impl<T: RealScalar> Default for Bvh<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Half the surface area of `b`: the SAH cost weight.
+fn area<T: RealScalar>(b: &Aabb<T>) -> T {
+    let s = b.size();
+    s.x * s.y + s.y * s.z + s.z * s.x
+}
+
+fn union_all<T: RealScalar>(items: &[(usize, Aabb<T>)]) -> Aabb<T> {
+    items[1..].iter().fold(items[0].1, |b, (_, i)| b.union(i))
+}
+
+/// Reorders `items` (at least two) into a left and right group and returns
+/// the size of the left one.
+fn split<T: RealScalar>(items: &mut [(usize, Aabb<T>)]) -> usize {
+    let centroids: Vec<[T; 3]> = items.iter().map(|(_, b)| b.center().0.to_array()).collect();
+    let (mut lo, mut hi) = (centroids[0], centroids[0]);
+    for c in &centroids[1..] {
+        for k in 0..3 {
+            lo[k] = lo[k].min(c[k]);
+            hi[k] = hi[k].max(c[k]);
+        }
+    }
+
+    // Bin of each centroid along each axis, counting the bin boundaries at
+    // or below it so that no division is needed.
+    let boundaries: [Vec<T>; 3] = [0, 1, 2].map(|axis| (1..BVH_BINS).map(|k| lo[axis] + (hi[axis] - lo[axis]) * T::from_f32(k as f32 / BVH_BINS as f32)).collect());
+    let bin_of = |c: T, axis: usize| boundaries[axis].iter().filter(|&&b| c >= b).count();
+    let mut best: Option<(T, usize, usize)> = None;
+    for axis in 0..3 {
+        if hi[axis] == lo[axis] {
+            continue;
+        }
+        let mut bins: [Option<(Aabb<T>, usize)>; BVH_BINS] = [None; BVH_BINS];
+        for ((_, b), c) in items.iter().zip(&centroids) {
+            let slot = &mut bins[bin_of(c[axis], axis)];
+            *slot = Some(slot.map_or((*b, 1), |(acc, n)| (acc.union(b), n + 1)));
+        }
+        let merge = |acc: Option<(Aabb<T>, usize)>, bin: &Option<(Aabb<T>, usize)>| match (acc, bin) {
+            (Some((a, n)), Some((b, m))) => Some((a.union(b), n + m)),
+            (a, b) => a.or(*b),
+        };
+        for cut in 1..BVH_BINS {
+            let (Some((left, nl)), Some((right, nr))) = (bins[..cut].iter().fold(None, merge), bins[cut..].iter().fold(None, merge)) else {
+                continue;
+            };
+            let cost = area(&left) * T::from_f64(nl as f64) + area(&right) * T::from_f64(nr as f64);
+            if best.map_or(true, |(c, _, _)| cost < c) {
+                best = Some((cost, axis, cut));
+            }
+        }
+    }
+
+    let Some((_, axis, cut)) = best else {
+        // Coincident centroids: halve the id order.
+        return items.len() / 2;
+    };
+    let (left, right): (Vec<_>, Vec<_>) = items.iter().zip(&centroids).partition(|(_, c)| bin_of(c[axis], axis) < cut);
+    let count = left.len();
+    let ordered: Vec<(usize, Aabb<T>)> = left.into_iter().chain(right).map(|(item, _)| *item).collect();
+    items.copy_from_slice(&ordered);
+    count
+}
+
+impl<T: RealScalar> Bvh<T> {
+    /// Empty hierarchy.
+    pub fn new() -> Self {
+        Self { nodes: Vec::new(), root: None, leaves: Vec::new(), free_nodes: Vec::new(), free_ids: BinaryHeap::new() }
+    }
+
+    /// Builds a hierarchy over `boxes` top-down with a binned surface area
+    /// heuristic; leaf `i` holds `boxes[i]`.
+    pub fn build(boxes: &[Aabb<T>]) -> Self {
+        let mut bvh = Self::new();
+        bvh.leaves = vec![None; boxes.len()];
+        let mut items: Vec<(usize, Aabb<T>)> = boxes.iter().copied().enumerate().collect();
+        if !items.is_empty() {
+            bvh.root = Some(bvh.build_range(&mut items, None));
+        }
+        bvh
+    }
+
+    fn build_range(&mut self, items: &mut [(usize, Aabb<T>)], parent: Option<usize>) -> usize {
+        if let [(id, bounds)] = *items {
+            let node = self.alloc(Node { bounds, parent, kind: Kind::Leaf(id) });
+            self.leaves[id] = Some(node);
+            return node;
+        }
+        let node = self.alloc(Node { bounds: union_all(items), parent, kind: Kind::Branch([0, 0]) });
+        let mid = split(items);
+        let (left, right) = items.split_at_mut(mid);
+        let children = [self.build_range(left, Some(node)), self.build_range(right, Some(node))];
+        self.nodes[node].kind = Kind::Branch(children);
+        node
+    }
+
+    fn alloc(&mut self, node: Node<T>) -> usize {
+        match self.free_nodes.pop() {
+            Some(i) => {
+                self.nodes[i] = node;
+                i
+            }
+            None => {
+                self.nodes.push(node);
+                self.nodes.len() - 1
+            }
+        }
+    }
+
+    /// Number of leaves.
+    pub fn len(&self) -> usize {
+        self.leaves.iter().filter(|l| l.is_some()).count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+
+    /// Box of leaf `id`, if present.
+    pub fn get(&self, id: usize) -> Option<Aabb<T>> {
+        let node = (*self.leaves.get(id)?)?;
+        Some(self.nodes[node].bounds)
+    }
+
+    /// Box around every leaf.
+    pub fn bounds(&self) -> Option<Aabb<T>> {
+        self.root.map(|r| self.nodes[r].bounds)
+    }
+
+    /// Points whichever of `parent`'s child links (or the root) refers to
+    /// `old` at `new`.
+    fn relink(&mut self, parent: Option<usize>, old: usize, new: usize) {
+        match parent {
+            None => self.root = Some(new),
+            Some(p) => {
+                if let Kind::Branch(children) = &mut self.nodes[p].kind {
+                    for c in children.iter_mut().filter(|c| **c == old) {
+                        *c = new;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Recomputes the bounds of `node` and its ancestors.
+    fn refit_from(&mut self, mut node: Option<usize>) {
+        while let Some(i) = node {
+            if let Kind::Branch([a, b]) = self.nodes[i].kind {
+                self.nodes[i].bounds = self.nodes[a].bounds.union(&self.nodes[b].bounds);
+            }
+            node = self.nodes[i].parent;
+        }
+    }
+
+    /// Adds a leaf and returns its id, reusing the smallest removed id.
+    ///
+    /// The leaf becomes the sibling of the existing leaf reached by always
+    /// descending into the child whose area grows least (the first on ties).
+    pub fn insert(&mut self, bounds: Aabb<T>) -> usize {
+        let id = match self.free_ids.pop() {
+            Some(Reverse(id)) => id,
+            None => {
+                self.leaves.push(None);
+                self.leaves.len() - 1
+            }
+        };
+        let leaf = self.alloc(Node { bounds, parent: None, kind: Kind::Leaf(id) });
+        self.leaves[id] = Some(leaf);
+        let Some(mut sibling) = self.root else {
+            self.root = Some(leaf);
+            return id;
+        };
+        while let Kind::Branch([a, b]) = self.nodes[sibling].kind {
+            let growth = |n: usize| {
+                let old = &self.nodes[n].bounds;
+                area(&old.union(&bounds)) - area(old)
+            };
+            sibling = if growth(b) < growth(a) { b } else { a };
+        }
+        let parent = self.nodes[sibling].parent;
+        let branch = self.alloc(Node { bounds: self.nodes[sibling].bounds.union(&bounds), parent, kind: Kind::Branch([sibling, leaf]) });
+        self.nodes[sibling].parent = Some(branch);
+        self.nodes[leaf].parent = Some(branch);
+        self.relink(parent, sibling, branch);
+        self.refit_from(parent);
+        id
+    }
+
+    /// Removes leaf `id`; returns `false` if it is not present.
+    pub fn remove(&mut self, id: usize) -> bool {
+        let Some(leaf) = self.leaves.get(id).copied().flatten() else {
+            return false;
+        };
+        self.leaves[id] = None;
+        self.free_ids.push(Reverse(id));
+        self.free_nodes.push(leaf);
+        let Some(parent) = self.nodes[leaf].parent else {
+            self.root = None;
+            return true;
+        };
+        let Kind::Branch([a, b]) = self.nodes[parent].kind else {
+            unreachable!("leaf parent is a branch");
+        };
+        let sibling = if a == leaf { b } else { a };
+        let grandparent = self.nodes[parent].parent;
+        self.nodes[sibling].parent = grandparent;
+        self.relink(grandparent, parent, sibling);
+        self.free_nodes.push(parent);
+        self.refit_from(grandparent);
+        true
+    }
+
+    /// Replaces the box of leaf `id` and refits its ancestors; returns
+    /// `false` if it is not present.
+    ///
+    /// The tree shape is kept, so after large motions rebuilding (or
+    /// removing and reinserting the moved leaves) gives tighter queries.
+    pub fn refit(&mut self, id: usize, bounds: Aabb<T>) -> bool {
+        let Some(leaf) = self.leaves.get(id).copied().flatten() else {
+            return false;
+        };
+        self.nodes[leaf].bounds = bounds;
+        self.refit_from(self.nodes[leaf].parent);
+        true
+    }
+
+    /// Visits every node whose bounds pass `enter`, calling `hit` on leaves.
+    fn traverse(&self, mut enter: impl FnMut(&Aabb<T>) -> bool, mut hit: impl FnMut(usize, &Aabb<T>)) {
+        let mut stack: Vec<usize> = self.root.into_iter().collect();
+        while let Some(i) = stack.pop() {
+            let node = &self.nodes[i];
+            if !enter(&node.bounds) {
+                continue;
+            }
+            match node.kind {
+                Kind::Leaf(id) => hit(id, &node.bounds),
+                Kind::Branch([a, b]) => stack.extend([b, a]),
+            }
+        }
+    }
+
+    fn collect(&self, enter: impl FnMut(&Aabb<T>) -> bool) -> Vec<usize> {
+        let mut ids = Vec::new();
+        self.traverse(enter, |id, _| ids.push(id));
+        ids.sort_unstable();
+        ids
+    }
+
+    /// Ids of the leaves overlapping or touching `aabb`, ascending.
+    pub fn query_aabb(&self, aabb: &Aabb<T>) -> Vec<usize> {
+        self.collect(|b| b.overlaps(aabb))
+    }
+
+    /// Ids of the leaves overlapping or touching `sphere`, ascending.
+    pub fn query_sphere(&self, sphere: &Sphere<T>) -> Vec<usize> {
+        let r = sphere.radius();
+        self.collect(|b| b.distance_sq(sphere.center()) <= r * r)
+    }
+
+    /// Ids of the leaves not entirely behind any of `planes`, ascending.
+    ///
+    /// A convex volume such as a view frustum is given by planes whose
+    /// normals point inwards. The test is conservative: a box near a corner
+    /// of the volume may be reported although it lies just outside.
+    pub fn query_planes(&self, planes: &[Plane<T>]) -> Vec<usize> {
+        self.collect(|b| planes.iter().all(|pl| pl.signed_distance(b.support(pl.normal())) >= T::ZERO))
+    }
+
+    /// Leaves whose boxes `ray` enters within `max_distance`, as `(id,
+    /// distance)` sorted by distance, then id.
+    pub fn cast_ray(&self, ray: &Ray3<T>, max_distance: T) -> Vec<(usize, T)> {
+        let mut hits = Vec::new();
+        self.traverse(|b| b.cast_ray(ray, max_distance).is_some(), |id, b| {
+            if let Some(hit) = b.cast_ray(ray, max_distance) {
+                hits.push((id, hit.distance));
+            }
+        });
+        hits.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(Ordering::Equal).then(a.0.cmp(&b.0)));
+        hits
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geom::test_support::{dir, fx, p};
+    use crate::scalar::DFix64;
+
+    fn cube(x: f32, y: f32, z: f32, half: f32) -> Aabb<DFix64> {
+        Aabb::new(p(x - half, y - half, z - half), p(x + half, y + half, z + half)).unwrap()
+    }
+
+    /// A 6 x 6 grid of boxes of assorted sizes, two apart.
+    fn grid() -> Vec<Aabb<DFix64>> {
+        (0..36).map(|i| cube((i % 6) as f32 * 2.0, (i / 6) as f32 * 2.0, (i % 3) as f32 * 0.5, 0.25 + (i % 4) as f32 * 0.125)).collect()
+    }
+
+    fn brute(boxes: &[Aabb<DFix64>], keep: impl Fn(&Aabb<DFix64>) -> bool) -> Vec<usize> {
+        (0..boxes.len()).filter(|&i| keep(&boxes[i])).collect()
+    }
+
+    #[test]
+    fn queries_match_brute_force() {
+        let boxes = grid();
+        let bvh = Bvh::build(&boxes);
+        assert_eq!((bvh.len(), bvh.bounds()), (36, Some(boxes.iter().skip(1).fold(boxes[0], |b, x| b.union(x)))));
+
+        let window = Aabb::new(p(1.0, 1.0, -1.0), p(5.0, 3.0, 1.0)).unwrap();
+        assert_eq!(bvh.query_aabb(&window), brute(&boxes, |b| b.overlaps(&window)));
+        assert!(!bvh.query_aabb(&window).is_empty());
+
+        let ball = Sphere::new(p(4.0, 4.0, 0.0), fx(2.5)).unwrap();
+        assert_eq!(bvh.query_sphere(&ball), brute(&boxes, |b| b.distance_sq(ball.center()) <= fx(6.25)));
+
+        // The slab 3 <= x <= 7.
+        let planes = [
+            Plane::from_point_normal(p(3.0, 0.0, 0.0), dir(1.0, 0.0, 0.0)).unwrap(),
+            Plane::from_point_normal(p(7.0, 0.0, 0.0), dir(-1.0, 0.0, 0.0)).unwrap(),
+        ];
+        assert_eq!(bvh.query_planes(&planes), brute(&boxes, |b| b.max().0.x >= fx(3.0) && b.min().0.x <= fx(7.0)));
+        assert_eq!(bvh.query_planes(&[]).len(), 36);
+    }
+
+    #[test]
+    fn ray_hits_sorted_by_distance() {
+        let bvh = Bvh::build(&grid());
+        let ray = Ray3::new(p(-5.0, 2.0, 0.0), dir(1.0, 0.0, 0.0)).unwrap();
+        let hits = bvh.cast_ray(&ray, fx(100.0));
+        let ids: Vec<usize> = hits.iter().map(|h| h.0).collect();
+        // Boxes 8 and 11 sit above the ray.
+        assert_eq!(ids, [6, 7, 9, 10]);
+        assert!(hits.windows(2).all(|w| w[0].1 <= w[1].1));
+        assert_eq!(hits[0].1, fx(4.5));
+        assert_eq!(bvh.cast_ray(&ray, fx(4.25)), Vec::new());
+    }
+
+    #[test]
+    fn incremental_edits_match_a_rebuild() {
+        let boxes = grid();
+        let mut bvh = Bvh::new();
+        for b in &boxes {
+            bvh.insert(*b);
+        }
+        let rebuilt = Bvh::build(&boxes);
+        let window = Aabb::new(p(-1.0, -1.0, -1.0), p(6.0, 6.0, 1.0)).unwrap();
+        assert_eq!(bvh.query_aabb(&window), rebuilt.query_aabb(&window));
+
+        assert!(bvh.remove(14) && bvh.remove(3));
+        assert!(!bvh.remove(3) && !bvh.remove(99));
+        assert_eq!((bvh.len(), bvh.get(3)), (34, None));
+        assert!(!bvh.query_aabb(&window).contains(&14));
+        // Freed ids come back smallest first.
+        assert_eq!(bvh.insert(cube(20.0, 20.0, 0.0, 0.5)), 3);
+        assert_eq!(bvh.insert(cube(21.0, 20.0, 0.0, 0.5)), 14);
+        assert_eq!(bvh.insert(cube(22.0, 20.0, 0.0, 0.5)), 36);
+
+        let far = cube(-30.0, 0.0, 0.0, 1.0);
+        assert!(bvh.refit(0, far));
+        assert_eq!(bvh.get(0), Some(far));
+        assert_eq!(bvh.query_aabb(&cube(-30.0, 0.0, 0.0, 0.1)), [0]);
+        assert!(!bvh.query_aabb(&window).contains(&0));
+        assert_eq!(bvh.bounds().unwrap().min().0.x, fx(-31.0));
+
+        for id in 0..37 {
+            bvh.remove(id);
+        }
+        assert!(bvh.is_empty() && bvh.query_aabb(&window).is_empty());
+    }
+}
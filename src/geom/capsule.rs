@@ -0,0 +1,46 @@
+use super::{GeomError, Segment3};
+use crate::scalar::RealScalar;
+use crate::Point3;
+
+/// All points within `radius` of a core segment.
+///
+/// A degenerate segment makes the capsule a sphere.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Capsule<T: RealScalar> {
+    segment: Segment3<T>,
+    radius: T,
+}
+
+impl<T: RealScalar> Capsule<T> {
+    /// Capsule around the segment `start..end`.
+    ///
+    /// Returns [`GeomError::NegativeRadius`] if `radius < 0`.
+    pub fn new(start: Point3<T>, end: Point3<T>, radius: T) -> Result<Self, GeomError> {
+        if radius < T::ZERO {
+            return Err(GeomError::NegativeRadius);
+        }
+        Ok(Self { segment: Segment3::new(start, end), radius })
+    }
+
+    /// Core segment.
+    pub fn segment(&self) -> Segment3<T> {
+        self.segment
+    }
+
+    pub fn radius(&self) -> T {
+        self.radius
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scalar::{DFix64, Scalar};
+
+    #[test]
+    fn rejects_negative_radius() {
+        let (a, b) = (Point3::new(DFix64::ZERO, DFix64::ZERO, DFix64::ZERO), Point3::new(DFix64::ONE, DFix64::ZERO, DFix64::ZERO));
+        assert_eq!(Capsule::new(a, b, DFix64::ONE).unwrap().segment(), Segment3::new(a, b));
+        assert_eq!(Capsule::new(a, b, -DFix64::ONE), Err(GeomError::NegativeRadius));
+    }
+}
@@ -0,0 +1,265 @@
+//! Closest-point and distance queries.
+//!
+//! Solid shapes (boxes, spheres) return the query point itself when it lies
+//! inside them; surfaces (planes, triangles) and curves (segments) always
+//! project onto the shape. Degenerate segments and triangles are handled by
+//! explicit branches rather than divisions by zero.
+
+use super::{unit, Aabb, Obb, Plane, Segment3, Sphere, Triangle};
+use crate::scalar::RealScalar;
+use crate::{Point3, Vec3};
+
+/// Shapes that can report their point nearest to a query point.
+pub trait ClosestPoint<T: RealScalar> {
+    /// Point of the shape nearest to `point`.
+    fn closest_point(&self, point: Point3<T>) -> Point3<T>;
+
+    /// Squared distance from `point` to the shape.
+    fn distance_sq(&self, point: Point3<T>) -> T {
+        (self.closest_point(point) - point).0.length_sq()
+    }
+}
+
+/// `num / den`, or zero when `den` is zero.
+fn ratio<T: RealScalar>(num: T, den: T) -> T {
+    if den == T::ZERO {
+        T::ZERO
+    } else {
+        num / den
+    }
+}
+
+impl<T: RealScalar> Segment3<T> {
+    /// Parameter in `[0, 1]` of the point nearest to `point`; `0` for a
+    /// degenerate segment.
+    pub fn closest_param(&self, point: Point3<T>) -> T {
+        let d = self.delta().0;
+        ratio((point - self.start).0.dot(&d), d.length_sq()).clamp(T::ZERO, T::ONE)
+    }
+
+    /// Parameters `(s, t)` of the closest points `self.at(s)` and
+    /// `other.at(t)` (Ericson, *Real-Time Collision Detection* §5.1.9).
+    ///
+    /// Parallel segments take `s = 0` before clamping, so of the many
+    /// closest pairs the one nearest to `self.start` is reported.
+    pub fn closest_params(&self, other: &Segment3<T>) -> (T, T) {
+        let (zero, one) = (T::ZERO, T::ONE);
+        let (d1, d2) = (self.delta().0, other.delta().0);
+        let r = (self.start - other.start).0;
+        let (len1, len2) = (d1.length_sq(), d2.length_sq());
+        let f = d2.dot(&r);
+        if len1 == zero {
+            return (zero, ratio(f, len2).clamp(zero, one));
+        }
+        let c = d1.dot(&r);
+        if len2 == zero {
+            return (ratio(-c, len1).clamp(zero, one), zero);
+        }
+        let bb = d1.dot(&d2);
+        let s = ratio(bb * f - c * len2, len1 * len2 - bb * bb).clamp(zero, one);
+        let t_num = bb * s + f;
+        if t_num < zero {
+            (ratio(-c, len1).clamp(zero, one), zero)
+        } else if t_num > len2 {
+            (ratio(bb - c, len1).clamp(zero, one), one)
+        } else {
+            (s, t_num / len2)
+        }
+    }
+
+    /// Closest points `(on self, on other)`; see [`closest_params`](Self::closest_params).
+    pub fn closest_points(&self, other: &Segment3<T>) -> (Point3<T>, Point3<T>) {
+        let (s, t) = self.closest_params(other);
+        (self.at(s), other.at(t))
+    }
+}
+
+impl<T: RealScalar> ClosestPoint<T> for Segment3<T> {
+    fn closest_point(&self, point: Point3<T>) -> Point3<T> {
+        self.at(self.closest_param(point))
+    }
+}
+
+impl<T: RealScalar> Triangle<T> {
+    /// Barycentric weights `[wa, wb, wc]` (summing to one) of the point of
+    /// the triangle nearest to `point` (Ericson §5.1.5). Degenerate
+    /// triangles fall back to their nearest edge.
+    pub fn closest_barycentric(&self, point: Point3<T>) -> [T; 3] {
+        let (zero, one) = (T::ZERO, T::ONE);
+        let (ab, ac) = ((self.b - self.a).0, (self.c - self.a).0);
+        let ap = (point - self.a).0;
+        let (d1, d2) = (ab.dot(&ap), ac.dot(&ap));
+        if d1 <= zero && d2 <= zero {
+            return [one, zero, zero];
+        }
+        let bp = (point - self.b).0;
+        let (d3, d4) = (ab.dot(&bp), ac.dot(&bp));
+        if d3 >= zero && d4 <= d3 {
+            return [zero, one, zero];
+        }
+        let vc = d1 * d4 - d3 * d2;
+        if vc <= zero && d1 >= zero && d3 <= zero {
+            let v = ratio(d1, d1 - d3);
+            return [one - v, v, zero];
+        }
+        let cp = (point - self.c).0;
+        let (d5, d6) = (ab.dot(&cp), ac.dot(&cp));
+        if d6 >= zero && d5 <= d6 {
+            return [zero, zero, one];
+        }
+        let vb = d5 * d2 - d1 * d6;
+        if vb <= zero && d2 >= zero && d6 <= zero {
+            let w = ratio(d2, d2 - d6);
+            return [one - w, zero, w];
+        }
+        let va = d3 * d6 - d5 * d4;
+        if va <= zero && d4 - d3 >= zero && d5 - d6 >= zero {
+            let w = ratio(d4 - d3, (d4 - d3) + (d5 - d6));
+            return [zero, one - w, w];
+        }
+        let denom = va + vb + vc;
+        if denom == zero {
+            return self.nearest_edge_barycentric(point);
+        }
+        let (v, w) = (vb / denom, vc / denom);
+        [one - v - w, v, w]
+    }
+
+    fn nearest_edge_barycentric(&self, point: Point3<T>) -> [T; 3] {
+        let edges = [(self.a, self.b, 0, 1), (self.b, self.c, 1, 2), (self.c, self.a, 2, 0)];
+        let mut best = ([T::ONE, T::ZERO, T::ZERO], (point - self.a).0.length_sq());
+        for (start, end, i, j) in edges {
+            let seg = Segment3::new(start, end);
+            let t = seg.closest_param(point);
+            let dist = (point - seg.at(t)).0.length_sq();
+            if dist < best.1 {
+                let mut w = [T::ZERO; 3];
+                (w[i], w[j]) = (T::ONE - t, t);
+                best = (w, dist);
+            }
+        }
+        best.0
+    }
+
+    /// Point with barycentric weights `[wa, wb, wc]`.
+    pub fn from_barycentric(&self, weights: [T; 3]) -> Point3<T> {
+        Point3(self.a.0 * weights[0] + self.b.0 * weights[1] + self.c.0 * weights[2])
+    }
+}
+
+impl<T: RealScalar> ClosestPoint<T> for Triangle<T> {
+    fn closest_point(&self, point: Point3<T>) -> Point3<T> {
+        self.from_barycentric(self.closest_barycentric(point))
+    }
+}
+
+/// Clamps `p` into `[lo, hi]` componentwise.
+pub(crate) fn clamp_box<T: RealScalar>(p: Vec3<T>, lo: Vec3<T>, hi: Vec3<T>) -> Vec3<T> {
+    Vec3::new(p.x.clamp(lo.x, hi.x), p.y.clamp(lo.y, hi.y), p.z.clamp(lo.z, hi.z))
+}
+
+impl<T: RealScalar> ClosestPoint<T> for Aabb<T> {
+    fn closest_point(&self, point: Point3<T>) -> Point3<T> {
+        Point3(clamp_box(point.0, self.min().0, self.max().0))
+    }
+}
+
+impl<T: RealScalar> ClosestPoint<T> for Obb<T> {
+    /// Clamps in the box's local frame. Points inside are returned as given,
+    /// without a round trip through the rotation.
+    fn closest_point(&self, point: Point3<T>) -> Point3<T> {
+        if self.contains_point(point) {
+            return point;
+        }
+        let h = self.half_extents();
+        self.to_world(clamp_box(self.to_local(point), -h, h))
+    }
+}
+
+impl<T: RealScalar> ClosestPoint<T> for Plane<T> {
+    fn closest_point(&self, point: Point3<T>) -> Point3<T> {
+        point + self.normal() * -self.signed_distance(point)
+    }
+
+    fn distance_sq(&self, point: Point3<T>) -> T {
+        let d = self.signed_distance(point);
+        d * d
+    }
+}
+
+impl<T: RealScalar> ClosestPoint<T> for Sphere<T> {
+    fn closest_point(&self, point: Point3<T>) -> Point3<T> {
+        if self.contains_point(point) {
+            return point;
+        }
+        match unit((point - self.center()).0) {
+            Ok(n) => self.center() + n * self.radius(),
+            Err(_) => point,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geom::test_support::{fx, p};
+    use crate::scalar::{DFix64, Scalar};
+    use crate::{Direction3, Mat3};
+
+    fn z() -> DFix64 {
+        DFix64::ZERO
+    }
+
+    #[test]
+    fn segment_params_and_pairs() {
+        let s = Segment3::new(p(0.0, 0.0, 0.0), p(4.0, 0.0, 0.0));
+        assert_eq!(s.closest_param(p(1.0, 5.0, 0.0)), fx(0.25));
+        assert_eq!(s.closest_param(p(-3.0, 1.0, 0.0)), DFix64::ZERO);
+        assert_eq!(s.distance_sq(p(6.0, 1.0, 0.0)), fx(5.0));
+        assert_eq!(Segment3::new(s.start, s.start).closest_param(p(1.0, 0.0, 0.0)), DFix64::ZERO);
+
+        let crossing = Segment3::new(p(2.0, 1.0, -1.0), p(2.0, 1.0, 1.0));
+        assert_eq!(s.closest_params(&crossing), (fx(0.5), fx(0.5)));
+        assert_eq!(s.closest_points(&crossing), (p(2.0, 0.0, 0.0), p(2.0, 1.0, 0.0)));
+        let parallel = Segment3::new(p(1.0, 1.0, 0.0), p(3.0, 1.0, 0.0));
+        assert_eq!(s.closest_points(&parallel), (p(1.0, 0.0, 0.0), p(1.0, 1.0, 0.0)));
+    }
+
+    #[test]
+    fn triangle_regions() {
+        let tri = Triangle::new(p(0.0, 0.0, 0.0), p(4.0, 0.0, 0.0), p(0.0, 4.0, 0.0));
+        let (o, z, h) = (DFix64::ONE, DFix64::ZERO, fx(0.5));
+        assert_eq!(tri.closest_barycentric(p(1.0, 1.0, 3.0)), [h, fx(0.25), fx(0.25)]);
+        assert_eq!(tri.closest_point(p(1.0, 1.0, 3.0)), p(1.0, 1.0, 0.0));
+        assert_eq!(tri.distance_sq(p(1.0, 1.0, 3.0)), fx(9.0));
+        assert_eq!(tri.closest_barycentric(p(-1.0, -1.0, 0.0)), [o, z, z]);
+        assert_eq!(tri.closest_barycentric(p(2.0, -2.0, 0.0)), [h, h, z]);
+        assert_eq!(tri.closest_barycentric(p(3.0, 3.0, 0.0)), [z, h, h]);
+        assert_eq!(tri.distance_sq(p(3.0, 3.0, 0.0)), fx(2.0));
+
+        let flat = Triangle::new(p(0.0, 0.0, 0.0), p(2.0, 0.0, 0.0), p(4.0, 0.0, 0.0));
+        assert_eq!(flat.closest_point(p(3.0, 1.0, 0.0)), p(3.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn solids_and_planes() {
+        let aabb = Aabb::new(p(-1.0, -1.0, -1.0), p(1.0, 1.0, 1.0)).unwrap();
+        assert_eq!(aabb.closest_point(p(3.0, 0.5, -2.0)), p(1.0, 0.5, -1.0));
+        assert_eq!(aabb.distance_sq(p(3.0, 0.5, -2.0)), fx(5.0));
+        assert_eq!(aabb.closest_point(p(0.5, 0.0, 0.0)), p(0.5, 0.0, 0.0));
+
+        let rot = Mat3::from_cols(Vec3::new(z(), fx(1.0), z()), Vec3::new(-fx(1.0), z(), z()), Vec3::new(z(), z(), fx(1.0)));
+        let obb = Obb::new(p(1.0, 1.0, 0.0), Vec3::new(fx(2.0), fx(0.5), fx(1.0)), rot).unwrap();
+        assert_eq!(obb.closest_point(p(1.0, 5.0, 0.0)), p(1.0, 3.0, 0.0));
+        assert_eq!(obb.closest_point(p(1.25, 2.5, 0.5)), p(1.25, 2.5, 0.5));
+
+        let ground = Plane::from_point_normal(p(0.0, 1.0, 0.0), Direction3::new(z(), fx(1.0), z())).unwrap();
+        assert_eq!(ground.closest_point(p(3.0, 4.0, 5.0)), p(3.0, 1.0, 5.0));
+        assert_eq!(ground.distance_sq(p(3.0, -2.0, 5.0)), fx(9.0));
+
+        let ball = Sphere::new(p(0.0, 0.0, 0.0), fx(2.0)).unwrap();
+        assert_eq!(ball.closest_point(p(0.0, 0.0, 5.0)), p(0.0, 0.0, 2.0));
+        assert_eq!(ball.distance_sq(p(0.0, 0.0, 5.0)), fx(9.0));
+        assert_eq!(ball.closest_point(p(1.0, 0.0, 0.0)), p(1.0, 0.0, 0.0));
+    }
+}
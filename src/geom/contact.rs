@@ -0,0 +1,687 @@
+//! Overlap tests and contact manifolds between primitive pairs.
+//!
+//! Conventions shared by every pair `a.contact(&b)`:
+//!
+//! * The normal is unit length and points from `a` towards `b`: moving `b`
+//!   along it by the depth separates the shapes.
+//! * Touching shapes (depth zero) overlap.
+//! * Contact points sit on `b`'s side of the overlap; `point + normal * depth`
+//!   is the matching point on `a`'s side.
+//! * A manifold holds at most four points, in a deterministic order.
+//! * Coincident centers and other degenerate configurations resolve to fixed
+//!   fallback normals (`+Y` unless stated); see
+//!   [determinism](super#determinism).
+
+use super::closest::{clamp_box, ClosestPoint};
+use super::raycast::slab;
+use super::support::SupportMap;
+use super::{axis_vec, unit, Aabb, Capsule, Obb, Segment3, Sphere, Triangle};
+use crate::scalar::RealScalar;
+use crate::{Direction3, Point3, Vec3};
+
+const MAX_POINTS: usize = 4;
+
+/// One point of a contact manifold.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ContactPoint<T: RealScalar> {
+    pub point: Point3<T>,
+    /// Penetration depth along the contact normal; zero when touching.
+    pub depth: T,
+}
+
+/// Contact manifold between two overlapping shapes: a shared normal and one
+/// to four points.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Contact<T: RealScalar> {
+    normal: Direction3<T>,
+    points: [ContactPoint<T>; MAX_POINTS],
+    len: usize,
+}
+
+impl<T: RealScalar> Contact<T> {
+    pub(super) fn new(normal: Direction3<T>, first: ContactPoint<T>) -> Self {
+        Self { normal, points: [first; MAX_POINTS], len: 1 }
+    }
+
+    /// Appends `point` unless the manifold is full or already holds it.
+    fn push(&mut self, point: ContactPoint<T>) {
+        if self.len < MAX_POINTS && self.points().iter().all(|p| p.point != point.point) {
+            self.points[self.len] = point;
+            self.len += 1;
+        }
+    }
+
+    /// Unit normal from the first shape towards the second.
+    pub fn normal(&self) -> Direction3<T> {
+        self.normal
+    }
+
+    pub fn points(&self) -> &[ContactPoint<T>] {
+        &self.points[..self.len]
+    }
+
+    /// Deepest penetration over all points.
+    pub fn depth(&self) -> T {
+        self.points().iter().fold(T::ZERO, |d, p| d.max(p.depth))
+    }
+
+    /// The same contact seen from the second shape: the normal is reversed
+    /// and each point moves to the other side of the overlap.
+    pub fn flipped(&self) -> Self {
+        let n = self.normal;
+        Self { normal: -n, points: self.points.map(|p| ContactPoint { point: p.point + n * p.depth, depth: p.depth }), len: self.len }
+    }
+}
+
+/// Builds a manifold from `points` in order, or `None` if there are none.
+fn manifold<T: RealScalar>(normal: Direction3<T>, points: impl IntoIterator<Item = ContactPoint<T>>) -> Option<Contact<T>> {
+    let mut points = points.into_iter();
+    let mut contact = Contact::new(normal, points.next()?);
+    points.for_each(|p| contact.push(p));
+    Some(contact)
+}
+
+/// Overlap and contact generation against shapes of type `Rhs`.
+pub trait Collide<T: RealScalar, Rhs = Self> {
+    /// Returns the contact manifold, or `None` if the shapes are disjoint.
+    fn contact(&self, other: &Rhs) -> Option<Contact<T>>;
+
+    /// Returns `true` if the shapes overlap or touch.
+    fn intersects(&self, other: &Rhs) -> bool {
+        self.contact(other).is_some()
+    }
+}
+
+fn up<T: RealScalar>() -> Direction3<T> {
+    Direction3::new(T::ZERO, T::ONE, T::ZERO)
+}
+
+/// Contact between the balls `(a, ra)` and `(b, rb)`.
+fn balls<T: RealScalar>(a: Point3<T>, ra: T, b: Point3<T>, rb: T) -> Option<Contact<T>> {
+    let d = (b - a).0;
+    let (dist_sq, reach) = (d.length_sq(), ra + rb);
+    if dist_sq > reach * reach {
+        return None;
+    }
+    let normal = unit(d).unwrap_or_else(|_| up());
+    Some(Contact::new(normal, ContactPoint { point: b + normal * -rb, depth: reach - dist_sq.sqrt() }))
+}
+
+impl<T: RealScalar> Collide<T> for Sphere<T> {
+    fn contact(&self, other: &Sphere<T>) -> Option<Contact<T>> {
+        balls(self.center(), self.radius(), other.center(), other.radius())
+    }
+}
+
+/// Nearest face of `[lo, hi]` to the interior point `p`, as `(axis, outward
+/// sign, distance)`. Ties go to the lowest axis, then the minimum face.
+fn nearest_face<T: RealScalar>(p: Vec3<T>, lo: Vec3<T>, hi: Vec3<T>) -> (usize, T, T) {
+    let (p, lo, hi) = (p.to_array(), lo.to_array(), hi.to_array());
+    let mut best = (0, -T::ONE, p[0] - lo[0]);
+    for axis in 0..3 {
+        for (sign, dist) in [(-T::ONE, p[axis] - lo[axis]), (T::ONE, hi[axis] - p[axis])] {
+            if dist < best.2 {
+                best = (axis, sign, dist);
+            }
+        }
+    }
+    best
+}
+
+impl<T: RealScalar> Collide<T, Aabb<T>> for Sphere<T> {
+    /// A center inside the box pushes out through the nearest face.
+    fn contact(&self, aabb: &Aabb<T>) -> Option<Contact<T>> {
+        let (c, r) = (self.center(), self.radius());
+        let q = aabb.closest_point(c);
+        if q != c {
+            let d = (q - c).0;
+            let dist_sq = d.length_sq();
+            if dist_sq > r * r {
+                return None;
+            }
+            let normal = unit(d).unwrap_or_else(|_| up());
+            return Some(Contact::new(normal, ContactPoint { point: q, depth: r - dist_sq.sqrt() }));
+        }
+        let (axis, sign, dist) = nearest_face(c.0, aabb.min().0, aabb.max().0);
+        let mut face = c.0.to_array();
+        face[axis] = if sign > T::ZERO { aabb.max().0.to_array()[axis] } else { aabb.min().0.to_array()[axis] };
+        let point = Point3(Vec3::from(face));
+        Some(Contact::new(Direction3(axis_vec(axis, -sign)), ContactPoint { point, depth: r + dist }))
+    }
+}
+
+impl<T: RealScalar> Collide<T, Sphere<T>> for Aabb<T> {
+    fn contact(&self, sphere: &Sphere<T>) -> Option<Contact<T>> {
+        sphere.contact(self).map(|c| c.flipped())
+    }
+}
+
+impl<T: RealScalar> Collide<T> for Aabb<T> {
+    /// Separates along the axis of least overlap (ties to the lowest axis);
+    /// the points are the corners of the overlap box's face on `other`'s
+    /// side, all at the same depth.
+    fn contact(&self, other: &Aabb<T>) -> Option<Contact<T>> {
+        let (amin, amax) = (self.min().0.to_array(), self.max().0.to_array());
+        let (bmin, bmax) = (other.min().0.to_array(), other.max().0.to_array());
+        let (mut lo, mut hi) = ([T::ZERO; 3], [T::ZERO; 3]);
+        let mut best: Option<(usize, T)> = None;
+        for axis in 0..3 {
+            lo[axis] = amin[axis].max(bmin[axis]);
+            hi[axis] = amax[axis].min(bmax[axis]);
+            let overlap = hi[axis] - lo[axis];
+            if overlap < T::ZERO {
+                return None;
+            }
+            if best.map_or(true, |(_, o)| overlap < o) {
+                best = Some((axis, overlap));
+            }
+        }
+        let (axis, depth) = best?;
+        let sign = if bmin[axis] + bmax[axis] >= amin[axis] + amax[axis] { T::ONE } else { -T::ONE };
+        let face = if sign > T::ZERO { lo[axis] } else { hi[axis] };
+        let (u, v) = ((axis + 1) % 3, (axis + 2) % 3);
+        let corners = [(lo[u], lo[v]), (hi[u], lo[v]), (hi[u], hi[v]), (lo[u], hi[v])].map(|(cu, cv)| {
+            let mut p = [T::ZERO; 3];
+            (p[axis], p[u], p[v]) = (face, cu, cv);
+            ContactPoint { point: Point3(Vec3::from(p)), depth }
+        });
+        manifold(Direction3(axis_vec(axis, sign)), corners)
+    }
+}
+
+impl<T: RealScalar> Collide<T> for Capsule<T> {
+    /// Closest points of the core segments; exactly parallel cores add the
+    /// ends of their overlapping stretch.
+    fn contact(&self, other: &Capsule<T>) -> Option<Contact<T>> {
+        let (sa, sb) = (self.segment(), other.segment());
+        let (ra, rb) = (self.radius(), other.radius());
+        let (pa, pb) = sa.closest_points(&sb);
+        let mut contact = balls(pa, ra, pb, rb)?;
+        if sa.delta().0.cross(&sb.delta().0) == Vec3::splat(T::ZERO) {
+            let n = contact.normal();
+            let pairs = [
+                (sa.start, sb.closest_point(sa.start)),
+                (sa.end, sb.closest_point(sa.end)),
+                (sa.closest_point(sb.start), sb.start),
+                (sa.closest_point(sb.end), sb.end),
+            ];
+            for (p, q) in pairs {
+                let dist_sq = (q - p).0.length_sq();
+                if dist_sq <= (ra + rb) * (ra + rb) {
+                    contact.push(ContactPoint { point: q + n * -rb, depth: ra + rb - dist_sq.sqrt() });
+                }
+            }
+        }
+        Some(contact)
+    }
+}
+
+impl<T: RealScalar> Collide<T, Triangle<T>> for Sphere<T> {
+    /// Two-sided. A center lying on the triangle pushes the sphere out of the
+    /// front face.
+    fn contact(&self, triangle: &Triangle<T>) -> Option<Contact<T>> {
+        let (c, r) = (self.center(), self.radius());
+        let q = triangle.closest_point(c);
+        let d = (q - c).0;
+        let dist_sq = d.length_sq();
+        if dist_sq > r * r {
+            return None;
+        }
+        let normal = unit(d).unwrap_or_else(|_| triangle.normal().map_or_else(up, |n| -n));
+        Some(Contact::new(normal, ContactPoint { point: q, depth: r - dist_sq.sqrt() }))
+    }
+}
+
+impl<T: RealScalar> Collide<T, Sphere<T>> for Triangle<T> {
+    fn contact(&self, sphere: &Sphere<T>) -> Option<Contact<T>> {
+        sphere.contact(self).map(|c| c.flipped())
+    }
+}
+
+/// Half-length of the projection of `obb` onto `axis`.
+fn obb_radius<T: RealScalar>(obb: &Obb<T>, axis: Vec3<T>) -> T {
+    let h = obb.half_extents().to_array();
+    (0..3).fold(T::ZERO, |r, i| r + h[i] * axis.dot(&obb.axis(i).0).abs())
+}
+
+/// Edge of `obb` parallel to local axis `i` that lies furthest along `dir`;
+/// ties go to the positive side.
+fn support_edge<T: RealScalar>(obb: &Obb<T>, i: usize, dir: Vec3<T>) -> Segment3<T> {
+    let h = obb.half_extents().to_array();
+    let mut mid = obb.center().0;
+    for k in (0..3).filter(|&k| k != i) {
+        let ak = obb.axis(k).0;
+        mid = mid + ak * if ak.dot(&dir) < T::ZERO { -h[k] } else { h[k] };
+    }
+    let half = obb.axis(i).0 * h[i];
+    Segment3::new(Point3(mid - half), Point3(mid + half))
+}
+
+/// Separating axis of least overlap.
+#[derive(Copy, Clone)]
+enum Axis {
+    /// Face normal `i` of the first shape.
+    FaceA(usize),
+    /// Face normal `i` of the second shape.
+    FaceB(usize),
+    /// Cross product of edge directions `i` of the first shape and `j` of
+    /// the second.
+    Edge(usize, usize),
+}
+
+/// Running minimum over candidate axes. Edge axes win only when they beat
+/// the best face axis by a margin, which keeps resting contacts on stable
+/// face manifolds despite rounding in the normalized cross products.
+struct AxisSearch<T: RealScalar> {
+    face: Option<(Axis, Direction3<T>, T)>,
+    edge: Option<(Axis, Direction3<T>, T)>,
+}
+
+impl<T: RealScalar> AxisSearch<T> {
+    fn new() -> Self {
+        Self { face: None, edge: None }
+    }
+
+    /// Records an oriented candidate; returns `false` on a separating axis.
+    fn offer(&mut self, axis: Axis, normal: Direction3<T>, overlap: T) -> bool {
+        if overlap < T::ZERO {
+            return false;
+        }
+        let slot = if matches!(axis, Axis::Edge(..)) { &mut self.edge } else { &mut self.face };
+        if slot.map_or(true, |(_, _, o)| overlap < o) {
+            *slot = Some((axis, normal, overlap));
+        }
+        true
+    }
+
+    fn best(self) -> Option<(Axis, Direction3<T>, T)> {
+        match (self.face, self.edge) {
+            (Some(face), Some(edge)) => {
+                let margin = face.2 * T::from_f32(1.0 / 32.0) + T::epsilon();
+                Some(if edge.2 < face.2 - margin { edge } else { face })
+            }
+            (face, edge) => face.or(edge),
+        }
+    }
+}
+
+/// Normalized `a × b`, or `None` when the edges are (nearly) parallel.
+fn edge_axis<T: RealScalar>(a: Vec3<T>, b: Vec3<T>) -> Option<Vec3<T>> {
+    let cross = a.cross(&b);
+    if cross.length_sq() <= T::epsilon() {
+        return None;
+    }
+    unit(cross).ok().map(|n| n.0)
+}
+
+/// 15-axis separating-axis test; normals are oriented from `a` to `b`.
+fn obb_sat<T: RealScalar>(a: &Obb<T>, b: &Obb<T>) -> Option<(Axis, Direction3<T>, T)> {
+    let d = (b.center() - a.center()).0;
+    let mut search = AxisSearch::new();
+    let mut offer = |axis: Axis, l: Vec3<T>| {
+        let dist = l.dot(&d);
+        let normal = Direction3(if dist < T::ZERO { -l } else { l });
+        search.offer(axis, normal, obb_radius(a, l) + obb_radius(b, l) - dist.abs())
+    };
+    for i in 0..3 {
+        if !offer(Axis::FaceA(i), a.axis(i).0) {
+            return None;
+        }
+    }
+    for j in 0..3 {
+        if !offer(Axis::FaceB(j), b.axis(j).0) {
+            return None;
+        }
+    }
+    for i in 0..3 {
+        for j in 0..3 {
+            if let Some(l) = edge_axis(a.axis(i).0, b.axis(j).0) {
+                if !offer(Axis::Edge(i, j), l) {
+                    return None;
+                }
+            }
+        }
+    }
+    search.best()
+}
+
+/// Keeps the part of `poly` with `normal · p <= offset` (Sutherland–Hodgman).
+fn clip<T: RealScalar>(poly: &[Point3<T>], normal: Vec3<T>, offset: T) -> Vec<Point3<T>> {
+    let mut out = Vec::with_capacity(poly.len() + 1);
+    for (k, &p) in poly.iter().enumerate() {
+        let q = poly[(k + 1) % poly.len()];
+        let (dp, dq) = (normal.dot(&p.0) - offset, normal.dot(&q.0) - offset);
+        if dp <= T::ZERO {
+            out.push(p);
+        }
+        if (dp <= T::ZERO) != (dq <= T::ZERO) {
+            out.push(p + (q - p) * (dp / (dp - dq)));
+        }
+    }
+    out
+}
+
+/// Reduces a clipped polygon to at most four points: the deepest, the one
+/// furthest from it, then the two spanning the largest areas on either side.
+/// Ties go to the earlier point.
+fn reduce<T: RealScalar>(normal: Direction3<T>, points: &[ContactPoint<T>]) -> Option<Contact<T>> {
+    if points.len() <= MAX_POINTS {
+        return manifold(normal, points.iter().copied());
+    }
+    let argmax = |key: &dyn Fn(&ContactPoint<T>) -> T| {
+        let mut best = 0;
+        for k in 1..points.len() {
+            if key(&points[k]) > key(&points[best]) {
+                best = k;
+            }
+        }
+        points[best]
+    };
+    let first = argmax(&|p| p.depth);
+    let second = argmax(&|p| (p.point - first.point).0.length_sq());
+    let edge = (second.point - first.point).0;
+    let area = |p: &ContactPoint<T>| edge.cross(&(p.point - first.point).0).dot(&normal.0);
+    let third = argmax(&area);
+    let fourth = argmax(&|p| -area(p));
+    manifold(normal, [first, second, third, fourth])
+}
+
+/// Face contact with face `i` of `reference` (normal `n`, pointing towards
+/// `incident`): the incident face is clipped to the reference face's sides.
+fn face_contact<T: RealScalar>(reference: &Obb<T>, i: usize, incident: &Obb<T>, n: Direction3<T>, depth: T) -> Contact<T> {
+    let h_ref = reference.half_extents().to_array();
+    let face_center = reference.center() + n * h_ref[i];
+
+    let dots = [0, 1, 2].map(|j| incident.axis(j).0.dot(&n.0));
+    let mut j = 0;
+    for k in 1..3 {
+        if dots[k].abs() > dots[j].abs() {
+            j = k;
+        }
+    }
+    let h_inc = incident.half_extents().to_array();
+    let (u, v) = ((j + 1) % 3, (j + 2) % 3);
+    let face_sign = if dots[j] > T::ZERO { -T::ONE } else { T::ONE };
+    let mut poly: Vec<Point3<T>> = [(T::ONE, T::ONE), (-T::ONE, T::ONE), (-T::ONE, -T::ONE), (T::ONE, -T::ONE)]
+        .iter()
+        .map(|&(su, sv)| {
+            let mut local = [T::ZERO; 3];
+            (local[j], local[u], local[v]) = (face_sign * h_inc[j], su * h_inc[u], sv * h_inc[v]);
+            incident.to_world(Vec3::from(local))
+        })
+        .collect();
+
+    for k in [(i + 1) % 3, (i + 2) % 3] {
+        let side = reference.axis(k).0;
+        let c = side.dot(&reference.center().0);
+        poly = clip(&poly, side, c + h_ref[k]);
+        poly = clip(&poly, -side, h_ref[k] - c);
+    }
+    let points: Vec<ContactPoint<T>> = poly
+        .into_iter()
+        .filter_map(|p| {
+            let depth = -(p - face_center).0.dot(&n.0);
+            (depth >= T::ZERO).then_some(ContactPoint { point: p, depth })
+        })
+        .collect();
+    reduce(n, &points).unwrap_or_else(|| Contact::new(n, ContactPoint { point: incident.support(-n), depth }))
+}
+
+impl<T: RealScalar> Collide<T> for Obb<T> {
+    /// Separating-axis test over the 15 face and edge axes. Face axes give a
+    /// clipped face manifold of up to four points; edge axes give the
+    /// closest points of the two supporting edges.
+    fn contact(&self, other: &Obb<T>) -> Option<Contact<T>> {
+        let (axis, normal, depth) = obb_sat(self, other)?;
+        Some(match axis {
+            Axis::FaceA(i) => face_contact(self, i, other, normal, depth),
+            Axis::FaceB(j) => face_contact(other, j, self, -normal, depth).flipped(),
+            Axis::Edge(i, j) => {
+                let (ea, eb) = (support_edge(self, i, normal.0), support_edge(other, j, -normal.0));
+                Contact::new(normal, ContactPoint { point: ea.closest_points(&eb).1, depth })
+            }
+        })
+    }
+
+    fn intersects(&self, other: &Obb<T>) -> bool {
+        obb_sat(self, other).is_some()
+    }
+}
+
+/// Closest points `(on seg, on box)` of a segment and a solid box.
+fn segment_obb_closest<T: RealScalar>(seg: &Segment3<T>, obb: &Obb<T>) -> (Point3<T>, Point3<T>) {
+    let h = obb.half_extents();
+    let local = Segment3::new(Point3(obb.to_local(seg.start)), Point3(obb.to_local(seg.end)));
+    if let Some((t, _)) = slab(local.start.0, local.delta().0, -h, h, T::ONE) {
+        let p = seg.at(t);
+        return (p, p);
+    }
+    let inside = |p: Point3<T>| Point3(clamp_box(p.0, -h, h));
+    let mut best = (local.start, inside(local.start));
+    let mut consider = |p: Point3<T>, q: Point3<T>| {
+        if (q - p).0.length_sq() < (best.1 - best.0).0.length_sq() {
+            best = (p, q);
+        }
+    };
+    consider(local.end, inside(local.end));
+    let h = h.to_array();
+    for axis in 0..3 {
+        let (u, v) = ((axis + 1) % 3, (axis + 2) % 3);
+        for (su, sv) in [(T::ONE, T::ONE), (-T::ONE, T::ONE), (-T::ONE, -T::ONE), (T::ONE, -T::ONE)] {
+            let (mut a, mut b) = ([T::ZERO; 3], [T::ZERO; 3]);
+            (a[axis], a[u], a[v]) = (-h[axis], su * h[u], sv * h[v]);
+            (b[axis], b[u], b[v]) = (h[axis], su * h[u], sv * h[v]);
+            let edge = Segment3::new(Point3(Vec3::from(a)), Point3(Vec3::from(b)));
+            let (p, q) = local.closest_points(&edge);
+            consider(p, q);
+        }
+    }
+    (obb.to_world(best.0 .0), obb.to_world(best.1 .0))
+}
+
+/// Capsule whose core segment touches the box: separating-axis search over
+/// the box faces and the core × box-axis directions.
+fn capsule_obb_penetration<T: RealScalar>(seg: &Segment3<T>, r: T, obb: &Obb<T>) -> Option<Contact<T>> {
+    let c = obb.center().0;
+    let mut search = AxisSearch::new();
+    let mut offer = |axis: Axis, l: Vec3<T>| {
+        let (a0, a1, cb, rb) = (l.dot(&seg.start.0), l.dot(&seg.end.0), l.dot(&c), obb_radius(obb, l));
+        if a0 + a1 <= cb + cb {
+            search.offer(axis, Direction3(l), a0.max(a1) + r - (cb - rb))
+        } else {
+            search.offer(axis, Direction3(-l), cb + rb - (a0.min(a1) - r))
+        }
+    };
+    for i in 0..3 {
+        if !offer(Axis::FaceB(i), obb.axis(i).0) {
+            return None;
+        }
+    }
+    for i in 0..3 {
+        if let Some(l) = edge_axis(seg.delta().0, obb.axis(i).0) {
+            if !offer(Axis::Edge(0, i), l) {
+                return None;
+            }
+        }
+    }
+    let (axis, n, depth) = search.best()?;
+    if let Axis::Edge(_, i) = axis {
+        let edge = support_edge(obb, i, -n.0);
+        return Some(Contact::new(n, ContactPoint { point: seg.closest_points(&edge).1, depth }));
+    }
+    let floor = n.0.dot(&c) - obb_radius(obb, n.0);
+    let ends = [seg.start, seg.end].map(|e| {
+        let depth = n.0.dot(&e.0) + r - floor;
+        let on_face = e + n * (r - depth);
+        ContactPoint { point: obb.closest_point(on_face), depth }
+    });
+    manifold(n, ends.into_iter().filter(|p| p.depth >= T::ZERO))
+}
+
+impl<T: RealScalar> Collide<T, Obb<T>> for Capsule<T> {
+    /// Closest points of the core segment and the box; a core touching the
+    /// box falls back to a separating-axis search. Core ends within reach of
+    /// the box add points, so a capsule lying on a face gets two.
+    fn contact(&self, obb: &Obb<T>) -> Option<Contact<T>> {
+        let (seg, r) = (self.segment(), self.radius());
+        let (p, q) = segment_obb_closest(&seg, obb);
+        let d = (q - p).0;
+        let dist_sq = d.length_sq();
+        if dist_sq > r * r {
+            return None;
+        }
+        let Ok(normal) = unit(d) else {
+            return capsule_obb_penetration(&seg, r, obb);
+        };
+        let mut contact = Contact::new(normal, ContactPoint { point: q, depth: r - dist_sq.sqrt() });
+        for e in [seg.start, seg.end] {
+            let qe = obb.closest_point(e);
+            let de_sq = (qe - e).0.length_sq();
+            if de_sq > T::ZERO && de_sq <= r * r {
+                contact.push(ContactPoint { point: qe, depth: r - de_sq.sqrt() });
+            }
+        }
+        Some(contact)
+    }
+}
+
+impl<T: RealScalar> Collide<T, Capsule<T>> for Obb<T> {
+    fn contact(&self, capsule: &Capsule<T>) -> Option<Contact<T>> {
+        capsule.contact(self).map(|c| c.flipped())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geom::test_support::{dir, fx, p};
+    use crate::scalar::{DFix64, F64Det, Scalar};
+    use crate::Mat3;
+
+    fn points(c: &Contact<DFix64>) -> Vec<(Point3<DFix64>, DFix64)> {
+        c.points().iter().map(|cp| (cp.point, cp.depth)).collect()
+    }
+
+    fn unit_box() -> Obb<DFix64> {
+        Obb::from_aabb(&Aabb::new(p(-1.0, -1.0, -1.0), p(1.0, 1.0, 1.0)).unwrap())
+    }
+
+    #[test]
+    fn sphere_pairs() {
+        let a = Sphere::new(p(0.0, 0.0, 0.0), fx(1.0)).unwrap();
+        let b = Sphere::new(p(1.5, 0.0, 0.0), fx(1.0)).unwrap();
+        let c = a.contact(&b).unwrap();
+        assert_eq!(c.normal(), dir(1.0, 0.0, 0.0));
+        assert_eq!(points(&c), vec![(p(0.5, 0.0, 0.0), fx(0.5))]);
+        assert_eq!(c.flipped(), b.contact(&a).unwrap());
+
+        assert!(!a.intersects(&Sphere::new(p(3.0, 0.0, 0.0), fx(1.0)).unwrap()));
+        assert_eq!(a.contact(&Sphere::new(p(2.0, 0.0, 0.0), fx(1.0)).unwrap()).unwrap().depth(), DFix64::ZERO);
+        assert_eq!(a.contact(&a).unwrap().normal(), dir(0.0, 1.0, 0.0));
+    }
+
+    #[test]
+    fn sphere_against_aabb() {
+        let b = Aabb::new(p(-1.0, -1.0, -1.0), p(1.0, 1.0, 1.0)).unwrap();
+        let outside = Sphere::new(p(2.5, 0.0, 0.0), fx(2.0)).unwrap();
+        let c = outside.contact(&b).unwrap();
+        assert_eq!((c.normal(), points(&c)), (dir(-1.0, 0.0, 0.0), vec![(p(1.0, 0.0, 0.0), fx(0.5))]));
+        assert_eq!(b.contact(&outside), Some(c.flipped()));
+
+        let inside = Sphere::new(p(0.5, 0.25, 0.0), fx(0.25)).unwrap();
+        let c = inside.contact(&b).unwrap();
+        assert_eq!((c.normal(), points(&c)), (dir(-1.0, 0.0, 0.0), vec![(p(1.0, 0.25, 0.0), fx(0.75))]));
+        assert!(!Sphere::new(p(3.0, 0.0, 0.0), fx(1.0)).unwrap().intersects(&b));
+    }
+
+    #[test]
+    fn aabb_pair_uses_least_overlap_face() {
+        let a = Aabb::new(p(0.0, 0.0, 0.0), p(2.0, 2.0, 2.0)).unwrap();
+        let b = Aabb::new(p(1.5, 0.5, 0.5), p(3.0, 1.5, 1.5)).unwrap();
+        let c = a.contact(&b).unwrap();
+        assert_eq!(c.normal(), dir(1.0, 0.0, 0.0));
+        let depth = fx(0.5);
+        assert_eq!(points(&c), vec![(p(1.5, 0.5, 0.5), depth), (p(1.5, 1.5, 0.5), depth), (p(1.5, 1.5, 1.5), depth), (p(1.5, 0.5, 1.5), depth)]);
+        assert_eq!(b.contact(&a).unwrap().normal(), dir(-1.0, 0.0, 0.0));
+        assert!(!a.intersects(&Aabb::new(p(2.5, 0.0, 0.0), p(3.0, 1.0, 1.0)).unwrap()));
+    }
+
+    #[test]
+    fn capsule_pairs() {
+        let a = Capsule::new(p(0.0, 0.0, 0.0), p(4.0, 0.0, 0.0), fx(0.5)).unwrap();
+        let crossing = Capsule::new(p(2.0, 0.75, -2.0), p(2.0, 0.75, 2.0), fx(0.5)).unwrap();
+        let c = a.contact(&crossing).unwrap();
+        assert_eq!((c.normal(), points(&c)), (dir(0.0, 1.0, 0.0), vec![(p(2.0, 0.25, 0.0), fx(0.25))]));
+
+        let parallel = Capsule::new(p(1.0, 0.75, 0.0), p(5.0, 0.75, 0.0), fx(0.5)).unwrap();
+        let c = a.contact(&parallel).unwrap();
+        assert_eq!(points(&c), vec![(p(1.0, 0.25, 0.0), fx(0.25)), (p(4.0, 0.25, 0.0), fx(0.25))]);
+        assert!(!a.intersects(&Capsule::new(p(0.0, 2.0, 0.0), p(4.0, 2.0, 0.0), fx(0.5)).unwrap()));
+    }
+
+    #[test]
+    fn sphere_against_triangle() {
+        let tri = Triangle::new(p(0.0, 0.0, 0.0), p(4.0, 0.0, 0.0), p(0.0, 4.0, 0.0));
+        let above = Sphere::new(p(1.0, 1.0, 0.5), fx(1.0)).unwrap();
+        let c = above.contact(&tri).unwrap();
+        assert_eq!((c.normal(), points(&c)), (dir(0.0, 0.0, -1.0), vec![(p(1.0, 1.0, 0.0), fx(0.5))]));
+        assert_eq!(tri.contact(&above), Some(c.flipped()));
+
+        let by_edge = Sphere::new(p(2.0, -0.5, 0.0), fx(1.0)).unwrap().contact(&tri).unwrap();
+        assert_eq!((by_edge.normal(), by_edge.depth()), (dir(0.0, 1.0, 0.0), fx(0.5)));
+        let centered = Sphere::new(p(1.0, 1.0, 0.0), fx(1.0)).unwrap().contact(&tri).unwrap();
+        assert_eq!((centered.normal(), centered.depth()), (dir(0.0, 0.0, -1.0), fx(1.0)));
+        assert!(!Sphere::new(p(3.0, 3.0, 0.0), fx(1.0)).unwrap().intersects(&tri));
+    }
+
+    #[test]
+    fn obb_face_manifold() {
+        let quarter = Mat3::from_cols(dir(0.0, 1.0, 0.0).0, dir(-1.0, 0.0, 0.0).0, dir(0.0, 0.0, 1.0).0);
+        let top = Obb::new(p(0.0, 1.25, 0.0), Vec3::splat(fx(0.5)), quarter).unwrap();
+        let c = unit_box().contact(&top).unwrap();
+        assert_eq!(c.normal(), dir(0.0, 1.0, 0.0));
+        let mut got = points(&c);
+        got.sort_by(|l, r| (l.0 .0.x, l.0 .0.z).partial_cmp(&(r.0 .0.x, r.0 .0.z)).unwrap());
+        let d = fx(0.25);
+        assert_eq!(got, vec![(p(-0.5, 0.75, -0.5), d), (p(-0.5, 0.75, 0.5), d), (p(0.5, 0.75, -0.5), d), (p(0.5, 0.75, 0.5), d)]);
+
+        let back = top.contact(&unit_box()).unwrap();
+        assert_eq!(back.normal(), dir(0.0, -1.0, 0.0));
+        assert!(back.points().iter().all(|cp| cp.point.0.y == fx(1.0) && cp.depth == d));
+    }
+
+    #[test]
+    fn obb_edge_axes_separate_and_touch() {
+        let (c, s) = (F64Det(0.5f64.sqrt()), F64Det(0.5f64.sqrt()));
+        let (o, z) = (F64Det(1.0), F64Det(0.0));
+        let about_x = Mat3::from_cols(Vec3::new(o, z, z), Vec3::new(z, c, s), Vec3::new(z, -s, c));
+        let about_z = Mat3::from_cols(Vec3::new(c, s, z), Vec3::new(-s, c, z), Vec3::new(z, z, o));
+        let a = Obb::new(Point3::new(z, z, z), Vec3::splat(o), about_x).unwrap();
+        let root2 = 2f64.sqrt();
+
+        let b = Obb::new(Point3::new(z, F64Det(2.0 * root2 - 0.1), z), Vec3::splat(o), about_z).unwrap();
+        let hit = a.contact(&b).unwrap();
+        assert_eq!(hit.points().len(), 1);
+        assert!((hit.normal().0 - Vec3::new(z, o, z)).length().0 < 1e-12);
+        assert!((hit.depth().0 - 0.1).abs() < 1e-12);
+        assert!((hit.points()[0].point - Point3::new(z, F64Det(root2 - 0.1), z)).0.length().0 < 1e-12);
+
+        let apart = Obb::new(Point3::new(z, F64Det(2.0 * root2 + 0.1), z), Vec3::splat(o), about_z).unwrap();
+        assert!(!a.intersects(&apart));
+        assert!(a.contact(&apart).is_none());
+    }
+
+    #[test]
+    fn capsule_against_obb() {
+        let lying = Capsule::new(p(-0.5, 1.25, 0.0), p(0.5, 1.25, 0.0), fx(0.5)).unwrap();
+        let c = lying.contact(&unit_box()).unwrap();
+        assert_eq!(c.normal(), dir(0.0, -1.0, 0.0));
+        assert_eq!(points(&c), vec![(p(-0.5, 1.0, 0.0), fx(0.25)), (p(0.5, 1.0, 0.0), fx(0.25))]);
+        assert_eq!(unit_box().contact(&lying), Some(c.flipped()));
+
+        let piercing = Capsule::new(p(0.0, 0.5, 0.0), p(0.0, 3.0, 0.0), fx(0.25)).unwrap();
+        let c = piercing.contact(&unit_box()).unwrap();
+        assert_eq!((c.normal(), points(&c)), (dir(0.0, -1.0, 0.0), vec![(p(0.0, 1.0, 0.0), fx(0.75))]));
+        assert!(!Capsule::new(p(0.0, 2.0, 0.0), p(0.0, 3.0, 0.0), fx(0.5)).unwrap().intersects(&unit_box()));
+    }
+}
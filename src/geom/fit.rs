@@ -0,0 +1,414 @@
+//! Bounding volumes fitted to point sets.
+//!
+//! Every fit depends only on the points and their order, so peers fitting
+//! the same list get identical volumes. As a last step each volume is grown
+//! until its own `contains_point` accepts every input point, whatever the
+//! rounding of the backend.
+
+use core::cmp::Ordering;
+
+use super::{unit, Aabb, GeomError, Obb, Sphere};
+use crate::scalar::RealScalar;
+use crate::{Mat3, Point3, Vec3};
+
+/// Fixed number of Jacobi sweeps over the covariance matrix.
+const JACOBI_SWEEPS: usize = 8;
+
+fn half<T: RealScalar>() -> T {
+    T::ONE / (T::ONE + T::ONE)
+}
+
+/// Divides each component by `s`.
+fn div<T: RealScalar>(v: Vec3<T>, s: T) -> Vec3<T> {
+    Vec3::new(v.x / s, v.y / s, v.z / s)
+}
+
+fn zip<T: RealScalar>(a: Vec3<T>, b: Vec3<T>, f: impl Fn(T, T) -> T) -> Vec3<T> {
+    Vec3::new(f(a.x, b.x), f(a.y, b.y), f(a.z, b.z))
+}
+
+impl<T: RealScalar> Aabb<T> {
+    /// Tightest box around `points`.
+    ///
+    /// Returns [`GeomError::EmptyPointSet`] if `points` is empty.
+    pub fn from_points(points: &[Point3<T>]) -> Result<Self, GeomError> {
+        let (first, rest) = points.split_first().ok_or(GeomError::EmptyPointSet)?;
+        let (mut lo, mut hi) = (first.0, first.0);
+        for p in rest {
+            lo = zip(lo, p.0, T::min);
+            hi = zip(hi, p.0, T::max);
+        }
+        Self::new(Point3(lo), Point3(hi))
+    }
+}
+
+/// Grows `radius` until the sphere around `center` contains every point.
+fn enclose<T: RealScalar>(center: Point3<T>, mut radius: T, points: &[Point3<T>]) -> Result<Sphere<T>, GeomError> {
+    let step = T::epsilon() * T::from_f32(4.0);
+    for &p in points {
+        let dist_sq = (p - center).0.length_sq();
+        radius = radius.max(dist_sq.sqrt());
+        while dist_sq > radius * radius {
+            radius = radius + (radius + T::ONE) * step;
+        }
+    }
+    Sphere::new(center, radius)
+}
+
+/// Containment test in a scaled frame, so that small radii do not lose
+/// their squares to fixed-point underflow.
+fn inside<T: RealScalar>(center: Point3<T>, radius: T, point: Point3<T>) -> bool {
+    let d = (point - center).0;
+    let (k, _) = unit_scale(largest(d).max(radius));
+    let r = radius * k;
+    (d * k).length_sq() <= r * r
+}
+
+/// Ball with `a` and `b` as antipodes.
+fn diameter_ball<T: RealScalar>(a: Point3<T>, b: Point3<T>) -> (Point3<T>, T) {
+    (Point3((a.0 + b.0) * half()), (b - a).0.length() * half())
+}
+
+/// Largest component magnitude of `v`.
+fn largest<T: RealScalar>(v: Vec3<T>) -> T {
+    v.x.abs().max(v.y.abs()).max(v.z.abs())
+}
+
+/// Power of two `k` (and its inverse) that brings a nonzero `magnitude` to
+/// between one half and one, so that products of scaled values neither
+/// underflow nor overflow in fixed point. Scaling by a power of two is exact
+/// in floating point.
+fn unit_scale<T: RealScalar>(magnitude: T) -> (T, T) {
+    let (two, half) = (T::ONE + T::ONE, half::<T>());
+    let (mut k, mut inv) = (T::ONE, T::ONE);
+    if magnitude == T::ZERO {
+        return (k, inv);
+    }
+    for _ in 0..64 {
+        if magnitude * k <= T::ONE {
+            break;
+        }
+        (k, inv) = (k * half, inv * two);
+    }
+    for _ in 0..64 {
+        if magnitude * k >= half {
+            break;
+        }
+        (k, inv) = (k * two, inv * half);
+    }
+    (k, inv)
+}
+
+/// Threshold below which a cross product or volume of offsets scaled by
+/// `k` is rounding noise: a few ulps of the unscaled coordinates.
+fn snap<T: RealScalar>(k: T) -> T {
+    T::epsilon() * k * T::from_f32(4.0)
+}
+
+/// Smallest ball with all of `boundary` (one to four points) on its
+/// surface, falling back to a smaller boundary when the points are
+/// collinear or coplanar to within rounding.
+fn boundary_ball<T: RealScalar>(boundary: &[Point3<T>]) -> (Point3<T>, T) {
+    let a = boundary[0];
+    match boundary.len() {
+        1 => (a, T::ZERO),
+        2 => diameter_ball(a, boundary[1]),
+        3 => {
+            let (k, inv) = unit_scale(boundary[1..].iter().fold(T::ZERO, |m, p| m.max(largest((*p - a).0))));
+            let (ab, ac) = ((boundary[1] - a).0 * k, (boundary[2] - a).0 * k);
+            let n = ab.cross(&ac);
+            let nn = n.length_sq();
+            if nn <= snap(k) * snap(k) {
+                // Collinear: the farthest pair spans the others.
+                let pairs = [(a, boundary[1]), (a, boundary[2]), (boundary[1], boundary[2])];
+                let widest = pairs.into_iter().fold(pairs[0], |w, p| if (p.1 - p.0).0.length_sq() > (w.1 - w.0).0.length_sq() { p } else { w });
+                return diameter_ball(widest.0, widest.1);
+            }
+            let offset = div(n.cross(&ab) * ac.length_sq() + ac.cross(&n) * ab.length_sq(), nn + nn) * inv;
+            (Point3(a.0 + offset), offset.length())
+        }
+        _ => {
+            let (k, inv) = unit_scale(boundary[1..].iter().fold(T::ZERO, |m, p| m.max(largest((*p - a).0))));
+            let (u, v, w) = ((boundary[1] - a).0 * k, (boundary[2] - a).0 * k, (boundary[3] - a).0 * k);
+            let det = u.dot(&v.cross(&w));
+            if det.abs() <= snap(k) {
+                // Coplanar: the smallest ball through three of the points
+                // that also holds the fourth.
+                let subsets = [[0, 1, 2], [0, 1, 3], [0, 2, 3], [1, 2, 3]];
+                let mut best: Option<(Point3<T>, T)> = None;
+                for (s, rest) in subsets.iter().zip([3, 2, 1, 0]) {
+                    let (c, r) = boundary_ball(&s.map(|i| boundary[i]));
+                    if inside(c, r, boundary[rest]) && best.map_or(true, |b| r < b.1) {
+                        best = Some((c, r));
+                    }
+                }
+                return best.unwrap_or_else(|| boundary_ball(&boundary[..3]));
+            }
+            let offset = div(v.cross(&w) * u.length_sq() + w.cross(&u) * v.length_sq() + u.cross(&v) * w.length_sq(), det + det) * inv;
+            (Point3(a.0 + offset), offset.length())
+        }
+    }
+}
+
+impl<T: RealScalar> Sphere<T> {
+    /// Ritter's approximate bounding sphere: a ball spanning two far-apart
+    /// points, grown in one pass over the rest. Usually within a few
+    /// percent of the minimal radius.
+    ///
+    /// Returns [`GeomError::EmptyPointSet`] if `points` is empty.
+    pub fn from_points_ritter(points: &[Point3<T>]) -> Result<Self, GeomError> {
+        let first = *points.first().ok_or(GeomError::EmptyPointSet)?;
+        // Earliest point farthest from `from`.
+        let farthest = |from: Point3<T>| {
+            points.iter().fold(from, |best, &p| if (p - from).0.length_sq() > (best - from).0.length_sq() { p } else { best })
+        };
+        let y = farthest(first);
+        let (mut center, mut radius) = diameter_ball(y, farthest(y));
+        for &p in points {
+            let dist = (p - center).0.length();
+            if dist > radius {
+                let grown = (radius + dist) * half();
+                center = center + (p - center) * ((grown - radius) / dist);
+                radius = grown;
+            }
+        }
+        enclose(center, radius, points)
+    }
+
+    /// Minimal bounding sphere by Welzl's algorithm, unrolled into four
+    /// nested loops: whenever a point falls outside the current ball, the
+    /// ball is rebuilt from that point and the outer loops' boundary points
+    /// by sweeping the earlier points again.
+    ///
+    /// The points are visited in the order given instead of being shuffled,
+    /// which keeps the result reproducible; the expected linear running time
+    /// assumes that order is random, so shuffle sorted or adversarial input
+    /// first (e.g. with a seeded [`Prng`](crate::Prng)).
+    ///
+    /// Rounding can break the algorithm's invariants on nearly degenerate
+    /// input, mostly with fixed-point coordinates only a few hundred ulps
+    /// apart; should the result come out larger than
+    /// [`Sphere::from_points_ritter`], that sphere is returned instead.
+    ///
+    /// Returns [`GeomError::EmptyPointSet`] if `points` is empty.
+    pub fn from_points_welzl(points: &[Point3<T>]) -> Result<Self, GeomError> {
+        let first = *points.first().ok_or(GeomError::EmptyPointSet)?;
+        let (mut center, mut radius) = (first, T::ZERO);
+        for i in 1..points.len() {
+            if inside(center, radius, points[i]) {
+                continue;
+            }
+            (center, radius) = (points[i], T::ZERO);
+            for j in 0..i {
+                if inside(center, radius, points[j]) {
+                    continue;
+                }
+                (center, radius) = boundary_ball(&[points[i], points[j]]);
+                for k in 0..j {
+                    if inside(center, radius, points[k]) {
+                        continue;
+                    }
+                    (center, radius) = boundary_ball(&[points[i], points[j], points[k]]);
+                    for l in 0..k {
+                        if !inside(center, radius, points[l]) {
+                            (center, radius) = boundary_ball(&[points[i], points[j], points[k], points[l]]);
+                        }
+                    }
+                }
+            }
+        }
+        let welzl = enclose(center, radius, points)?;
+        let ritter = Self::from_points_ritter(points)?;
+        Ok(if ritter.radius() < welzl.radius() { ritter } else { welzl })
+    }
+}
+
+/// Tangent of the Jacobi rotation angle that zeroes `apq`, computed without
+/// squaring large ratios so fixed-point backends cannot overflow.
+fn jacobi_tangent<T: RealScalar>(app: T, aqq: T, apq: T) -> T {
+    let (diff, twice) = (aqq - app, apq + apq);
+    if diff.abs() > twice.abs() {
+        let phi = twice / diff;
+        phi / (T::ONE + (T::ONE + phi * phi).sqrt())
+    } else {
+        let theta = diff / twice;
+        let t = T::ONE / (theta.abs() + (theta * theta + T::ONE).sqrt());
+        if theta < T::ZERO { -t } else { t }
+    }
+}
+
+/// Eigenvectors of the symmetric matrix `a` by cyclic Jacobi rotations,
+/// ordered by decreasing eigenvalue (ties keep axis order).
+fn principal_axes<T: RealScalar>(mut a: [[T; 3]; 3]) -> [Vec3<T>; 3] {
+    let mut v = [[T::ZERO; 3]; 3];
+    for (k, row) in v.iter_mut().enumerate() {
+        row[k] = T::ONE;
+    }
+    for _ in 0..JACOBI_SWEEPS {
+        for (p, q) in [(0, 1), (0, 2), (1, 2)] {
+            if a[p][q] == T::ZERO {
+                continue;
+            }
+            let t = jacobi_tangent(a[p][p], a[q][q], a[p][q]);
+            let c = T::ONE / (t * t + T::ONE).sqrt();
+            let s = t * c;
+            for row in a.iter_mut() {
+                let (kp, kq) = (row[p], row[q]);
+                (row[p], row[q]) = (c * kp - s * kq, s * kp + c * kq);
+            }
+            let (upper, lower) = a.split_at_mut(q);
+            for (pk, qk) in upper[p].iter_mut().zip(lower[0].iter_mut()) {
+                (*pk, *qk) = (c * *pk - s * *qk, s * *pk + c * *qk);
+            }
+            (a[p][q], a[q][p]) = (T::ZERO, T::ZERO);
+            for row in v.iter_mut() {
+                let (kp, kq) = (row[p], row[q]);
+                (row[p], row[q]) = (c * kp - s * kq, s * kp + c * kq);
+            }
+        }
+    }
+    let mut order = [0, 1, 2];
+    order.sort_by(|&i, &j| a[j][j].partial_cmp(&a[i][i]).unwrap_or(Ordering::Equal));
+    order.map(|i| Vec3::new(v[0][i], v[1][i], v[2][i]))
+}
+
+/// Flips `v` so that its largest component (the earliest on ties) is
+/// positive.
+fn canonical_sign<T: RealScalar>(v: Vec3<T>) -> Vec3<T> {
+    let c = v.to_array();
+    let lead = (1..3).fold(0, |best, k| if c[k].abs() > c[best].abs() { k } else { best });
+    if c[lead] < T::ZERO { -v } else { v }
+}
+
+/// Right-handed orthonormal frame from approximate principal axes, falling
+/// back to world axes where they degenerate.
+fn frame<T: RealScalar>(axes: [Vec3<T>; 3]) -> Mat3<T> {
+    let world = Mat3::<T>::identity();
+    let first = canonical_sign(unit(axes[0]).map_or_else(|_| world.col(0), |d| d.0));
+    let second = [axes[1], axes[2], world.col(1), world.col(2), world.col(0)]
+        .into_iter()
+        .find_map(|a| unit(a - first * first.dot(&a)).ok())
+        .map_or_else(|| world.col(1), |d| canonical_sign(d.0));
+    Mat3::from_cols(first, second, first.cross(&second))
+}
+
+impl<T: RealScalar> Obb<T> {
+    /// Box aligned with the principal axes of `points`: the eigenvectors of
+    /// their covariance, longest spread first, each flipped so its largest
+    /// component is positive. The box then spans the points' extent along
+    /// those axes.
+    ///
+    /// Returns [`GeomError::EmptyPointSet`] if `points` is empty.
+    pub fn from_points_pca(points: &[Point3<T>]) -> Result<Self, GeomError> {
+        if points.is_empty() {
+            return Err(GeomError::EmptyPointSet);
+        }
+        let n = T::from_f64(points.len() as f64);
+        let mean = div(points.iter().fold(Vec3::splat(T::ZERO), |s, p| s + p.0), n);
+        let mut cov = [[T::ZERO; 3]; 3];
+        for p in points {
+            let d = (p.0 - mean).to_array();
+            for (i, row) in cov.iter_mut().enumerate() {
+                for (j, c) in row.iter_mut().enumerate() {
+                    *c = *c + d[i] * d[j];
+                }
+            }
+        }
+        let cov = cov.map(|row| row.map(|c| c / n));
+        let rotation = frame(principal_axes(cov));
+
+        let local: Vec<Vec3<T>> = points.iter().map(|p| rotation.transform_transposed(p.0 - mean)).collect();
+        let (mut lo, mut hi) = (local[0], local[0]);
+        for &l in &local[1..] {
+            lo = zip(lo, l, T::min);
+            hi = zip(hi, l, T::max);
+        }
+        let center = Point3(mean + rotation.transform((lo + hi) * half()));
+        let mut half_extents = Vec3::splat(T::ZERO);
+        let probe = Obb::new(center, half_extents, rotation)?;
+        for &p in points {
+            let l = probe.to_local(p);
+            half_extents = zip(half_extents, Vec3::new(l.x.abs(), l.y.abs(), l.z.abs()), T::max);
+        }
+        Obb::new(center, half_extents, rotation)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geom::test_support::{fx, p, pf};
+    use crate::scalar::{DFix64, F64Det, Scalar};
+
+    /// Deterministic cloud on the surface of a unit-radius shape.
+    fn cloud() -> Vec<Point3<DFix64>> {
+        (0..40)
+            .map(|i| {
+                let (a, b) = (i as f32 * 0.7, i as f32 * 1.3);
+                p(3.0 + a.cos() * b.sin(), -2.0 + a.sin() * b.sin(), 1.0 + b.cos())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn aabb_from_points() {
+        let b = Aabb::from_points(&[p(1.0, -2.0, 0.0), p(-1.0, 3.0, 0.5), p(0.0, 0.0, -4.0)]).unwrap();
+        assert_eq!((b.min(), b.max()), (p(-1.0, -2.0, -4.0), p(1.0, 3.0, 0.5)));
+        assert_eq!(Aabb::<DFix64>::from_points(&[]), Err(GeomError::EmptyPointSet));
+    }
+
+    #[test]
+    fn bounding_spheres_contain_every_point() {
+        let pts = cloud();
+        let welzl = Sphere::from_points_welzl(&pts).unwrap();
+        let ritter = Sphere::from_points_ritter(&pts).unwrap();
+        for s in [welzl, ritter] {
+            assert!(pts.iter().all(|&q| s.contains_point(q)));
+        }
+        assert!(welzl.radius() <= ritter.radius());
+        assert!((welzl.radius().to_f64() - 1.0).abs() < 0.05);
+
+        // Square corners: the minimal ball is the circumcircle, Ritter's is
+        // no smaller.
+        let square = [p(0.0, 0.0, 0.0), p(2.0, 0.0, 0.0), p(2.0, 2.0, 0.0), p(0.0, 2.0, 0.0)];
+        let s = Sphere::from_points_welzl(&square).unwrap();
+        assert_eq!(s.center(), p(1.0, 1.0, 0.0));
+        assert!((s.radius().to_f64() - 2f64.sqrt()).abs() < 1e-3);
+        assert!(Sphere::from_points_ritter(&square).unwrap().radius() >= s.radius());
+        assert_eq!(Sphere::from_points_welzl(&[p(1.0, 1.0, 1.0)]).unwrap().radius(), fx(0.0));
+        assert_eq!(Sphere::<DFix64>::from_points_ritter(&[]), Err(GeomError::EmptyPointSet));
+    }
+
+    #[test]
+    fn pca_box_follows_the_spread() {
+        // A thin rod along (1, 1, 0).
+        let rod: Vec<Point3<F64Det>> = (0..11).map(|i| {
+            let t = i as f64 - 5.0;
+            pf(t, t + if i % 2 == 0 { 0.1 } else { -0.1 }, 0.0)
+        }).collect();
+        let obb = Obb::from_points_pca(&rod).unwrap();
+        let axis = obb.axis(0).0;
+        assert!((axis.x.0 - 0.5f64.sqrt()).abs() < 1e-3 && (axis.y.0 - 0.5f64.sqrt()).abs() < 1e-3);
+        assert!((obb.half_extents().x.0 - 50f64.sqrt()).abs() < 0.1);
+        assert!(rod.iter().all(|&q| obb.contains_point(q)));
+        assert!((obb.rotation().determinant().0 - 1.0).abs() < 1e-9);
+
+        let pts = cloud();
+        let obb = Obb::from_points_pca(&pts).unwrap();
+        assert!(pts.iter().all(|&q| obb.contains_point(q)));
+
+        // Pinned raw output over an integer-built cloud, so any change to the
+        // fixed-point arithmetic shows up here.
+        let skew: Vec<Point3<DFix64>> = (0..24).map(|i| p((i * 7 % 11) as f32 - 5.0, (i * 5 % 13) as f32 * 0.5 + i as f32 * 0.25, (i * 3 % 7) as f32 * 0.25)).collect();
+        let obb = Obb::from_points_pca(&skew).unwrap();
+        assert!(skew.iter().all(|&q| obb.contains_point(q)));
+        assert_eq!(obb.center().0.to_array().map(|v| v.0), [-48_701, 315_975, 47_521]);
+        assert_eq!(obb.half_extents().to_array().map(|v| v.0), [377_446, 327_922, 50_396]);
+        assert_eq!([0, 1, 2].map(|i| obb.axis(i).0.to_array().map(|v| v.0)), [
+            [60_770, 24_530, 653],
+            [-24_535, 60_738, 2_002],
+            [144, -2_101, 65_504],
+        ]);
+        assert_eq!(Obb::<DFix64>::from_points_pca(&[]), Err(GeomError::EmptyPointSet));
+    }
+}
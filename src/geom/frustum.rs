@@ -0,0 +1,274 @@
+//! View frustums for culling and shadow cascade fitting.
+
+use super::{Aabb, GeomError, Obb, Plane, Sphere};
+use crate::scalar::RealScalar;
+use crate::{Direction3, Mat4, Point3, Vec3};
+
+/// Clip-space depth range of a projection matrix.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum DepthRange {
+    /// `0 <= z <= w`, as in Vulkan, Metal, Direct3D and wgpu.
+    ZeroToOne,
+    /// `-w <= z <= w`, as in OpenGL.
+    NegativeOneToOne,
+}
+
+/// Where a shape lies relative to a [`Frustum`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Containment {
+    Outside,
+    Intersecting,
+    Inside,
+}
+
+/// Convex volume bounded by six planes whose normals point inward.
+///
+/// The planes are stored as left, right, bottom, top, near, far. Because the
+/// normals point inward, [`Frustum::planes`] can be passed straight to
+/// [`Bvh::query_planes`](super::Bvh::query_planes).
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Frustum<T: RealScalar> {
+    planes: [Plane<T>; 6],
+}
+
+impl<T: RealScalar> Frustum<T> {
+    /// Frustum from six inward-facing planes: left, right, bottom, top, near,
+    /// far.
+    pub fn from_planes(planes: [Plane<T>; 6]) -> Self {
+        Self { planes }
+    }
+
+    /// Extracts the frustum of a view-projection matrix (Gribb–Hartmann).
+    ///
+    /// `m` maps world space to clip space for column vectors, so a camera's
+    /// frustum is `projection * view`. The planes are summed from the rows of
+    /// `m` (exactly) and normalized in `f64` before conversion to `T`; the
+    /// small coefficients of a wide orthographic projection would otherwise
+    /// lose most of their bits in fixed point.
+    ///
+    /// Returns [`GeomError::ZeroDirection`] if a plane has no normal, as with
+    /// an infinite far plane.
+    pub fn from_view_projection(m: &Mat4, depth: DepthRange) -> Result<Self, GeomError> {
+        let a = m.to_array();
+        let row = |i: usize| [a[i], a[4 + i], a[8 + i], a[12 + i]].map(f64::from);
+        let (r0, r1, r2, r3) = (row(0), row(1), row(2), row(3));
+        let add = |x: [f64; 4], y: [f64; 4]| [x[0] + y[0], x[1] + y[1], x[2] + y[2], x[3] + y[3]];
+        let sub = |x: [f64; 4], y: [f64; 4]| [x[0] - y[0], x[1] - y[1], x[2] - y[2], x[3] - y[3]];
+        let near = match depth {
+            DepthRange::ZeroToOne => r2,
+            DepthRange::NegativeOneToOne => add(r3, r2),
+        };
+        Ok(Self {
+            planes: [
+                clip_plane(add(r3, r0))?,
+                clip_plane(sub(r3, r0))?,
+                clip_plane(add(r3, r1))?,
+                clip_plane(sub(r3, r1))?,
+                clip_plane(near)?,
+                clip_plane(sub(r3, r2))?,
+            ],
+        })
+    }
+
+    /// Left, right, bottom, top, near and far planes, facing inward.
+    pub fn planes(&self) -> &[Plane<T>; 6] {
+        &self.planes
+    }
+
+    /// Whether `point` is inside or on the boundary.
+    pub fn contains_point(&self, point: Point3<T>) -> bool {
+        self.planes.iter().all(|pl| pl.signed_distance(point) >= T::ZERO)
+    }
+
+    /// Classifies a shape given its center and its extent along each plane
+    /// normal.
+    ///
+    /// Like every plane-by-plane test this is conservative: a shape near a
+    /// frustum edge but outside it can come back as
+    /// [`Containment::Intersecting`], never the other way round.
+    fn classify(&self, center: Point3<T>, radius: impl Fn(Vec3<T>) -> T) -> Containment {
+        let mut result = Containment::Inside;
+        for pl in &self.planes {
+            let (s, r) = (pl.signed_distance(center), radius(pl.normal().0));
+            if s < -r {
+                return Containment::Outside;
+            }
+            if s < r {
+                result = Containment::Intersecting;
+            }
+        }
+        result
+    }
+
+    pub fn classify_sphere(&self, sphere: &Sphere<T>) -> Containment {
+        self.classify(sphere.center(), |_| sphere.radius())
+    }
+
+    pub fn classify_aabb(&self, aabb: &Aabb<T>) -> Containment {
+        let h = aabb.half_extents();
+        self.classify(aabb.center(), |n| h.x * n.x.abs() + h.y * n.y.abs() + h.z * n.z.abs())
+    }
+
+    pub fn classify_obb(&self, obb: &Obb<T>) -> Containment {
+        let h = obb.half_extents().to_array();
+        self.classify(obb.center(), |n| (0..3).fold(T::ZERO, |r, i| r + h[i] * n.dot(&obb.axis(i).0).abs()))
+    }
+
+    /// The eight corners, indexed by bits: bit 0 picks right over left, bit 1
+    /// top over bottom and bit 2 far over near.
+    ///
+    /// Returns `None` if some three planes do not meet in a single point.
+    pub fn corners(&self) -> Option<[Point3<T>; 8]> {
+        let mut corners = [Point3::new(T::ZERO, T::ZERO, T::ZERO); 8];
+        for (k, corner) in corners.iter_mut().enumerate() {
+            let pick = |bit: usize, base: usize| &self.planes[base + usize::from(k & bit != 0)];
+            *corner = meet(pick(1, 0), pick(2, 2), pick(4, 4))?;
+        }
+        Some(corners)
+    }
+
+    /// Corners of the slice between fractions `from` and `to` of the way from
+    /// the near plane to the far plane, indexed as in [`Frustum::corners`].
+    ///
+    /// Each corner moves along its frustum edge, and view depth is linear
+    /// along those edges, so a cascade split at view depth `z` sits at
+    /// `(z - near) / (far - near)` for perspective and orthographic cameras
+    /// alike.
+    pub fn slice_corners(&self, from: T, to: T) -> Option<[Point3<T>; 8]> {
+        let c = self.corners()?;
+        let mut slice = c;
+        for k in 0..4 {
+            let edge = c[k + 4] - c[k];
+            slice[k] = c[k] + edge * from;
+            slice[k + 4] = c[k] + edge * to;
+        }
+        Some(slice)
+    }
+}
+
+/// Plane `c[0] x + c[1] y + c[2] z + c[3] = 0`, normalized in `f64` so that
+/// each coefficient is rounded to `T` only once.
+fn clip_plane<T: RealScalar>(c: [f64; 4]) -> Result<Plane<T>, GeomError> {
+    let len = (c[0] * c[0] + c[1] * c[1] + c[2] * c[2]).sqrt();
+    if len == 0.0 || !len.is_finite() {
+        return Err(GeomError::ZeroDirection);
+    }
+    let [a, b, cz, d] = c.map(|k| T::from_f64(k / len));
+    Plane::new(Direction3::new(a, b, cz), d)
+}
+
+/// The point on all three planes, or `None` if their normals are coplanar.
+fn meet<T: RealScalar>(a: &Plane<T>, b: &Plane<T>, c: &Plane<T>) -> Option<Point3<T>> {
+    let (na, nb, nc) = (a.normal().0, b.normal().0, c.normal().0);
+    let (bc, ca, ab) = (nb.cross(&nc), nc.cross(&na), na.cross(&nb));
+    let det = na.dot(&bc);
+    if det == T::ZERO {
+        return None;
+    }
+    let sum = bc * a.d() + ca * b.d() + ab * c.d();
+    Some(Point3::new(-sum.x / det, -sum.y / det, -sum.z / det))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geom::test_support::{fx, p};
+    use crate::scalar::DFix64;
+    use crate::Mat3;
+
+    fn near(a: Point3<DFix64>, b: Point3<DFix64>) -> bool {
+        (a - b).0.length() < fx(1e-3)
+    }
+
+    /// Right-handed perspective looking down -z: 90° vertical field of view,
+    /// square aspect, near 1, far 10.
+    fn perspective(depth: DepthRange) -> Mat4 {
+        let (n, f) = (1.0f32, 10.0f32);
+        let (zz, zw) = match depth {
+            DepthRange::ZeroToOne => (f / (n - f), n * f / (n - f)),
+            DepthRange::NegativeOneToOne => ((f + n) / (n - f), 2.0 * f * n / (n - f)),
+        };
+        Mat4::new([1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, zz, -1.0, 0.0, 0.0, zw, 0.0])
+    }
+
+    #[test]
+    fn extracts_planes_and_corners() {
+        for depth in [DepthRange::ZeroToOne, DepthRange::NegativeOneToOne] {
+            // Camera at z = 5, so the near plane sits at z = 4 and the far at z = -5.
+            let fr: Frustum<DFix64> = Frustum::from_view_projection(&(perspective(depth) * Mat4::translation(0.0, 0.0, -5.0)), depth).unwrap();
+            let pl = fr.planes();
+            assert!(near(Point3(pl[4].normal().0), p(0.0, 0.0, -1.0)) && (pl[4].d() - fx(4.0)).abs() < fx(1e-3));
+            assert!(near(Point3(pl[0].normal().0), p(0.5f32.sqrt(), 0.0, -(0.5f32.sqrt()))));
+
+            let c = fr.corners().unwrap();
+            assert!(near(c[0], p(-1.0, -1.0, 4.0)) && near(c[3], p(1.0, 1.0, 4.0)));
+            assert!(near(c[5], p(10.0, -10.0, -5.0)) && near(c[6], p(-10.0, 10.0, -5.0)));
+
+            // The middle third in view depth: z from 4 down to 1 ... -2.
+            let s = fr.slice_corners(fx(1.0 / 3.0), fx(2.0 / 3.0)).unwrap();
+            assert!(near(s[0], p(-4.0, -4.0, 1.0)) && near(s[7], p(7.0, 7.0, -2.0)));
+
+            assert!(fr.contains_point(p(0.0, 0.0, 0.0)) && fr.contains_point(p(-8.9, 8.9, -4.0)));
+            assert!(!fr.contains_point(p(0.0, 0.0, 4.5)) && !fr.contains_point(p(3.0, 0.0, 3.0)));
+        }
+        let flat = Mat4::new([0.0; 16]);
+        assert_eq!(Frustum::<DFix64>::from_view_projection(&flat, DepthRange::ZeroToOne), Err(GeomError::ZeroDirection));
+    }
+
+    /// Right-handed orthographic box `[l, r] x [b, t]`, depth `n..f` down -z.
+    fn orthographic(depth: DepthRange, (l, r): (f32, f32), (b, t): (f32, f32), (n, f): (f32, f32)) -> Mat4 {
+        let (zz, zw) = match depth {
+            DepthRange::ZeroToOne => (1.0 / (n - f), n / (n - f)),
+            DepthRange::NegativeOneToOne => (2.0 / (n - f), (f + n) / (n - f)),
+        };
+        let (sx, sy) = (2.0 / (r - l), 2.0 / (t - b));
+        Mat4::new([sx, 0.0, 0.0, 0.0, 0.0, sy, 0.0, 0.0, 0.0, 0.0, zz, 0.0, -(r + l) / (r - l), -(t + b) / (t - b), zw, 1.0])
+    }
+
+    #[test]
+    fn wide_orthographic_cascade() {
+        // A shadow camera covering 4 km x 2 km and 1 km of depth, centred
+        // on (500, 0) and looking down -z from z = 100.
+        for depth in [DepthRange::ZeroToOne, DepthRange::NegativeOneToOne] {
+            let m = orthographic(depth, (-2000.0, 2000.0), (-1000.0, 1000.0), (0.5, 1000.0)) * Mat4::translation(-500.0, 0.0, -100.0);
+            let fr: Frustum<DFix64> = Frustum::from_view_projection(&m, depth).unwrap();
+            let want = [(1.0, 0.0, 0.0, 1500.0), (-1.0, 0.0, 0.0, 2500.0), (0.0, 1.0, 0.0, 1000.0), (0.0, -1.0, 0.0, 1000.0), (0.0, 0.0, -1.0, 99.5), (0.0, 0.0, 1.0, 900.0)];
+            for (pl, (x, y, z, d)) in fr.planes().iter().zip(want) {
+                assert_eq!(pl.normal(), Direction3::new(fx(x), fx(y), fx(z)));
+                assert!((pl.d() - fx(d)).abs() < fx(1e-3), "{:?}", pl);
+            }
+            let c = fr.corners().unwrap();
+            assert!(near(c[0], p(-1500.0, -1000.0, 99.5)) && near(c[7], p(2500.0, 1000.0, -900.0)));
+            let s = fr.slice_corners(fx(0.25), fx(0.5)).unwrap();
+            assert!(near(s[1], p(2500.0, -1000.0, 99.5 - 999.5 * 0.25)) && near(s[6], p(-1500.0, 1000.0, 99.5 - 999.5 * 0.5)));
+            assert_eq!(fr.classify_aabb(&Aabb::new(p(2400.0, -50.0, -10.0), p(2600.0, 50.0, 10.0)).unwrap()), Containment::Intersecting);
+            assert_eq!(fr.classify_sphere(&Sphere::new(p(-1400.0, 900.0, -800.0), fx(50.0)).unwrap()), Containment::Inside);
+        }
+    }
+
+    #[test]
+    fn classifies_spheres_and_boxes() {
+        let fr: Frustum<DFix64> = Frustum::from_view_projection(&perspective(DepthRange::ZeroToOne), DepthRange::ZeroToOne).unwrap();
+        let sphere = |x, y, z, r| Sphere::new(p(x, y, z), fx(r)).unwrap();
+        assert_eq!(fr.classify_sphere(&sphere(0.0, 0.0, -5.0, 1.0)), Containment::Inside);
+        assert_eq!(fr.classify_sphere(&sphere(0.0, 0.0, -10.5, 1.0)), Containment::Intersecting);
+        assert_eq!(fr.classify_sphere(&sphere(0.0, 0.0, 2.0, 1.0)), Containment::Outside);
+
+        let cube = |x: f32, y: f32, z: f32, h: f32| Aabb::new(p(x - h, y - h, z - h), p(x + h, y + h, z + h)).unwrap();
+        assert_eq!(fr.classify_aabb(&cube(0.0, 0.0, -5.0, 1.0)), Containment::Inside);
+        assert_eq!(fr.classify_aabb(&cube(5.0, 0.0, -5.0, 1.0)), Containment::Intersecting);
+        assert_eq!(fr.classify_aabb(&cube(8.0, 0.0, -5.0, 1.0)), Containment::Outside);
+
+        // The right plane leans outward with depth, so a box must clear it by
+        // its extent along the tilted normal. Turned 45° about z, the cube
+        // reaches further than its axis-aligned twin.
+        let (c, s) = (fx(0.5f32.sqrt()), fx(0.5f32.sqrt()));
+        let turn = Mat3::from_cols(Vec3::new(c, s, fx(0.0)), Vec3::new(-s, c, fx(0.0)), Vec3::new(fx(0.0), fx(0.0), fx(1.0)));
+        let obb = |x| Obb::new(p(x, 0.0, -5.0), p(0.5, 0.5, 0.5).0, turn).unwrap();
+        assert_eq!(fr.classify_aabb(&cube(6.1, 0.0, -5.0, 0.5)), Containment::Outside);
+        assert_eq!(fr.classify_obb(&obb(6.1)), Containment::Intersecting);
+        assert_eq!(fr.classify_obb(&obb(6.5)), Containment::Outside);
+        assert_eq!(fr.classify_obb(&obb(0.0)), Containment::Inside);
+        assert_eq!(fr.classify_obb(&Obb::from_aabb(&cube(8.0, 0.0, -5.0, 1.0))), Containment::Outside);
+    }
+}
@@ -0,0 +1,400 @@
+//! GJK distance and EPA penetration depth between any two [`SupportMap`]
+//! shapes.
+//!
+//! Both algorithms are written for bit-identical results across peers:
+//!
+//! * Iteration counts are capped by [`GJK_MAX_ITERATIONS`] and
+//!   [`EPA_MAX_ITERATIONS`]; hitting a cap returns the best result so far
+//!   rather than failing.
+//! * The first search direction is `+X`, sub-simplices and polytope faces are
+//!   scanned in a fixed order with ties going to the earliest, and every
+//!   division is guarded, so a given backend always takes the same path.
+//! * GJK stops as soon as an iteration fails to shrink the distance, which
+//!   bounds the work in fixed point where rounding can stall convergence.
+
+use super::contact::{Contact, ContactPoint};
+use super::support::SupportMap;
+use super::{axis_vec, length, unit, Segment3, Triangle};
+use crate::scalar::RealScalar;
+use crate::{Direction3, Point3, Vec3};
+
+/// Iteration cap for [`gjk_distance`] and [`gjk_intersects`].
+pub const GJK_MAX_ITERATIONS: usize = 64;
+
+/// Iteration cap for the polytope expansion in [`epa_penetration`].
+pub const EPA_MAX_ITERATIONS: usize = 64;
+
+/// Closest points of two disjoint shapes.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Separation<T: RealScalar> {
+    pub distance: T,
+    /// Point of the first shape nearest to the second.
+    pub point_a: Point3<T>,
+    /// Point of the second shape nearest to the first.
+    pub point_b: Point3<T>,
+}
+
+/// Vertex of the Minkowski difference `a - b`, with the support points it
+/// came from.
+#[derive(Copy, Clone)]
+struct Vertex<T: RealScalar> {
+    w: Vec3<T>,
+    a: Point3<T>,
+    b: Point3<T>,
+}
+
+fn vertex<T: RealScalar>(a: &impl SupportMap<T>, b: &impl SupportMap<T>, dir: Vec3<T>) -> Vertex<T> {
+    let (pa, pb) = (a.support(Direction3(dir)), b.support(Direction3(-dir)));
+    Vertex { w: (pa - pb).0, a: pa, b: pb }
+}
+
+fn origin<T: RealScalar>() -> Point3<T> {
+    Point3(Vec3::splat(T::ZERO))
+}
+
+/// Closest point of the simplex to the origin with its barycentric weights,
+/// or `None` when the origin lies inside a tetrahedron.
+fn closest<T: RealScalar>(s: &[Vertex<T>]) -> Option<(Vec3<T>, Vec<T>)> {
+    let pt = |i: usize| Point3(s[i].w);
+    match s.len() {
+        1 => Some((s[0].w, vec![T::ONE])),
+        2 => {
+            let seg = Segment3::new(pt(0), pt(1));
+            let t = seg.closest_param(origin());
+            Some((seg.at(t).0, vec![T::ONE - t, t]))
+        }
+        3 => {
+            let tri = Triangle::new(pt(0), pt(1), pt(2));
+            let w = tri.closest_barycentric(origin());
+            Some((tri.from_barycentric(w).0, w.to_vec()))
+        }
+        _ => closest_in_tetrahedron(s),
+    }
+}
+
+fn det<T: RealScalar>(a: Vec3<T>, b: Vec3<T>, c: Vec3<T>) -> T {
+    a.dot(&b.cross(&c))
+}
+
+fn closest_in_tetrahedron<T: RealScalar>(s: &[Vertex<T>]) -> Option<(Vec3<T>, Vec<T>)> {
+    let [p0, p1, p2, p3] = [0, 1, 2, 3].map(|i| s[i].w);
+    let (e1, e2, e3) = (p1 - p0, p2 - p0, p3 - p0);
+    let volume = det(e1, e2, e3);
+    if volume != T::ZERO {
+        // Signed volumes with the origin standing in for each vertex.
+        let parts = [det(p1, p2, p3), det(-p0, e2, e3), det(e1, -p0, e3), det(e1, e2, -p0)];
+        if parts.iter().all(|&v| v * volume >= T::ZERO) {
+            return None;
+        }
+    }
+    let mut best: Option<(Vec3<T>, Vec<T>)> = None;
+    for skip in (0..4).rev() {
+        let face: Vec<Vertex<T>> = (0..4).filter(|&i| i != skip).map(|i| s[i]).collect();
+        let Some((v, w)) = closest(&face) else { continue };
+        if best.as_ref().map_or(true, |(b, _)| v.length_sq() < b.length_sq()) {
+            let mut weights = w;
+            weights.insert(skip, T::ZERO);
+            best = Some((v, weights));
+        }
+    }
+    best
+}
+
+enum Outcome<T: RealScalar> {
+    /// Reduced simplex and the weights of its closest point.
+    Disjoint(Vec<(Vertex<T>, T)>),
+    /// Simplex containing (or touching) the origin.
+    Overlapping(Vec<Vertex<T>>),
+}
+
+/// The distance tests work on [`length`] rather than squared lengths, which
+/// in fixed point would round every gap below about `sqrt(epsilon)` to zero.
+fn gjk<T: RealScalar>(a: &impl SupportMap<T>, b: &impl SupportMap<T>) -> Outcome<T> {
+    let first = vertex(a, b, axis_vec(0, T::ONE));
+    let mut simplex = vec![(first, T::ONE)];
+    let mut v = first.w;
+    let rel = T::epsilon() * T::from_f32(16.0);
+    for _ in 0..GJK_MAX_ITERATIONS {
+        let len = length(v);
+        if len <= T::epsilon() {
+            break;
+        }
+        let Ok(dir) = unit(v) else { break };
+        let w = vertex(a, b, -v);
+        // `dir · w` is a lower bound on the distance.
+        if len - dir.0.dot(&w.w) <= (len * rel).max(T::epsilon()) || simplex.iter().any(|(s, _)| s.w == w.w) {
+            break;
+        }
+        let candidate: Vec<Vertex<T>> = simplex.iter().map(|&(s, _)| s).chain([w]).collect();
+        let Some((next, weights)) = closest(&candidate) else {
+            return Outcome::Overlapping(candidate);
+        };
+        if length(next) >= len {
+            break;
+        }
+        simplex = candidate.into_iter().zip(weights).filter(|&(_, wt)| wt != T::ZERO).collect();
+        v = next;
+    }
+    if length(v) <= T::epsilon() {
+        Outcome::Overlapping(simplex.into_iter().map(|(s, _)| s).collect())
+    } else {
+        Outcome::Disjoint(simplex)
+    }
+}
+
+/// Returns `true` if the shapes overlap or touch.
+pub fn gjk_intersects<T: RealScalar>(a: &impl SupportMap<T>, b: &impl SupportMap<T>) -> bool {
+    matches!(gjk(a, b), Outcome::Overlapping(_))
+}
+
+/// Closest points of two convex shapes, or `None` if they overlap.
+pub fn gjk_distance<T: RealScalar>(a: &impl SupportMap<T>, b: &impl SupportMap<T>) -> Option<Separation<T>> {
+    let Outcome::Disjoint(simplex) = gjk(a, b) else { return None };
+    let zero = Vec3::splat(T::ZERO);
+    let (mut pa, mut pb) = (zero, zero);
+    for (s, wt) in simplex {
+        pa = pa + s.a.0 * wt;
+        pb = pb + s.b.0 * wt;
+    }
+    Some(Separation { distance: length(pb - pa), point_a: Point3(pa), point_b: Point3(pb) })
+}
+
+/// Grows an overlapping simplex into a tetrahedron by adding support points
+/// along the axes (and the triangle normal, for a triangle) until it has
+/// volume. Stops early if the Minkowski difference is flat.
+fn blow_up<T: RealScalar>(a: &impl SupportMap<T>, b: &impl SupportMap<T>, s: &mut Vec<Vertex<T>>) {
+    let zero = Vec3::splat(T::ZERO);
+    while s.len() < 4 {
+        let mut dirs = Vec::with_capacity(8);
+        if s.len() == 3 {
+            let n = (s[1].w - s[0].w).cross(&(s[2].w - s[0].w));
+            dirs.extend([n, -n]);
+        }
+        for axis in 0..3 {
+            dirs.extend([axis_vec(axis, T::ONE), axis_vec(axis, -T::ONE)]);
+        }
+        let raises = |w: Vec3<T>| match s.len() {
+            1 => w != s[0].w,
+            2 => (w - s[0].w).cross(&(s[1].w - s[0].w)) != zero,
+            _ => det(s[1].w - s[0].w, s[2].w - s[0].w, w - s[0].w) != T::ZERO,
+        };
+        let Some(next) = dirs.into_iter().filter(|&d| d != zero).map(|d| vertex(a, b, d)).find(|v| raises(v.w)) else {
+            return;
+        };
+        s.push(next);
+    }
+}
+
+struct Face<T: RealScalar> {
+    idx: [usize; 3],
+    normal: Vec3<T>,
+    dist: T,
+}
+
+/// Face through `idx` with its outward normal, or `None` if degenerate.
+fn face<T: RealScalar>(verts: &[Vertex<T>], idx: [usize; 3]) -> Option<Face<T>> {
+    let [p0, p1, p2] = idx.map(|i| verts[i].w);
+    let normal = unit((p1 - p0).cross(&(p2 - p0))).ok()?.0;
+    Some(Face { idx, normal, dist: normal.dot(&p0) })
+}
+
+fn edges(idx: [usize; 3]) -> [[usize; 2]; 3] {
+    [[idx[0], idx[1]], [idx[1], idx[2]], [idx[2], idx[0]]]
+}
+
+/// Faces seen from `w`, grown from `start` across shared edges so that the
+/// region stays connected even when rounding misjudges a distant face.
+fn visible_region<T: RealScalar>(verts: &[Vertex<T>], faces: &[Face<T>], start: usize, w: Vec3<T>, tolerance: T) -> Vec<bool> {
+    let sees = |f: &Face<T>| f.normal.dot(&(w - verts[f.idx[0]].w)) > tolerance;
+    let mut visible = vec![false; faces.len()];
+    visible[start] = true;
+    let mut stack = vec![start];
+    while let Some(current) = stack.pop() {
+        for [i, j] in edges(faces[current].idx) {
+            for (k, f) in faces.iter().enumerate() {
+                if !visible[k] && edges(f.idx).contains(&[j, i]) && sees(f) {
+                    visible[k] = true;
+                    stack.push(k);
+                }
+            }
+        }
+    }
+    visible
+}
+
+/// Contact for the polytope face nearest the origin.
+fn face_contact<T: RealScalar>(verts: &[Vertex<T>], f: &Face<T>) -> Contact<T> {
+    let [v0, v1, v2] = f.idx.map(|i| verts[i]);
+    let w = Triangle::new(Point3(v0.w), Point3(v1.w), Point3(v2.w)).closest_barycentric(origin());
+    let point = Point3(v0.b.0 * w[0] + v1.b.0 * w[1] + v2.b.0 * w[2]);
+    Contact::new(Direction3(f.normal), ContactPoint { point, depth: f.dist.max(T::ZERO) })
+}
+
+/// Zero-depth contact for a simplex that could not be grown into a polytope,
+/// along its plane's normal when it has one and `+Y` otherwise.
+fn flat_contact<T: RealScalar>(verts: &[Vertex<T>]) -> Contact<T> {
+    let plane = (verts.len() >= 3).then(|| (verts[1].w - verts[0].w).cross(&(verts[2].w - verts[0].w)));
+    let normal = plane.and_then(|n| unit(n).ok()).unwrap_or(Direction3(axis_vec(1, T::ONE)));
+    Contact::new(normal, ContactPoint { point: verts[0].b, depth: T::ZERO })
+}
+
+/// Penetration of two overlapping convex shapes as a one-point [`Contact`]
+/// (normal from `a` to `b`, point on `b`'s side), or `None` if they are
+/// disjoint.
+///
+/// Runs GJK first, then expands its final simplex with EPA. When the
+/// Minkowski difference is flat (e.g. two coplanar triangles) no polytope
+/// can be built, and a zero-depth contact along the flat difference's normal
+/// (or `+Y`) is returned.
+pub fn epa_penetration<T: RealScalar>(a: &impl SupportMap<T>, b: &impl SupportMap<T>) -> Option<Contact<T>> {
+    let Outcome::Overlapping(mut verts) = gjk(a, b) else { return None };
+    blow_up(a, b, &mut verts);
+    if verts.len() < 4 {
+        return Some(flat_contact(&verts));
+    }
+
+    // Orient the tetrahedron so that every face normal points outwards.
+    if det(verts[1].w - verts[0].w, verts[2].w - verts[0].w, verts[3].w - verts[0].w) > T::ZERO {
+        verts.swap(1, 2);
+    }
+    let mut faces: Vec<Face<T>> = [[0, 1, 2], [0, 3, 1], [0, 2, 3], [1, 3, 2]].into_iter().filter_map(|idx| face(&verts, idx)).collect();
+    let tolerance = T::epsilon() * T::from_f32(16.0);
+    let rel = T::from_f32(1.0 / 4096.0);
+
+    let mut previous: Option<(T, Contact<T>)> = None;
+    for iteration in 0..=EPA_MAX_ITERATIONS {
+        let Some(mut nearest) = (!faces.is_empty()).then_some(0) else { break };
+        for (k, f) in faces.iter().enumerate() {
+            if f.dist < faces[nearest].dist {
+                nearest = k;
+            }
+        }
+        let f = &faces[nearest];
+        let contact = face_contact(&verts, f);
+        // Once it encloses the origin the polytope only grows, so a closer
+        // face means rounding broke it.
+        if let Some((dist, prev)) = previous {
+            if dist > T::ZERO && f.dist < dist {
+                return Some(prev);
+            }
+        }
+        let w = vertex(a, b, f.normal);
+        let converged = w.w.dot(&f.normal) - f.dist <= (f.dist * rel).max(tolerance);
+        if converged || iteration == EPA_MAX_ITERATIONS || verts.iter().any(|v| v.w == w.w) {
+            return Some(contact);
+        }
+        previous = Some((f.dist, contact));
+
+        let k = verts.len();
+        verts.push(w);
+        let visible = visible_region(&verts, &faces, nearest, w.w, tolerance);
+        let mut horizon: Vec<[usize; 2]> = Vec::new();
+        for e in faces.iter().zip(&visible).filter(|(_, &v)| v).flat_map(|(f, _)| edges(f.idx)) {
+            match horizon.iter().position(|h| *h == [e[1], e[0]]) {
+                Some(shared) => {
+                    horizon.remove(shared);
+                }
+                None => horizon.push(e),
+            }
+        }
+        let mut keep = visible.iter().map(|v| !v);
+        faces.retain(|_| keep.next().unwrap_or(true));
+        faces.extend(horizon.into_iter().filter_map(|[i, j]| face(&verts, [i, j, k])));
+    }
+    Some(flat_contact(&verts))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geom::test_support::{fx, p, pf};
+    use crate::geom::{Aabb, Capsule, Collide, ConvexHull, Obb, Sphere};
+    use crate::scalar::{DFix64, F64Det, Scalar};
+    use crate::Mat3;
+
+    fn cube(center: Point3<DFix64>, half: f32) -> ConvexHull<DFix64> {
+        let h = fx(half);
+        let corners = (0..8).map(|i| {
+            let s = |bit: usize| if i & bit == 0 { -h } else { h };
+            Point3(center.0 + Vec3::new(s(1), s(2), s(4)))
+        });
+        ConvexHull::new(corners.collect()).unwrap()
+    }
+
+    #[test]
+    fn distance_between_boxes_and_hulls() {
+        let a = Aabb::new(p(0.0, 0.0, 0.0), p(1.0, 1.0, 1.0)).unwrap();
+        let b = Aabb::new(p(3.0, 0.5, 0.0), p(4.0, 2.0, 1.0)).unwrap();
+        let sep = gjk_distance(&a, &b).unwrap();
+        assert_eq!(sep.distance, fx(2.0));
+        assert_eq!((sep.point_a.0.x, sep.point_b.0.x), (fx(1.0), fx(3.0)));
+
+        let hull = cube(p(0.5, 0.5, 0.5), 0.5);
+        assert_eq!(gjk_distance(&hull, &b).unwrap().distance, fx(2.0));
+        assert!(gjk_intersects(&hull, &a));
+        assert!(gjk_distance(&hull, &a).is_none());
+        assert!(!gjk_intersects(&a, &b));
+    }
+
+    #[test]
+    fn distance_between_round_shapes() {
+        let s = Sphere::new(pf(0.0, 0.0, 0.0), F64Det(1.0)).unwrap();
+        let t = Sphere::new(pf(3.0, 4.0, 0.0), F64Det(1.5)).unwrap();
+        let sep = gjk_distance(&s, &t).unwrap();
+        assert!((sep.distance.0 - 2.5).abs() < 1e-6);
+        assert!((sep.point_a - pf(0.6, 0.8, 0.0)).0.length().0 < 1e-3);
+
+        let cap = Capsule::new(pf(-2.0, 3.0, 0.0), pf(2.0, 3.0, 0.0), F64Det(0.5)).unwrap();
+        assert!((gjk_distance(&s, &cap).unwrap().distance.0 - 1.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn epa_matches_primitive_contacts() {
+        let a = Sphere::new(pf(0.0, 0.0, 0.0), F64Det(1.0)).unwrap();
+        let b = Sphere::new(pf(1.5, 0.0, 0.0), F64Det(1.0)).unwrap();
+        let c = epa_penetration(&a, &b).unwrap();
+        let expected = a.contact(&b).unwrap();
+        assert!((c.depth().0 - expected.depth().0).abs() < 1e-3);
+        assert!((c.normal().0 - expected.normal().0).length().0 < 0.05);
+
+        let box_a = Obb::from_aabb(&Aabb::new(p(0.0, 0.0, 0.0), p(2.0, 2.0, 2.0)).unwrap());
+        let box_b = Obb::new(p(1.0, 2.5, 1.0), Vec3::splat(fx(0.75)), Mat3::identity()).unwrap();
+        let c = epa_penetration(&box_a, &box_b).unwrap();
+        assert_eq!((c.normal(), c.depth()), (Direction3::new(fx(0.0), fx(1.0), fx(0.0)), fx(0.25)));
+        assert_eq!(c.points()[0].point.0.y, fx(1.75));
+        assert!(epa_penetration(&box_a, &Sphere::new(p(5.0, 0.0, 0.0), fx(1.0)).unwrap()).is_none());
+    }
+
+    #[test]
+    fn near_touching_gaps_stay_disjoint() {
+        // Gaps well below sqrt(epsilon) in Q48.16.
+        let a = Sphere::new(p(0.0, 0.0, 0.0), fx(1.0)).unwrap();
+        let b = Sphere::new(p(2.003, 0.0, 0.0), fx(1.0)).unwrap();
+        assert!(!a.intersects(&b));
+        assert_eq!(gjk_intersects(&a, &b), a.intersects(&b));
+        let gap = gjk_distance(&a, &b).unwrap().distance;
+        assert!((gap - fx(0.003)).abs() <= fx(1e-4));
+        let still = Direction3::new(fx(0.0), fx(0.0), fx(0.0));
+        assert_eq!(crate::geom::conservative_advancement(&a, still, &b, still, fx(0.001)), None);
+
+        let c = Aabb::new(p(0.0, 0.0, 0.0), p(1.0, 1.0, 1.0)).unwrap();
+        let d = Aabb::new(p(1.002, 0.0, 0.0), p(2.0, 1.0, 1.0)).unwrap();
+        assert!(!c.intersects(&d) && !gjk_intersects(&c, &d));
+        assert!((gjk_distance(&c, &d).unwrap().distance - fx(0.002)).abs() <= fx(1e-4));
+        assert!(epa_penetration(&c, &d).is_none());
+
+        let touching = Aabb::new(p(1.0, 0.0, 0.0), p(2.0, 1.0, 1.0)).unwrap();
+        assert_eq!(gjk_intersects(&c, &touching), c.intersects(&touching));
+    }
+
+    #[test]
+    fn results_are_reproducible() {
+        let hull = cube(p(0.25, 0.0, 0.0), 1.0);
+        let cap = Capsule::new(p(-1.0, 1.5, 0.3), p(2.0, 0.5, -0.2), fx(0.75)).unwrap();
+        let first = epa_penetration(&hull, &cap).unwrap();
+        for _ in 0..4 {
+            assert_eq!(epa_penetration(&hull, &cap), Some(first));
+        }
+        assert!(first.depth() > DFix64::ZERO);
+    }
+}
@@ -0,0 +1,205 @@
+//! Geometric primitives for collision and spatial queries.
+//!
+//! * Every shape is generic over a [`RealScalar`], so a `DFix64` server and its
+//!   clients build and query identical shapes.
+//! * Shapes with invariants (`min <= max`, unit normals and directions,
+//!   non-negative radii) check them in their constructors and keep their
+//!   fields private; the remaining shapes are plain data with public fields.
+//! * Positions are [`Point3`]s and orientations are [`Direction3`]s, so the
+//!   type system keeps them apart.
+//! * Queries are traits implemented per shape or shape pair: [`RayCast`] for
+//!   rays, [`ClosestPoint`] for distances and [`Collide`] for overlap and
+//!   contact manifolds between primitive pairs. Any two [`SupportMap`]
+//!   shapes, convex hulls included, also go through GJK and EPA. [`Sweep`]
+//!   and [`conservative_advancement`] find the time of impact of moving
+//!   shapes.
+//! * Boxes and spheres can also be fitted to point sets
+//!   ([`Aabb::from_points`], [`Sphere::from_points_welzl`],
+//!   [`Obb::from_points_pca`] and friends).
+//! * [`Bvh`] indexes many boxes for ray, box, sphere and plane-set queries
+//!   with a deterministic build and result order; for crowds of similar
+//!   boxes, [`SpatialHash`] and [`sweep_and_prune`] report overlapping pairs
+//!   in a canonical sorted order.
+//! * [`Frustum`] extracts the six culling planes of a view-projection matrix,
+//!   classifies spheres and boxes against them and yields the corners of
+//!   cascade slices for shadow maps.
+//!
+//! # Determinism
+//!
+//! Degenerate and parallel configurations are resolved by explicit branches
+//! and fixed fallbacks rather than by dividing by zero, and ties go to the
+//! first candidate in a fixed order. Fixed-point backends therefore never
+//! panic, and every backend takes the same branch for the same input, so
+//! peers that agree on their inputs agree on every query result.
+//!
+//! [`Point3`]: crate::Point3
+//! [`Direction3`]: crate::Direction3
+
+mod aabb;
+mod broadphase;
+mod bvh;
+mod capsule;
+mod closest;
+mod contact;
+mod fit;
+mod frustum;
+mod gjk;
+mod obb;
+mod plane;
+mod ray;
+mod raycast;
+mod segment;
+mod sphere;
+mod support;
+mod sweep;
+mod triangle;
+
+#[cfg(test)]
+pub(crate) mod test_support;
+
+pub use aabb::Aabb;
+pub use broadphase::{sweep_and_prune, GridCell, SpatialHash, GRID_MAX_CELLS_PER_BOX};
+pub use bvh::{Bvh, BVH_BINS};
+pub use capsule::Capsule;
+pub use closest::ClosestPoint;
+pub use contact::{Collide, Contact, ContactPoint};
+pub use frustum::{Containment, DepthRange, Frustum};
+pub use gjk::{epa_penetration, gjk_distance, gjk_intersects, Separation, EPA_MAX_ITERATIONS, GJK_MAX_ITERATIONS};
+pub use obb::Obb;
+pub use plane::Plane;
+pub use ray::Ray3;
+pub use raycast::{RayCast, RayHit};
+pub use segment::Segment3;
+pub use sphere::Sphere;
+pub use support::{ConvexHull, SupportMap};
+pub use sweep::{conservative_advancement, Sweep, Toi, ADVANCEMENT_MAX_ITERATIONS};
+pub use triangle::Triangle;
+
+use core::fmt;
+
+use crate::scalar::RealScalar;
+use crate::{Direction3, Vec3};
+
+/// Reasons a shape is rejected at construction.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum GeomError {
+    /// A minimum corner exceeds the maximum on some axis, or an extent is
+    /// negative.
+    InvertedBounds,
+    /// A direction or normal has zero length (or its points are collinear).
+    ZeroDirection,
+    /// A radius is negative.
+    NegativeRadius,
+    /// A shape built from points was given none.
+    EmptyPointSet,
+    /// A grid cell size is zero or negative.
+    NonPositiveCellSize,
+}
+
+impl fmt::Display for GeomError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::InvertedBounds => "minimum corner exceeds maximum",
+            Self::ZeroDirection => "zero-length direction",
+            Self::NegativeRadius => "negative radius",
+            Self::EmptyPointSet => "empty point set",
+            Self::NonPositiveCellSize => "cell size is not positive",
+        })
+    }
+}
+
+impl std::error::Error for GeomError {}
+
+/// Normalizes `v`, rejecting the zero vector.
+///
+/// The vector is first scaled by a power of two (exact in every backend) so
+/// that its squared length neither underflows nor overflows in fixed point.
+/// Each component is then divided by the length rather than multiplied by
+/// its reciprocal, which keeps axis-aligned inputs exact unless scaling down
+/// rounded them.
+fn unit<T: RealScalar>(v: Vec3<T>) -> Result<Direction3<T>, GeomError> {
+    let (v, _) = rescale(v);
+    if v.length_sq() == T::ZERO {
+        return Err(GeomError::ZeroDirection);
+    }
+    let len = v.length();
+    Ok(Direction3::new(v.x / len, v.y / len, v.z / len))
+}
+
+/// Scales a nonzero `v` by `2^e` until its largest component lies in
+/// `[1/2, 1]`, returning the scaled vector and `e`; zero stays zero.
+fn rescale<T: RealScalar>(mut v: Vec3<T>) -> (Vec3<T>, i32) {
+    let largest = |v: Vec3<T>| v.x.abs().max(v.y.abs()).max(v.z.abs());
+    if largest(v) == T::ZERO {
+        return (v, 0);
+    }
+    let (coarse, fine, half) = (T::from_f32(256.0), T::ONE + T::ONE, T::from_f32(0.5));
+    let (coarse_inv, fine_inv) = (T::ONE / coarse, half);
+    let mut e = 0;
+    // Step counts cover the widest exponent range of any backend.
+    for _ in 0..128 {
+        if largest(v) * coarse >= half {
+            break;
+        }
+        v = v * coarse;
+        e += 8;
+    }
+    for _ in 0..128 {
+        if largest(v) <= coarse {
+            break;
+        }
+        v = v * coarse_inv;
+        e -= 8;
+    }
+    for _ in 0..8 {
+        if largest(v) >= half {
+            break;
+        }
+        v = v * fine;
+        e += 1;
+    }
+    for _ in 0..8 {
+        if largest(v) <= T::ONE {
+            break;
+        }
+        v = v * fine_inv;
+        e -= 1;
+    }
+    (v, e)
+}
+
+/// Length of `v`, measured on the rescaled vector so that it neither
+/// underflows nor overflows in fixed point.
+fn length<T: RealScalar>(v: Vec3<T>) -> T {
+    let (v, e) = rescale(v);
+    scale_pow2(v.length(), -e)
+}
+
+/// `x * 2^e`, in the same power-of-two steps as [`rescale`].
+fn scale_pow2<T: RealScalar>(mut x: T, mut e: i32) -> T {
+    let (coarse, fine) = (T::from_f32(256.0), T::ONE + T::ONE);
+    while e >= 8 {
+        x = x * coarse;
+        e -= 8;
+    }
+    while e <= -8 {
+        x = x / coarse;
+        e += 8;
+    }
+    while e > 0 {
+        x = x * fine;
+        e -= 1;
+    }
+    while e < 0 {
+        x = x / fine;
+        e += 1;
+    }
+    x
+}
+
+/// `sign` times the unit vector along `axis`.
+fn axis_vec<T: RealScalar>(axis: usize, sign: T) -> Vec3<T> {
+    let mut v = [T::ZERO; 3];
+    v[axis] = sign;
+    Vec3::from(v)
+}
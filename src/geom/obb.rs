@@ -0,0 +1,108 @@
+use super::{Aabb, GeomError};
+use crate::scalar::RealScalar;
+use crate::{Direction3, Mat3, Mat4, Point3, Quat, Vec3};
+
+/// Oriented bounding box: a box of `half_extents` around `center`, whose
+/// local axes are the columns of `rotation`.
+///
+/// `rotation` is expected to be orthonormal (a pure rotation); queries treat
+/// its transpose as its inverse.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Obb<T: RealScalar> {
+    center: Point3<T>,
+    half_extents: Vec3<T>,
+    rotation: Mat3<T>,
+}
+
+impl<T: RealScalar> Obb<T> {
+    /// Returns [`GeomError::InvertedBounds`] for a negative half extent.
+    pub fn new(center: Point3<T>, half_extents: Vec3<T>, rotation: Mat3<T>) -> Result<Self, GeomError> {
+        if half_extents.x < T::ZERO || half_extents.y < T::ZERO || half_extents.z < T::ZERO {
+            return Err(GeomError::InvertedBounds);
+        }
+        Ok(Self { center, half_extents, rotation })
+    }
+
+    /// The same box as `aabb`, with identity rotation.
+    pub fn from_aabb(aabb: &Aabb<T>) -> Self {
+        Self { center: aabb.center(), half_extents: aabb.half_extents(), rotation: Mat3::identity() }
+    }
+
+    pub fn center(&self) -> Point3<T> {
+        self.center
+    }
+
+    pub fn half_extents(&self) -> Vec3<T> {
+        self.half_extents
+    }
+
+    pub fn rotation(&self) -> Mat3<T> {
+        self.rotation
+    }
+
+    /// Local axis `i` in world space.
+    ///
+    /// # Panics
+    /// Panics if `i >= 3`.
+    pub fn axis(&self, i: usize) -> Direction3<T> {
+        Direction3(self.rotation.col(i))
+    }
+
+    /// Expresses a world-space point in the box's local frame.
+    pub fn to_local(&self, point: Point3<T>) -> Vec3<T> {
+        self.rotation.transform_transposed((point - self.center).0)
+    }
+
+    /// Maps a local-frame point back to world space.
+    pub fn to_world(&self, local: Vec3<T>) -> Point3<T> {
+        self.center + Direction3(self.rotation.transform(local))
+    }
+
+    /// Returns `true` if `point` lies inside or on the box.
+    pub fn contains_point(&self, point: Point3<T>) -> bool {
+        let (p, h) = (self.to_local(point), self.half_extents);
+        p.x.abs() <= h.x && p.y.abs() <= h.y && p.z.abs() <= h.z
+    }
+}
+
+impl Obb<f32> {
+    /// Box whose orientation is given by a unit quaternion.
+    ///
+    /// Returns [`GeomError::InvertedBounds`] for a negative half extent.
+    pub fn from_quat(center: Point3<f32>, half_extents: Vec3<f32>, rotation: &Quat) -> Result<Self, GeomError> {
+        let m = Mat4::from_quat(rotation);
+        let [x, y, z] = [Vec3::new(1.0, 0.0, 0.0), Vec3::new(0.0, 1.0, 0.0), Vec3::new(0.0, 0.0, 1.0)].map(|axis| m.transform_direction(&axis));
+        Self::new(center, half_extents, Mat3::from_cols(x, y, z))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scalar::{DFix64, Scalar};
+
+    fn v(x: f32, y: f32, z: f32) -> Vec3<DFix64> {
+        Vec3::new(DFix64::from_f32(x), DFix64::from_f32(y), DFix64::from_f32(z))
+    }
+
+    #[test]
+    fn local_frame_round_trips() {
+        let rot = Mat3::from_cols(v(0.0, 1.0, 0.0), v(-1.0, 0.0, 0.0), v(0.0, 0.0, 1.0));
+        let obb = Obb::new(Point3(v(1.0, 1.0, 0.0)), v(2.0, 0.5, 1.0), rot).unwrap();
+        assert_eq!(obb.to_local(Point3(v(1.0, 3.0, 0.0))), v(2.0, 0.0, 0.0));
+        assert_eq!(obb.to_world(v(2.0, 0.0, 0.0)), Point3(v(1.0, 3.0, 0.0)));
+        assert!(obb.contains_point(Point3(v(1.25, 2.5, 0.5))));
+        assert!(!obb.contains_point(Point3(v(2.0, 1.0, 0.0))));
+        assert_eq!(Obb::new(Point3(v(0.0, 0.0, 0.0)), v(1.0, 1.0, -1.0), rot), Err(GeomError::InvertedBounds));
+    }
+
+    #[test]
+    fn quaternion_sets_the_axes() {
+        let q = Quat::from_axis_angle(Vec3::new(0.0, 0.0, 1.0), core::f32::consts::FRAC_PI_2);
+        let obb = Obb::from_quat(Point3::new(0.0, 0.0, 0.0), Vec3::new(2.0, 0.5, 1.0), &q).unwrap();
+        let x = obb.axis(0).0;
+        assert!(x.x.abs() < 1e-6 && (x.y - 1.0).abs() < 1e-6);
+        assert!(obb.contains_point(Point3::new(0.0, 1.9, 0.0)));
+        assert!(!obb.contains_point(Point3::new(1.9, 0.0, 0.0)));
+    }
+}
@@ -0,0 +1,108 @@
+use super::{rescale, scale_pow2, unit, GeomError};
+use crate::scalar::RealScalar;
+use crate::{Direction3, Point3};
+
+/// Plane `normal · p + d = 0` with a unit normal.
+///
+/// [`Plane::signed_distance`] is positive on the side the normal points to.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Plane<T: RealScalar> {
+    normal: Direction3<T>,
+    d: T,
+}
+
+impl<T: RealScalar> Plane<T> {
+    /// Creates the plane `normal · p + d = 0`, scaling `d` along with the
+    /// normal as it is normalized.
+    ///
+    /// Returns [`GeomError::ZeroDirection`] for a zero-length normal.
+    pub fn new(normal: Direction3<T>, d: T) -> Result<Self, GeomError> {
+        // Scale `d` with the normal so tiny or huge normals keep their length
+        // representable, shrinking it last so it rounds only once.
+        let (scaled, e) = rescale(normal.0);
+        let unit_normal = unit(scaled)?;
+        let len = scaled.length();
+        let d = if e < 0 { scale_pow2(d / len, e) } else { scale_pow2(d, e) / len };
+        Ok(Self { normal: unit_normal, d })
+    }
+
+    /// Plane through `point` with the given normal.
+    pub fn from_point_normal(point: Point3<T>, normal: Direction3<T>) -> Result<Self, GeomError> {
+        let normal = unit(normal.0)?;
+        Ok(Self { normal, d: -normal.0.dot(&point.0) })
+    }
+
+    /// Plane through three points, facing the side from which `a, b, c` wind
+    /// counter-clockwise.
+    ///
+    /// Returns [`GeomError::ZeroDirection`] if the points are collinear.
+    pub fn from_points(a: Point3<T>, b: Point3<T>, c: Point3<T>) -> Result<Self, GeomError> {
+        let normal = (b - a).0.cross(&(c - a).0);
+        Self::from_point_normal(a, Direction3(normal))
+    }
+
+    /// Unit normal.
+    pub fn normal(&self) -> Direction3<T> {
+        self.normal
+    }
+
+    /// Offset `d`: minus the distance from the origin along the normal.
+    pub fn d(&self) -> T {
+        self.d
+    }
+
+    /// Signed distance from `point` to the plane.
+    pub fn signed_distance(&self, point: Point3<T>) -> T {
+        self.normal.0.dot(&point.0) + self.d
+    }
+
+    /// The same plane facing the other way.
+    pub fn flipped(&self) -> Self {
+        Self { normal: -self.normal, d: -self.d }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geom::test_support::p;
+    use crate::scalar::{DFix64, Scalar};
+
+    #[test]
+    fn constructions_agree() {
+        let from_points = Plane::from_points(p(0.0, 2.0, 0.0), p(0.0, 2.0, 1.0), p(1.0, 2.0, 0.0)).unwrap();
+        let up = Direction3::new(DFix64::ZERO, DFix64::from_f32(3.0), DFix64::ZERO);
+        let from_normal = Plane::from_point_normal(p(5.0, 2.0, -1.0), up).unwrap();
+        let raw = Plane::new(up, DFix64::from_f32(-6.0)).unwrap();
+        assert_eq!(from_points, from_normal);
+        assert_eq!(raw, from_normal);
+        assert_eq!(from_points.signed_distance(p(3.0, -1.0, 7.0)), DFix64::from_f32(-3.0));
+        assert_eq!(from_points.flipped().signed_distance(p(3.0, -1.0, 7.0)), DFix64::from_f32(3.0));
+    }
+
+    #[test]
+    fn tiny_and_huge_normals() {
+        // A normal of one raw unit would have a zero fixed-point length.
+        let tiny = Plane::new(Direction3::new(DFix64(1), DFix64::ZERO, DFix64::ZERO), DFix64::ONE).unwrap();
+        assert_eq!(tiny.normal(), Direction3::new(DFix64::ONE, DFix64::ZERO, DFix64::ZERO));
+        assert_eq!(tiny.d(), DFix64::from_f32(65536.0));
+        let small = Plane::new(Direction3::new(DFix64(3), DFix64(4), DFix64::ZERO), DFix64(10)).unwrap();
+        assert_eq!(small.d(), DFix64::from_f32(2.0));
+
+        // Squaring these components would wrap in Q48.16.
+        let big = Plane::from_points(p(0.0, 0.0, 0.0), p(5000.0, 0.0, 0.0), p(0.0, 5000.0, 0.0)).unwrap();
+        let n = big.normal().0;
+        assert!(n.x == DFix64::ZERO && n.y == DFix64::ZERO && (n.z - DFix64::ONE).abs() <= DFix64(1));
+        assert_eq!(big.d(), DFix64::ZERO);
+        let huge = Plane::new(Direction3::new(DFix64::from_f32(3e7), DFix64::from_f32(4e7), DFix64::ZERO), DFix64::from_f32(-1e8)).unwrap();
+        assert!((huge.normal().0.length() - DFix64::ONE).abs() <= DFix64(4));
+        assert!((huge.d() - DFix64::from_f32(-2.0)).abs() <= DFix64(8));
+        assert!(crate::Ray3::new(p(0.0, 0.0, 0.0), Direction3::new(DFix64::from_f32(2e7), DFix64::ZERO, DFix64::ZERO)).is_ok());
+    }
+
+    #[test]
+    fn rejects_degenerate_input() {
+        assert_eq!(Plane::from_points(p(0.0, 0.0, 0.0), p(1.0, 1.0, 1.0), p(2.0, 2.0, 2.0)), Err(GeomError::ZeroDirection));
+        assert_eq!(Plane::new(Direction3::new(DFix64::ZERO, DFix64::ZERO, DFix64::ZERO), DFix64::ONE), Err(GeomError::ZeroDirection));
+    }
+}
@@ -0,0 +1,56 @@
+use super::{unit, GeomError};
+use crate::scalar::RealScalar;
+use crate::{Direction3, Point3};
+
+/// Half-line from `origin` along a unit `direction`.
+///
+/// Because the direction is unit length, the parameter `t` of
+/// [`Ray3::at`] is a distance.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Ray3<T: RealScalar> {
+    origin: Point3<T>,
+    direction: Direction3<T>,
+}
+
+impl<T: RealScalar> Ray3<T> {
+    /// Creates a ray, normalizing `direction`.
+    ///
+    /// Returns [`GeomError::ZeroDirection`] for a zero-length direction.
+    pub fn new(origin: Point3<T>, direction: Direction3<T>) -> Result<Self, GeomError> {
+        Ok(Self { origin, direction: unit(direction.0)? })
+    }
+
+    /// Ray from `origin` through `target`.
+    pub fn through(origin: Point3<T>, target: Point3<T>) -> Result<Self, GeomError> {
+        Self::new(origin, target - origin)
+    }
+
+    pub fn origin(&self) -> Point3<T> {
+        self.origin
+    }
+
+    /// Unit direction.
+    pub fn direction(&self) -> Direction3<T> {
+        self.direction
+    }
+
+    /// Point at distance `t` along the ray.
+    pub fn at(&self, t: T) -> Point3<T> {
+        self.origin + self.direction * t
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scalar::{DFix64, Scalar};
+
+    #[test]
+    fn normalizes_and_rejects_zero_direction() {
+        let o = Point3::new(DFix64::ONE, DFix64::ZERO, DFix64::ZERO);
+        let ray = Ray3::new(o, Direction3::new(DFix64::ZERO, DFix64::from_f32(4.0), DFix64::ZERO)).unwrap();
+        assert_eq!(ray.direction(), Direction3::new(DFix64::ZERO, DFix64::ONE, DFix64::ZERO));
+        assert_eq!(ray.at(DFix64::from_f32(2.5)), Point3::new(DFix64::ONE, DFix64::from_f32(2.5), DFix64::ZERO));
+        assert_eq!(Ray3::through(o, o), Err(GeomError::ZeroDirection));
+    }
+}
@@ -0,0 +1,371 @@
+//! Ray casts against the primitive shapes.
+//!
+//! Conventions shared by every cast:
+//!
+//! * Only hits with `0 <= distance <= max_distance` are reported. Ray
+//!   directions are unit length, so `distance` is a true distance.
+//! * The reported normal is unit length and faces against the ray, whichever
+//!   side of a plane or triangle was hit.
+//! * A ray starting inside a solid (box, sphere, capsule) hits at distance 0
+//!   with normal `-direction`.
+//! * Parallel and degenerate configurations are handled by explicit branches,
+//!   never by dividing by zero; see [determinism](super#determinism).
+
+use super::{axis_vec, unit, Aabb, Capsule, Obb, Plane, Ray3, Sphere, Triangle};
+use crate::scalar::RealScalar;
+use crate::{Direction3, Point3, Vec3};
+
+/// First intersection of a ray with a shape.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct RayHit<T: RealScalar> {
+    /// Distance from the ray origin.
+    pub distance: T,
+    pub point: Point3<T>,
+    /// Unit surface normal facing against the ray.
+    pub normal: Direction3<T>,
+}
+
+/// Shapes that can be hit by a [`Ray3`].
+pub trait RayCast<T: RealScalar> {
+    /// Returns the first hit within `max_distance` of the ray origin.
+    fn cast_ray(&self, ray: &Ray3<T>, max_distance: T) -> Option<RayHit<T>>;
+}
+
+fn inside_hit<T: RealScalar>(ray: &Ray3<T>) -> RayHit<T> {
+    RayHit { distance: T::ZERO, point: ray.origin(), normal: -ray.direction() }
+}
+
+/// Hit at `distance` whose normal points from `core` to the hit point.
+fn radial_hit<T: RealScalar>(ray: &Ray3<T>, distance: T, core: Point3<T>) -> RayHit<T> {
+    let point = ray.at(distance);
+    let normal = unit((point - core).0).unwrap_or(-ray.direction());
+    RayHit { distance, point, normal }
+}
+
+/// Entry distance into the sphere `(center, radius)` for a ray starting
+/// outside it.
+fn sphere_entry<T: RealScalar>(ray: &Ray3<T>, center: Point3<T>, radius: T) -> Option<T> {
+    let m = (ray.origin() - center).0;
+    let b = m.dot(&ray.direction().0);
+    let c = m.length_sq() - radius * radius;
+    if b > T::ZERO {
+        return None;
+    }
+    let disc = b * b - c;
+    if disc < T::ZERO {
+        return None;
+    }
+    Some((-b - disc.sqrt()).max(T::ZERO))
+}
+
+/// Slab test against `[lo, hi]` for a ray given in the box's own frame.
+///
+/// Returns the entry distance and the entered face as `(axis, outward
+/// sign)`, or no face when the origin is inside. Ties between axes go to the
+/// lowest axis.
+pub(super) fn slab<T: RealScalar>(origin: Vec3<T>, dir: Vec3<T>, lo: Vec3<T>, hi: Vec3<T>, max_distance: T) -> Option<(T, Option<(usize, T)>)> {
+    let (o, d, lo, hi) = (origin.to_array(), dir.to_array(), lo.to_array(), hi.to_array());
+    let (mut enter, mut exit, mut face) = (T::ZERO, max_distance, None);
+    for axis in 0..3 {
+        if d[axis] == T::ZERO {
+            if o[axis] < lo[axis] || o[axis] > hi[axis] {
+                return None;
+            }
+            continue;
+        }
+        let (mut near, mut far, mut sign) = ((lo[axis] - o[axis]) / d[axis], (hi[axis] - o[axis]) / d[axis], -T::ONE);
+        if near > far {
+            (near, far, sign) = (far, near, T::ONE);
+        }
+        if near > enter {
+            (enter, face) = (near, Some((axis, sign)));
+        }
+        exit = exit.min(far);
+        if enter > exit {
+            return None;
+        }
+    }
+    Some((enter, face))
+}
+
+impl<T: RealScalar> RayCast<T> for Aabb<T> {
+    /// Slab test.
+    fn cast_ray(&self, ray: &Ray3<T>, max_distance: T) -> Option<RayHit<T>> {
+        match slab(ray.origin().0, ray.direction().0, self.min().0, self.max().0, max_distance)? {
+            (_, None) => Some(inside_hit(ray)),
+            (distance, Some((axis, sign))) => {
+                Some(RayHit { distance, point: ray.at(distance), normal: Direction3(axis_vec(axis, sign)) })
+            }
+        }
+    }
+}
+
+impl<T: RealScalar> RayCast<T> for Obb<T> {
+    /// Slab test in the box's local frame.
+    fn cast_ray(&self, ray: &Ray3<T>, max_distance: T) -> Option<RayHit<T>> {
+        let origin = self.to_local(ray.origin());
+        let dir = self.rotation().transform_transposed(ray.direction().0);
+        let h = self.half_extents();
+        match slab(origin, dir, -h, h, max_distance)? {
+            (_, None) => Some(inside_hit(ray)),
+            (distance, Some((axis, sign))) => {
+                let normal = Direction3(self.axis(axis).0 * sign);
+                Some(RayHit { distance, point: ray.at(distance), normal })
+            }
+        }
+    }
+}
+
+impl<T: RealScalar> RayCast<T> for Sphere<T> {
+    fn cast_ray(&self, ray: &Ray3<T>, max_distance: T) -> Option<RayHit<T>> {
+        if self.contains_point(ray.origin()) {
+            return Some(inside_hit(ray));
+        }
+        let distance = sphere_entry(ray, self.center(), self.radius())?;
+        (distance <= max_distance).then(|| radial_hit(ray, distance, self.center()))
+    }
+}
+
+impl<T: RealScalar> RayCast<T> for Plane<T> {
+    /// Hits the plane from either side; a ray parallel to it never hits, even
+    /// when lying in it.
+    fn cast_ray(&self, ray: &Ray3<T>, max_distance: T) -> Option<RayHit<T>> {
+        let denom = self.normal().0.dot(&ray.direction().0);
+        if denom == T::ZERO {
+            return None;
+        }
+        let distance = -self.signed_distance(ray.origin()) / denom;
+        if distance < T::ZERO || distance > max_distance {
+            return None;
+        }
+        let normal = if denom < T::ZERO { self.normal() } else { -self.normal() };
+        Some(RayHit { distance, point: ray.at(distance), normal })
+    }
+}
+
+/// Orients the triangle's normal against the ray, or `None` if degenerate.
+fn facing_normal<T: RealScalar>(triangle: &Triangle<T>, ray: &Ray3<T>) -> Option<Direction3<T>> {
+    let n = triangle.normal()?;
+    Some(if n.0.dot(&ray.direction().0) > T::ZERO { -n } else { n })
+}
+
+impl<T: RealScalar> RayCast<T> for Triangle<T> {
+    /// Möller–Trumbore, two-sided. Barycentric bounds are compared before the
+    /// single division by the determinant, so the inside test itself involves
+    /// no rounding from division.
+    fn cast_ray(&self, ray: &Ray3<T>, max_distance: T) -> Option<RayHit<T>> {
+        let d = ray.direction().0;
+        let (e1, e2) = ((self.b - self.a).0, (self.c - self.a).0);
+        let p = d.cross(&e2);
+        let det = e1.dot(&p);
+        if det == T::ZERO {
+            return None;
+        }
+        let s = (ray.origin() - self.a).0;
+        let q = s.cross(&e1);
+        let (mut u, mut v, mut t, mut det) = (s.dot(&p), d.dot(&q), e2.dot(&q), det);
+        if det < T::ZERO {
+            (u, v, t, det) = (-u, -v, -t, -det);
+        }
+        if u < T::ZERO || v < T::ZERO || u + v > det || t < T::ZERO {
+            return None;
+        }
+        let distance = t / det;
+        if distance > max_distance {
+            return None;
+        }
+        Some(RayHit { distance, point: ray.at(distance), normal: facing_normal(self, ray)? })
+    }
+}
+
+impl<T: RealScalar> Triangle<T> {
+    /// Watertight ray/triangle test (Woop, Benthin and Wald, 2013), two-sided.
+    ///
+    /// Edge functions are evaluated from each edge's two vertices alone, so a
+    /// ray through an edge shared by two triangles of a mesh hits at least
+    /// one of them: no cracks along seams. Slightly costlier than
+    /// [`RayCast::cast_ray`].
+    pub fn cast_ray_watertight(&self, ray: &Ray3<T>, max_distance: T) -> Option<RayHit<T>> {
+        let dir = ray.direction().0.to_array();
+        let mut kz = 0;
+        for axis in 1..3 {
+            if dir[axis].abs() > dir[kz].abs() {
+                kz = axis;
+            }
+        }
+        let (mut kx, mut ky) = ((kz + 1) % 3, (kz + 2) % 3);
+        if dir[kz] < T::ZERO {
+            (kx, ky) = (ky, kx);
+        }
+        let (sx, sy, sz) = (dir[kx] / dir[kz], dir[ky] / dir[kz], T::ONE / dir[kz]);
+
+        let shear = |p: Point3<T>| {
+            let v = (p - ray.origin()).0.to_array();
+            (v[kx] - sx * v[kz], v[ky] - sy * v[kz], sz * v[kz])
+        };
+        let ((ax, ay, az), (bx, by, bz), (cx, cy, cz)) = (shear(self.a), shear(self.b), shear(self.c));
+
+        let u = cx * by - cy * bx;
+        let v = ax * cy - ay * cx;
+        let w = bx * ay - by * ax;
+        let zero = T::ZERO;
+        if (u < zero || v < zero || w < zero) && (u > zero || v > zero || w > zero) {
+            return None;
+        }
+        let det = u + v + w;
+        if det == zero {
+            return None;
+        }
+        let distance = (u * az + v * bz + w * cz) / det;
+        if distance < zero || distance > max_distance {
+            return None;
+        }
+        Some(RayHit { distance, point: ray.at(distance), normal: facing_normal(self, ray)? })
+    }
+}
+
+impl<T: RealScalar> RayCast<T> for Capsule<T> {
+    /// Nearest of the cylinder body (solved perpendicular to the axis) and
+    /// the two end spheres; ties go to the body, then the start cap.
+    fn cast_ray(&self, ray: &Ray3<T>, max_distance: T) -> Option<RayHit<T>> {
+        let seg = self.segment();
+        let r = self.radius();
+        let ba = seg.delta().0;
+        let oa = (ray.origin() - seg.start).0;
+        let len_sq = ba.length_sq();
+
+        let s = if len_sq == T::ZERO { T::ZERO } else { (oa.dot(&ba) / len_sq).clamp(T::ZERO, T::ONE) };
+        if (ray.origin() - seg.at(s)).0.length_sq() <= r * r {
+            return Some(inside_hit(ray));
+        }
+
+        let mut best: Option<(T, Point3<T>)> = None;
+        let mut consider = |t: T, core: Point3<T>| {
+            if t <= max_distance && best.map_or(true, |(b, _)| t < b) {
+                best = Some((t, core));
+            }
+        };
+
+        if len_sq > T::ZERO {
+            let len = len_sq.sqrt();
+            let n = Vec3::new(ba.x / len, ba.y / len, ba.z / len);
+            let d = ray.direction().0;
+            let (dn, on) = (d.dot(&n), oa.dot(&n));
+            let (d_perp, m_perp) = (d - n * dn, oa - n * on);
+            let a = d_perp.length_sq();
+            if a > T::ZERO {
+                let b = m_perp.dot(&d_perp);
+                let c = m_perp.length_sq() - r * r;
+                let disc = b * b - a * c;
+                if disc >= T::ZERO {
+                    let t = (-b - disc.sqrt()) / a;
+                    let axial = on + t * dn;
+                    if t >= T::ZERO && axial >= T::ZERO && axial <= len {
+                        consider(t, seg.start + Direction3(n * axial));
+                    }
+                }
+            }
+        }
+        for center in [seg.start, seg.end] {
+            if let Some(t) = sphere_entry(ray, center, r) {
+                consider(t, center);
+            }
+        }
+        best.map(|(t, core)| radial_hit(ray, t, core))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geom::test_support::{dir, fx, p};
+    use crate::scalar::{DFix64, F64Det, Scalar};
+    use crate::Mat3;
+
+    fn ray(o: Point3<DFix64>, d: Direction3<DFix64>) -> Ray3<DFix64> {
+        Ray3::new(o, d).unwrap()
+    }
+
+    #[test]
+    fn aabb_slab_reports_entry_face() {
+        let b = Aabb::new(p(-1.0, -1.0, -1.0), p(1.0, 1.0, 1.0)).unwrap();
+        let hit = b.cast_ray(&ray(p(-5.0, 0.5, 0.0), dir(1.0, 0.0, 0.0)), fx(10.0)).unwrap();
+        assert_eq!((hit.distance, hit.point, hit.normal), (fx(4.0), p(-1.0, 0.5, 0.0), dir(-1.0, 0.0, 0.0)));
+        assert!(b.cast_ray(&ray(p(-5.0, 0.5, 0.0), dir(1.0, 0.0, 0.0)), fx(3.5)).is_none());
+        assert!(b.cast_ray(&ray(p(-5.0, 2.0, 0.0), dir(1.0, 0.0, 0.0)), fx(10.0)).is_none());
+        let inside = b.cast_ray(&ray(p(0.0, 0.0, 0.0), dir(0.0, 1.0, 0.0)), fx(10.0)).unwrap();
+        assert_eq!((inside.distance, inside.normal), (DFix64::ZERO, dir(0.0, -1.0, 0.0)));
+        let top = b.cast_ray(&ray(p(0.0, 3.0, 0.0), dir(0.0, -1.0, 0.0)), fx(10.0)).unwrap();
+        assert_eq!((top.distance, top.normal), (fx(2.0), dir(0.0, 1.0, 0.0)));
+    }
+
+    #[test]
+    fn obb_hits_rotated_faces() {
+        let rot = Mat3::from_cols(dir(0.0, 1.0, 0.0).0, dir(-1.0, 0.0, 0.0).0, dir(0.0, 0.0, 1.0).0);
+        let obb = Obb::new(p(0.0, 0.0, 0.0), Vec3::new(fx(2.0), fx(0.5), fx(0.5)), rot).unwrap();
+        let hit = obb.cast_ray(&ray(p(0.0, -5.0, 0.0), dir(0.0, 1.0, 0.0)), fx(10.0)).unwrap();
+        assert_eq!((hit.distance, hit.normal), (fx(3.0), dir(0.0, -1.0, 0.0)));
+        let side = obb.cast_ray(&ray(p(5.0, 0.0, 0.0), dir(-1.0, 0.0, 0.0)), fx(10.0)).unwrap();
+        assert_eq!((side.distance, side.normal), (fx(4.5), dir(1.0, 0.0, 0.0)));
+    }
+
+    #[test]
+    fn sphere_and_plane() {
+        let s = Sphere::new(p(0.0, 0.0, 5.0), fx(2.0)).unwrap();
+        let hit = s.cast_ray(&ray(p(0.0, 0.0, 0.0), dir(0.0, 0.0, 1.0)), fx(10.0)).unwrap();
+        assert_eq!((hit.distance, hit.point, hit.normal), (fx(3.0), p(0.0, 0.0, 3.0), dir(0.0, 0.0, -1.0)));
+        assert!(s.cast_ray(&ray(p(0.0, 0.0, 0.0), dir(0.0, 0.0, -1.0)), fx(10.0)).is_none());
+        assert!(s.cast_ray(&ray(p(0.0, 2.5, 0.0), dir(0.0, 0.0, 1.0)), fx(10.0)).is_none());
+
+        let ground = Plane::from_point_normal(p(0.0, 1.0, 0.0), dir(0.0, 1.0, 0.0)).unwrap();
+        let below = ground.cast_ray(&ray(p(0.0, -2.0, 0.0), dir(0.0, 1.0, 0.0)), fx(10.0)).unwrap();
+        assert_eq!((below.distance, below.normal), (fx(3.0), dir(0.0, -1.0, 0.0)));
+        assert!(ground.cast_ray(&ray(p(0.0, -2.0, 0.0), dir(1.0, 0.0, 0.0)), fx(10.0)).is_none());
+    }
+
+    #[test]
+    fn triangle_variants_agree() {
+        let tri = Triangle::new(p(0.0, 0.0, 0.0), p(4.0, 0.0, 0.0), p(0.0, 4.0, 0.0));
+        for (o, d, expected) in [
+            (p(1.0, 1.0, 3.0), dir(0.0, 0.0, -1.0), Some((fx(3.0), dir(0.0, 0.0, 1.0)))),
+            (p(1.0, 1.0, -2.0), dir(0.0, 0.0, 1.0), Some((fx(2.0), dir(0.0, 0.0, -1.0)))),
+            (p(3.0, 3.0, 3.0), dir(0.0, 0.0, -1.0), None),
+            (p(1.0, 1.0, 3.0), dir(1.0, 0.0, 0.0), None),
+        ] {
+            let r = ray(o, d);
+            let mt = tri.cast_ray(&r, fx(10.0)).map(|h| (h.distance, h.normal));
+            let wt = tri.cast_ray_watertight(&r, fx(10.0)).map(|h| (h.distance, h.normal));
+            assert_eq!(mt, expected);
+            assert_eq!(wt, expected);
+        }
+    }
+
+    #[test]
+    fn watertight_leaves_no_seam() {
+        // A quad split along its diagonal; rays straddle the shared edge.
+        let (a, b, c, d) = (p(0.0, 0.0, 0.0), p(1.0, 0.0, 0.0), p(1.0, 1.0, 0.0), p(0.0, 1.0, 0.0));
+        let (t0, t1) = (Triangle::new(a, b, c), Triangle::new(a, c, d));
+        for i in 1..200 {
+            let x = DFix64(i * 327);
+            let r = ray(Point3::new(x, x, fx(1.0)), dir(0.0, 0.0, -1.0));
+            let hits = [&t0, &t1].iter().filter(|t| t.cast_ray_watertight(&r, fx(2.0)).is_some()).count();
+            assert!(hits >= 1, "ray through the seam at {x:?} slipped through");
+        }
+    }
+
+    #[test]
+    fn capsule_body_caps_and_inside() {
+        let cap = Capsule::new(p(0.0, 0.0, 0.0), p(0.0, 4.0, 0.0), fx(1.0)).unwrap();
+        let body = cap.cast_ray(&ray(p(-5.0, 2.0, 0.0), dir(1.0, 0.0, 0.0)), fx(10.0)).unwrap();
+        assert_eq!((body.distance, body.normal), (fx(4.0), dir(-1.0, 0.0, 0.0)));
+        let top = cap.cast_ray(&ray(p(0.0, 9.0, 0.0), dir(0.0, -1.0, 0.0)), fx(10.0)).unwrap();
+        assert_eq!((top.distance, top.point, top.normal), (fx(4.0), p(0.0, 5.0, 0.0), dir(0.0, 1.0, 0.0)));
+        assert!(cap.cast_ray(&ray(p(-5.0, 2.0, 1.5), dir(1.0, 0.0, 0.0)), fx(10.0)).is_none());
+        assert_eq!(cap.cast_ray(&ray(p(0.5, 2.0, 0.0), dir(1.0, 0.0, 0.0)), fx(10.0)).unwrap().distance, DFix64::ZERO);
+
+        let f = Capsule::new(Point3::new(F64Det(0.0), F64Det(0.0), F64Det(0.0)), Point3::new(F64Det(0.0), F64Det(4.0), F64Det(0.0)), F64Det(1.0)).unwrap();
+        let slanted = Ray3::new(Point3::new(F64Det(-3.0), F64Det(-3.0), F64Det(0.0)), Direction3::new(F64Det(1.0), F64Det(1.0), F64Det(0.0))).unwrap();
+        let hit = f.cast_ray(&slanted, F64Det(10.0)).unwrap();
+        assert!((hit.distance.0 - (18.0f64.sqrt() - 1.0)).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,43 @@
+use crate::scalar::RealScalar;
+use crate::{Direction3, Point3};
+
+/// Line segment from `start` to `end`; may be degenerate (a point).
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Segment3<T: RealScalar> {
+    pub start: Point3<T>,
+    pub end: Point3<T>,
+}
+
+impl<T: RealScalar> Segment3<T> {
+    pub fn new(start: Point3<T>, end: Point3<T>) -> Self {
+        Self { start, end }
+    }
+
+    /// Vector from `start` to `end`.
+    pub fn delta(&self) -> Direction3<T> {
+        self.end - self.start
+    }
+
+    pub fn length(&self) -> T {
+        self.delta().0.length()
+    }
+
+    /// Point at parameter `t`: `start` at `0`, `end` at `1`.
+    pub fn at(&self, t: T) -> Point3<T> {
+        self.start + self.delta() * t
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scalar::{DFix64, Scalar};
+
+    #[test]
+    fn parametrizes_start_to_end() {
+        let s = Segment3::new(Point3::new(DFix64::ZERO, DFix64::ONE, DFix64::ZERO), Point3::new(DFix64::from_f32(3.0), DFix64::from_f32(5.0), DFix64::ZERO));
+        assert_eq!(s.length(), DFix64::from_f32(5.0));
+        assert_eq!(s.at(DFix64::ZERO), s.start);
+        assert_eq!(s.at(DFix64::ONE), s.end);
+    }
+}
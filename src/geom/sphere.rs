@@ -0,0 +1,49 @@
+use super::GeomError;
+use crate::scalar::RealScalar;
+use crate::Point3;
+
+/// Solid ball with a non-negative radius.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Sphere<T: RealScalar> {
+    center: Point3<T>,
+    radius: T,
+}
+
+impl<T: RealScalar> Sphere<T> {
+    /// Returns [`GeomError::NegativeRadius`] if `radius < 0`.
+    pub fn new(center: Point3<T>, radius: T) -> Result<Self, GeomError> {
+        if radius < T::ZERO {
+            return Err(GeomError::NegativeRadius);
+        }
+        Ok(Self { center, radius })
+    }
+
+    pub fn center(&self) -> Point3<T> {
+        self.center
+    }
+
+    pub fn radius(&self) -> T {
+        self.radius
+    }
+
+    /// Returns `true` if `point` lies inside or on the sphere.
+    pub fn contains_point(&self, point: Point3<T>) -> bool {
+        (point - self.center).0.length_sq() <= self.radius * self.radius
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scalar::{DFix64, Scalar};
+
+    #[test]
+    fn rejects_negative_radius() {
+        let c = Point3::new(DFix64::ONE, DFix64::ONE, DFix64::ZERO);
+        let s = Sphere::new(c, DFix64::ONE).unwrap();
+        assert!(s.contains_point(Point3::new(DFix64::ONE, DFix64::ZERO, DFix64::ZERO)));
+        assert!(!s.contains_point(Point3::new(DFix64::ZERO, DFix64::ZERO, DFix64::ZERO)));
+        assert!(Sphere::new(c, DFix64::ZERO).is_ok());
+        assert_eq!(Sphere::new(c, -DFix64::epsilon()), Err(GeomError::NegativeRadius));
+    }
+}
@@ -0,0 +1,139 @@
+//! Support mappings: the farthest point of a convex shape in a direction.
+//!
+//! Directions need not be unit length. Ties between equally far features go
+//! to the positive side of each axis, the segment start, or the earliest
+//! vertex, so every backend picks the same point; a zero direction returns
+//! a fixed point of the shape.
+
+use super::{unit, Aabb, Capsule, GeomError, Obb, Segment3, Sphere, Triangle};
+use crate::scalar::RealScalar;
+use crate::{Direction3, Point3, Vec3};
+
+/// Convex shapes described by their support function, as consumed by
+/// [`gjk_distance`](super::gjk_distance) and
+/// [`epa_penetration`](super::epa_penetration).
+pub trait SupportMap<T: RealScalar> {
+    /// A point of the shape maximizing `dot(point, dir)`.
+    fn support(&self, dir: Direction3<T>) -> Point3<T>;
+}
+
+/// Convex hull of a non-empty point set.
+///
+/// The points are kept as given; hull faces are never built, since the
+/// support function of the hull is that of its points.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConvexHull<T: RealScalar> {
+    points: Vec<Point3<T>>,
+}
+
+impl<T: RealScalar> ConvexHull<T> {
+    /// Returns [`GeomError::EmptyPointSet`] if `points` is empty.
+    pub fn new(points: Vec<Point3<T>>) -> Result<Self, GeomError> {
+        if points.is_empty() {
+            return Err(GeomError::EmptyPointSet);
+        }
+        Ok(Self { points })
+    }
+
+    pub fn points(&self) -> &[Point3<T>] {
+        &self.points
+    }
+}
+
+/// Earliest of `points` maximizing `dot(point, dir)`.
+fn furthest<T: RealScalar>(points: &[Point3<T>], dir: Vec3<T>) -> Point3<T> {
+    let mut best = points[0];
+    let mut best_dot = best.0.dot(&dir);
+    for &p in &points[1..] {
+        let d = p.0.dot(&dir);
+        if d > best_dot {
+            (best, best_dot) = (p, d);
+        }
+    }
+    best
+}
+
+/// Pushes `core` out by `radius` along `dir`.
+fn inflate<T: RealScalar>(core: Point3<T>, radius: T, dir: Direction3<T>) -> Point3<T> {
+    match unit(dir.0) {
+        Ok(n) => core + n * radius,
+        Err(_) => core,
+    }
+}
+
+impl<T: RealScalar> SupportMap<T> for ConvexHull<T> {
+    fn support(&self, dir: Direction3<T>) -> Point3<T> {
+        furthest(&self.points, dir.0)
+    }
+}
+
+impl<T: RealScalar> SupportMap<T> for Triangle<T> {
+    fn support(&self, dir: Direction3<T>) -> Point3<T> {
+        furthest(&[self.a, self.b, self.c], dir.0)
+    }
+}
+
+impl<T: RealScalar> SupportMap<T> for Segment3<T> {
+    fn support(&self, dir: Direction3<T>) -> Point3<T> {
+        furthest(&[self.start, self.end], dir.0)
+    }
+}
+
+impl<T: RealScalar> SupportMap<T> for Sphere<T> {
+    /// The center for a zero direction.
+    fn support(&self, dir: Direction3<T>) -> Point3<T> {
+        inflate(self.center(), self.radius(), dir)
+    }
+}
+
+impl<T: RealScalar> SupportMap<T> for Capsule<T> {
+    /// The segment start for a zero direction.
+    fn support(&self, dir: Direction3<T>) -> Point3<T> {
+        inflate(self.segment().support(dir), self.radius(), dir)
+    }
+}
+
+impl<T: RealScalar> SupportMap<T> for Aabb<T> {
+    fn support(&self, dir: Direction3<T>) -> Point3<T> {
+        let (lo, hi, d) = (self.min().0, self.max().0, dir.0);
+        let pick = |lo: T, hi: T, d: T| if d < T::ZERO { lo } else { hi };
+        Point3::new(pick(lo.x, hi.x, d.x), pick(lo.y, hi.y, d.y), pick(lo.z, hi.z, d.z))
+    }
+}
+
+impl<T: RealScalar> SupportMap<T> for Obb<T> {
+    fn support(&self, dir: Direction3<T>) -> Point3<T> {
+        let h = self.half_extents().to_array();
+        let local = [0, 1, 2].map(|k| if self.axis(k).0.dot(&dir.0) < T::ZERO { -h[k] } else { h[k] });
+        self.to_world(Vec3::from(local))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geom::test_support::{dir, fx, p};
+    use crate::scalar::DFix64;
+
+    #[test]
+    fn supports_with_tie_breaks() {
+        let b = Aabb::new(p(-1.0, -2.0, -3.0), p(1.0, 2.0, 3.0)).unwrap();
+        assert_eq!(b.support(dir(1.0, -1.0, 0.0)), p(1.0, -2.0, 3.0));
+        assert_eq!(Obb::from_aabb(&b).support(dir(1.0, -1.0, 0.0)), p(1.0, -2.0, 3.0));
+
+        let s = Sphere::new(p(1.0, 0.0, 0.0), fx(2.0)).unwrap();
+        assert_eq!(s.support(dir(0.0, 0.0, -5.0)), p(1.0, 0.0, -2.0));
+        assert_eq!(s.support(dir(0.0, 0.0, 0.0)), s.center());
+
+        let cap = Capsule::new(p(0.0, 0.0, 0.0), p(0.0, 4.0, 0.0), fx(1.0)).unwrap();
+        assert_eq!(cap.support(dir(0.0, 1.0, 0.0)), p(0.0, 5.0, 0.0));
+        assert_eq!(cap.support(dir(1.0, 0.0, 0.0)), p(1.0, 0.0, 0.0));
+
+        let tri = Triangle::new(p(0.0, 0.0, 0.0), p(2.0, 0.0, 0.0), p(2.0, 1.0, 0.0));
+        assert_eq!(tri.support(dir(1.0, 0.0, 0.0)), tri.b);
+
+        let hull = ConvexHull::new(vec![p(0.0, 0.0, 0.0), p(1.0, 1.0, 0.0), p(1.0, -1.0, 0.0)]).unwrap();
+        assert_eq!(hull.support(dir(1.0, 0.0, 0.0)), p(1.0, 1.0, 0.0));
+        assert_eq!(ConvexHull::<DFix64>::new(Vec::new()), Err(GeomError::EmptyPointSet));
+    }
+}
@@ -0,0 +1,333 @@
+//! Continuous collision detection: time of impact for moving shapes.
+//!
+//! Conventions shared by every sweep `a.time_of_impact(motion, &b)`:
+//!
+//! * `a` translates by `motion` over the step while `b` stays put; for two
+//!   moving shapes pass the relative motion `motion_a - motion_b`.
+//! * The time is the fraction of the step in `[0, 1]` at which the shapes
+//!   first touch; shapes already overlapping at the start hit at time 0.
+//! * As for [`Collide`], the normal is unit length and points from `a`
+//!   towards `b`, and the point lies on `b`'s surface at the time of impact.
+//! * Ties between features hit at the same time go to the first feature in
+//!   a fixed order; see [determinism](super#determinism).
+
+use super::contact::{Collide, Contact};
+use super::gjk::{epa_penetration, gjk_distance};
+use super::raycast::{slab, RayCast, RayHit};
+use super::support::SupportMap;
+use super::{axis_vec, length, unit, Aabb, Capsule, Plane, Ray3, Sphere, Triangle};
+use crate::scalar::RealScalar;
+use crate::{Direction3, Point3, Vec3};
+
+/// Iteration cap for [`conservative_advancement`].
+pub const ADVANCEMENT_MAX_ITERATIONS: usize = 64;
+
+/// First contact of a moving shape during a step.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Toi<T: RealScalar> {
+    /// Fraction of the step in `[0, 1]`.
+    pub time: T,
+    /// Point of contact on the static shape.
+    pub point: Point3<T>,
+    /// Unit normal from the moving shape towards the static one.
+    pub normal: Direction3<T>,
+}
+
+/// Time-of-impact queries against static shapes of type `Rhs`.
+pub trait Sweep<T: RealScalar, Rhs = Self> {
+    /// Returns the first contact while `self` moves by `motion`, or `None`
+    /// if the shapes stay apart for the whole step.
+    fn time_of_impact(&self, motion: Direction3<T>, other: &Rhs) -> Option<Toi<T>>;
+}
+
+/// Impact at time 0 from a contact manifold of overlapping shapes.
+fn overlap_toi<T: RealScalar>(contact: Contact<T>) -> Toi<T> {
+    Toi { time: T::ZERO, point: contact.points()[0].point, normal: contact.normal() }
+}
+
+/// Casts `center` along `motion` against the parts of a Minkowski sum and
+/// turns the earliest hit into an impact for a sphere of `radius`.
+fn sweep_parts<T: RealScalar, S: RayCast<T>>(center: Point3<T>, radius: T, motion: Direction3<T>, parts: &[S]) -> Option<Toi<T>> {
+    let ray = Ray3::new(center, motion).ok()?;
+    // Rescaled, as `Ray3::new` does: a short motion's squared length would
+    // underflow in fixed point.
+    let length = length(motion.0);
+    let mut best: Option<RayHit<T>> = None;
+    for hit in parts.iter().filter_map(|part| part.cast_ray(&ray, length)) {
+        if best.map_or(true, |b| hit.distance < b.distance) {
+            best = Some(hit);
+        }
+    }
+    let hit = best?;
+    let time = if length > T::ZERO { (hit.distance / length).min(T::ONE) } else { T::ZERO };
+    Some(Toi { time, point: hit.point + hit.normal * -radius, normal: -hit.normal })
+}
+
+/// Capsules of radius `radius` around the edges `(i, j)` of `corners`.
+fn edge_capsules<T: RealScalar>(corners: &[Point3<T>], edges: &[[usize; 2]], radius: T) -> Vec<Capsule<T>> {
+    edges.iter().filter_map(|&[i, j]| Capsule::new(corners[i], corners[j], radius).ok()).collect()
+}
+
+impl<T: RealScalar> Sweep<T, Plane<T>> for Sphere<T> {
+    /// Two-sided: the sphere hits whichever side it starts on, and a sphere
+    /// moving parallel to the plane never hits it.
+    fn time_of_impact(&self, motion: Direction3<T>, plane: &Plane<T>) -> Option<Toi<T>> {
+        let (c, r, n) = (self.center(), self.radius(), plane.normal());
+        let s = plane.signed_distance(c);
+        // Normal from the sphere towards the plane.
+        let normal = if s < T::ZERO { n } else { -n };
+        if s.abs() <= r {
+            return Some(Toi { time: T::ZERO, point: c + normal * s.abs(), normal });
+        }
+        let closing = normal.0.dot(&motion.0);
+        let gap = s.abs() - r;
+        if closing <= T::ZERO || gap > closing {
+            return None;
+        }
+        let time = gap / closing;
+        Some(Toi { time, point: c + motion * time + normal * r, normal })
+    }
+}
+
+impl<T: RealScalar> Sweep<T, Triangle<T>> for Sphere<T> {
+    /// Two-sided. The center is cast against the triangle pushed out by the
+    /// radius on both sides and the capsules around its edges.
+    fn time_of_impact(&self, motion: Direction3<T>, triangle: &Triangle<T>) -> Option<Toi<T>> {
+        if let Some(contact) = self.contact(triangle) {
+            return Some(overlap_toi(contact));
+        }
+        let r = self.radius();
+        let corners = [triangle.a, triangle.b, triangle.c];
+        let faces: Vec<Triangle<T>> = triangle.normal().map_or_else(Vec::new, |n| {
+            [n * r, n * -r].iter().map(|&off| Triangle::new(triangle.a + off, triangle.b + off, triangle.c + off)).collect()
+        });
+        let face_hit = sweep_parts(self.center(), r, motion, &faces);
+        let edge_hit = sweep_parts(self.center(), r, motion, &edge_capsules(&corners, &[[0, 1], [1, 2], [2, 0]], r));
+        earliest(face_hit, edge_hit)
+    }
+}
+
+impl<T: RealScalar> Sweep<T, Aabb<T>> for Sphere<T> {
+    /// The center is cast against the box grown by the radius along one axis
+    /// at a time and the capsules around its twelve edges, which together
+    /// make up the rounded box.
+    fn time_of_impact(&self, motion: Direction3<T>, aabb: &Aabb<T>) -> Option<Toi<T>> {
+        if let Some(contact) = self.contact(aabb) {
+            return Some(overlap_toi(contact));
+        }
+        let r = self.radius();
+        let (lo, hi) = (aabb.min().0, aabb.max().0);
+        let slabs: Vec<Aabb<T>> = (0..3).filter_map(|axis| {
+            let grow = axis_vec(axis, r);
+            Aabb::new(Point3(lo - grow), Point3(hi + grow)).ok()
+        }).collect();
+        let corners: Vec<Point3<T>> = (0..8).map(|k| Point3::new(
+            if k & 1 == 0 { lo.x } else { hi.x },
+            if k & 2 == 0 { lo.y } else { hi.y },
+            if k & 4 == 0 { lo.z } else { hi.z },
+        )).collect();
+        let edges = [[0, 1], [2, 3], [4, 5], [6, 7], [0, 2], [1, 3], [4, 6], [5, 7], [0, 4], [1, 5], [2, 6], [3, 7]];
+        let face_hit = sweep_parts(self.center(), r, motion, &slabs);
+        let edge_hit = sweep_parts(self.center(), r, motion, &edge_capsules(&corners, &edges, r));
+        earliest(face_hit, edge_hit)
+    }
+}
+
+/// Earlier of two impacts; ties go to `first`.
+fn earliest<T: RealScalar>(first: Option<Toi<T>>, second: Option<Toi<T>>) -> Option<Toi<T>> {
+    match (first, second) {
+        (Some(a), Some(b)) => Some(if b.time < a.time { b } else { a }),
+        (a, b) => a.or(b),
+    }
+}
+
+impl<T: RealScalar> Sweep<T> for Aabb<T> {
+    /// The moving box's minimum corner is cast against the static box grown
+    /// by the moving box's size, which involves no rounding. The point is
+    /// the center of the touching face patch.
+    fn time_of_impact(&self, motion: Direction3<T>, other: &Aabb<T>) -> Option<Toi<T>> {
+        let (lo, hi) = (other.min().0 - self.size(), other.max().0);
+        let (time, face) = slab(self.min().0, motion.0, lo, hi, T::ONE)?;
+        let Some((axis, sign)) = face else {
+            return self.contact(other).map(overlap_toi);
+        };
+        let offset = motion.0 * time;
+        let (a_lo, a_hi) = ((self.min().0 + offset).to_array(), (self.max().0 + offset).to_array());
+        let (b_lo, b_hi) = (other.min().0.to_array(), other.max().0.to_array());
+        let half = T::ONE / (T::ONE + T::ONE);
+        let mut point = [T::ZERO; 3];
+        for k in 0..3 {
+            point[k] = if k != axis {
+                (a_lo[k].max(b_lo[k]) + a_hi[k].min(b_hi[k])) * half
+            } else if sign > T::ZERO {
+                b_hi[k]
+            } else {
+                b_lo[k]
+            };
+        }
+        Some(Toi { time, point: Point3(Vec3::from(point)), normal: Direction3(axis_vec(axis, -sign)) })
+    }
+}
+
+/// `shape` moved by `offset`.
+struct Translated<'a, T: RealScalar, S> {
+    shape: &'a S,
+    offset: Vec3<T>,
+}
+
+impl<T: RealScalar, S: SupportMap<T>> SupportMap<T> for Translated<'_, T, S> {
+    fn support(&self, dir: Direction3<T>) -> Point3<T> {
+        Point3(self.shape.support(dir).0 + self.offset)
+    }
+}
+
+/// Time of impact between two translating convex shapes by conservative
+/// advancement.
+///
+/// `a` moves by `motion_a` and `b` by `motion_b` over the step; rotation is
+/// not modelled. Each iteration measures the gap with [`gjk_distance`] and
+/// advances until the gap would shrink to half of `tolerance` at the
+/// closing speed along the separating direction, which never steps past
+/// the first contact beyond the rounding of GJK itself. The sweep stops
+/// once the gap is at most `tolerance` (so `tolerance` must be positive),
+/// or at the last safe time after [`ADVANCEMENT_MAX_ITERATIONS`] or when
+/// rounding stalls progress. Overlapping shapes hit at time 0 with the
+/// [`epa_penetration`] normal.
+///
+/// Returns `None` if the shapes never come within `tolerance` during the
+/// step.
+pub fn conservative_advancement<T: RealScalar, A: SupportMap<T>, B: SupportMap<T>>(
+    a: &A,
+    motion_a: Direction3<T>,
+    b: &B,
+    motion_b: Direction3<T>,
+    tolerance: T,
+) -> Option<Toi<T>> {
+    let relative = motion_a.0 - motion_b.0;
+    let mut time = T::ZERO;
+    let mut last: Option<Toi<T>> = None;
+    let half = T::ONE / (T::ONE + T::ONE);
+    for _ in 0..ADVANCEMENT_MAX_ITERATIONS {
+        let moved_a = Translated { shape: a, offset: motion_a.0 * time };
+        let moved_b = Translated { shape: b, offset: motion_b.0 * time };
+        let Some(sep) = gjk_distance(&moved_a, &moved_b) else {
+            // Past time 0 the shapes are within rounding of touching: report
+            // the current time with the last separating normal.
+            return match last {
+                Some(prev) => Some(Toi { time, point: Point3(prev.point.0 + motion_b.0 * (time - prev.time)), normal: prev.normal }),
+                None => epa_penetration(&moved_a, &moved_b).map(overlap_toi),
+            };
+        };
+        let normal = unit((sep.point_b - sep.point_a).0).or_else(|_| unit(relative)).ok()?;
+        let toi = Toi { time, point: sep.point_b, normal };
+        if sep.distance <= tolerance {
+            return Some(toi);
+        }
+        let closing = relative.dot(&normal.0);
+        if closing <= T::ZERO || sep.distance - tolerance > closing * (T::ONE - time) {
+            return None;
+        }
+        // Aim for half the tolerance so the shapes never quite touch, which
+        // GJK would report as an overlap.
+        let next = (time + (sep.distance - tolerance * half) / closing).min(T::ONE);
+        if next <= time {
+            return Some(toi);
+        }
+        (time, last) = (next, Some(toi));
+    }
+    last
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geom::test_support::{df, dir, fx, p, pf};
+    use crate::geom::Obb;
+    use crate::scalar::{DFix64, F64Det, Scalar};
+
+    fn ball(x: f32, y: f32, z: f32, r: f32) -> Sphere<DFix64> {
+        Sphere::new(p(x, y, z), fx(r)).unwrap()
+    }
+
+    #[test]
+    fn sphere_against_plane_and_triangle() {
+        let plane = Plane::from_point_normal(p(0.0, 0.0, 0.0), dir(0.0, 1.0, 0.0)).unwrap();
+        let s = ball(0.0, 5.0, 0.0, 1.0);
+        let down = Toi { time: fx(0.5), point: p(0.0, 0.0, 0.0), normal: dir(0.0, -1.0, 0.0) };
+        assert_eq!(s.time_of_impact(dir(0.0, -8.0, 0.0), &plane), Some(down));
+        assert_eq!(s.time_of_impact(dir(0.0, 8.0, 0.0), &plane), None);
+        assert_eq!(s.time_of_impact(dir(0.0, -2.0, 0.0), &plane), None);
+        assert_eq!(ball(0.0, -0.5, 0.0, 1.0).time_of_impact(dir(3.0, 0.0, 0.0), &plane).map(|t| (t.time, t.normal)), Some((fx(0.0), dir(0.0, 1.0, 0.0))));
+
+        let tri = Triangle::new(p(-2.0, 0.0, -2.0), p(2.0, 0.0, -2.0), p(0.0, 0.0, 2.0));
+        assert_eq!(s.time_of_impact(dir(0.0, -8.0, 0.0), &tri), Some(down));
+        // Grazing the edge z = -2 from the side.
+        let edge = ball(0.0, 0.0, -6.0, 1.0).time_of_impact(dir(0.0, 0.0, 8.0), &tri).unwrap();
+        assert_eq!((edge.time, edge.point, edge.normal), (fx(0.375), p(0.0, 0.0, -2.0), dir(0.0, 0.0, 1.0)));
+        assert_eq!(ball(5.0, 0.5, 0.0, 1.0).time_of_impact(dir(0.0, 0.0, 8.0), &tri), None);
+    }
+
+    #[test]
+    fn sphere_against_aabb_faces_and_edges() {
+        let b = Aabb::new(p(-1.0, -1.0, -1.0), p(1.0, 1.0, 1.0)).unwrap();
+        let face = ball(-5.0, 0.0, 0.0, 1.0).time_of_impact(dir(8.0, 0.0, 0.0), &b).unwrap();
+        assert_eq!(face, Toi { time: fx(0.375), point: p(-1.0, 0.0, 0.0), normal: dir(1.0, 0.0, 0.0) });
+        assert_eq!(ball(-5.0, 3.0, 0.0, 1.0).time_of_impact(dir(8.0, 0.0, 0.0), &b), None);
+        assert_eq!(ball(0.0, 1.5, 0.0, 1.0).time_of_impact(dir(8.0, 0.0, 0.0), &b).map(|t| t.time), Some(fx(0.0)));
+
+        // Diagonal approach hits the z edge at (-1, -1) after 4 - 1 / sqrt(2).
+        let edge = ball(-4.0, -4.0, 0.0, 1.0).time_of_impact(dir(8.0, 8.0, 0.0), &b).unwrap();
+        assert!((edge.time.to_f64() - (3.0 - 0.5f64.sqrt()) / 8.0).abs() < 1e-3);
+        assert!((edge.point - p(-1.0, -1.0, 0.0)).0.length().to_f64() < 1e-3);
+        assert!((edge.normal.0.x.to_f64() - 0.5f64.sqrt()).abs() < 1e-3);
+    }
+
+    #[test]
+    fn short_motions_keep_their_time() {
+        // A gap of 98 raw units crossed by a motion of 196: the squared
+        // motion length is below one raw unit in Q48.16.
+        let s = Sphere::new(p(0.0, 0.0, 0.0), fx(1.0)).unwrap();
+        let motion = Direction3::new(DFix64(196), DFix64::ZERO, DFix64::ZERO);
+        let near = DFix64::ONE + DFix64(98);
+        let b = Aabb::new(Point3::new(near, fx(-1.0), fx(-1.0)), Point3::new(near + fx(2.0), fx(1.0), fx(1.0))).unwrap();
+        assert_eq!(s.time_of_impact(motion, &b).map(|t| t.time), Some(fx(0.5)));
+        let tri = Triangle::new(Point3::new(near, fx(-1.0), fx(-1.0)), Point3::new(near, fx(1.0), fx(-1.0)), Point3::new(near, fx(0.0), fx(1.0)));
+        assert_eq!(s.time_of_impact(motion, &tri).map(|t| t.time), Some(fx(0.5)));
+        let plane = Plane::from_point_normal(Point3::new(near, fx(0.0), fx(0.0)), dir(1.0, 0.0, 0.0)).unwrap();
+        assert_eq!(s.time_of_impact(motion, &plane).map(|t| t.time), Some(fx(0.5)));
+    }
+
+    #[test]
+    fn aabb_against_aabb() {
+        let a = Aabb::new(p(0.0, 0.0, 0.0), p(1.0, 1.0, 1.0)).unwrap();
+        let b = Aabb::new(p(3.0, 0.5, 0.0), p(5.0, 2.0, 1.0)).unwrap();
+        let hit = Toi { time: fx(0.5), point: p(3.0, 0.75, 0.5), normal: dir(1.0, 0.0, 0.0) };
+        assert_eq!(a.time_of_impact(dir(4.0, 0.0, 0.0), &b), Some(hit));
+        assert_eq!(a.time_of_impact(dir(1.0, 0.0, 0.0), &b), None);
+        assert_eq!(a.time_of_impact(dir(4.0, 6.0, 0.0), &b), None);
+        assert_eq!(b.time_of_impact(dir(-4.0, 0.0, 0.0), &a).map(|t| (t.time, t.normal)), Some((fx(0.5), dir(-1.0, 0.0, 0.0))));
+        assert_eq!(a.time_of_impact(dir(0.0, 0.0, 0.0), &a).map(|t| t.time), Some(fx(0.0)));
+    }
+
+    #[test]
+    fn conservative_advancement_matches_analytic_sweeps() {
+        let tol = F64Det(1e-6);
+        let a = Sphere::new(pf(0.0, 0.0, 0.0), F64Det(1.0)).unwrap();
+        let b = Sphere::new(pf(6.0, 0.0, 0.0), F64Det(1.0)).unwrap();
+        let toi = conservative_advancement(&a, df(10.0, 0.0, 0.0), &b, df(-2.0, 0.0, 0.0), tol).unwrap();
+        assert!((toi.time.0 - 1.0 / 3.0).abs() < 1e-6);
+        assert!((toi.point.0.x.0 - 13.0 / 3.0).abs() < 1e-5);
+        assert!((toi.normal.0.x.0 - 1.0).abs() < 1e-6);
+        assert_eq!(conservative_advancement(&a, df(2.0, 0.0, 0.0), &b, df(0.0, 0.0, 0.0), tol), None);
+        assert_eq!(conservative_advancement(&a, df(-9.0, 0.0, 0.0), &b, df(0.0, 0.0, 0.0), tol), None);
+        assert_eq!(conservative_advancement(&a, df(1.0, 0.0, 0.0), &a, df(0.0, 0.0, 0.0), tol).map(|t| t.time), Some(F64Det(0.0)));
+
+        let cube = Aabb::new(pf(-1.0, -1.0, -1.0), pf(1.0, 1.0, 1.0)).unwrap();
+        let ball = Sphere::new(pf(-4.0, -4.0, 0.5), F64Det(1.0)).unwrap();
+        let motion = df(8.0, 8.0, 0.0);
+        let analytic = ball.time_of_impact(motion, &cube).unwrap();
+        let advanced = conservative_advancement(&ball, motion, &Obb::from_aabb(&cube), df(0.0, 0.0, 0.0), tol).unwrap();
+        assert!(advanced.time <= analytic.time && (analytic.time.0 - advanced.time.0) < 1e-6);
+        assert!((advanced.point - analytic.point).0.length().0 < 1e-4);
+    }
+}
@@ -0,0 +1,25 @@
+//! Fixture helpers shared by the geometry tests.
+
+use crate::scalar::{DFix64, F64Det, Scalar};
+use crate::{Direction3, Point3};
+
+pub(crate) fn fx(x: f32) -> DFix64 {
+    DFix64::from_f32(x)
+}
+
+pub(crate) fn p(x: f32, y: f32, z: f32) -> Point3<DFix64> {
+    Point3::new(fx(x), fx(y), fx(z))
+}
+
+pub(crate) fn dir(x: f32, y: f32, z: f32) -> Direction3<DFix64> {
+    Direction3::new(fx(x), fx(y), fx(z))
+}
+
+/// [`p`] for the irrational cases that fixed point cannot pin exactly.
+pub(crate) fn pf(x: f64, y: f64, z: f64) -> Point3<F64Det> {
+    Point3::new(F64Det(x), F64Det(y), F64Det(z))
+}
+
+pub(crate) fn df(x: f64, y: f64, z: f64) -> Direction3<F64Det> {
+    Direction3::new(F64Det(x), F64Det(y), F64Det(z))
+}
@@ -0,0 +1,61 @@
+use super::{unit, Plane};
+use crate::scalar::RealScalar;
+use crate::{Direction3, Point3};
+
+/// Triangle with vertices `a, b, c`; may be degenerate.
+///
+/// The front face is the side from which the vertices wind
+/// counter-clockwise.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Triangle<T: RealScalar> {
+    pub a: Point3<T>,
+    pub b: Point3<T>,
+    pub c: Point3<T>,
+}
+
+impl<T: RealScalar> Triangle<T> {
+    pub fn new(a: Point3<T>, b: Point3<T>, c: Point3<T>) -> Self {
+        Self { a, b, c }
+    }
+
+    /// `(b - a) × (c - a)`: front-facing, with length twice the area.
+    pub fn scaled_normal(&self) -> Direction3<T> {
+        Direction3((self.b - self.a).0.cross(&(self.c - self.a).0))
+    }
+
+    /// Unit front-face normal, or `None` for a degenerate triangle.
+    pub fn normal(&self) -> Option<Direction3<T>> {
+        unit(self.scaled_normal().0).ok()
+    }
+
+    pub fn area(&self) -> T {
+        self.scaled_normal().0.length() / (T::ONE + T::ONE)
+    }
+
+    /// Supporting plane, or `None` for a degenerate triangle.
+    pub fn plane(&self) -> Option<Plane<T>> {
+        Plane::from_points(self.a, self.b, self.c).ok()
+    }
+
+    /// Arithmetic mean of the vertices.
+    pub fn centroid(&self) -> Point3<T> {
+        let third = T::ONE / (T::ONE + T::ONE + T::ONE);
+        Point3((self.a.0 + self.b.0 + self.c.0) * third)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geom::test_support::p;
+    use crate::scalar::{DFix64, Scalar};
+
+    #[test]
+    fn normal_follows_winding() {
+        let t = Triangle::new(p(0.0, 0.0, 0.0), p(2.0, 0.0, 0.0), p(0.0, 2.0, 0.0));
+        assert_eq!(t.normal(), Some(Direction3::new(DFix64::ZERO, DFix64::ZERO, DFix64::ONE)));
+        assert_eq!(t.area(), DFix64::from_f32(2.0));
+        assert_eq!(t.plane().unwrap().signed_distance(p(5.0, 5.0, -1.0)), -DFix64::ONE);
+        assert_eq!(Triangle::new(t.a, t.b, t.b).normal(), None);
+    }
+}
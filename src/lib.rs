@@ -2,8 +2,33 @@ pub mod scalar;
 pub mod vec3;
 pub mod types;
 pub mod angle;
+pub mod mat3;
+pub mod mat4;
+pub mod quat;
+pub mod prng;
+pub mod codec;
+pub mod geom;
+pub mod noise;
 
-pub use scalar::{Scalar, DeterministicScalar, NondetScalar, F32Det, DFix64};
+#[cfg(feature = "serde")]
+mod serde_impls;
+
+pub use scalar::{Scalar, RealScalar, TrigScalar, DeterministicScalar, NondetScalar, F32Det, F64Det, DFix32, DFix64, DFix128};
+pub use scalar::{ScalarCast, Quantize};
 pub use vec3::Vec3;
 pub use types::{Point3, Direction3};
-pub use angle::{Angle, Rad, Deg, RadAngle, DegAngle};
\ No newline at end of file
+pub use angle::{Angle, AngleUnit, Rad, Deg, RadAngle, DegAngle};
+pub use mat3::Mat3;
+pub use mat4::Mat4;
+pub use quat::Quat;
+pub use prng::{
+    random, AliasTable, DeterministicRng, Pcg32, Philox4x64, Prng, Reservoir, SplitMix64, WeightError, Xoroshiro128Plus,
+    Xoshiro256StarStar, PRNG_ALGO_VERSION,
+};
+pub use codec::{Codec, DecodeError, CODEC_VERSION};
+pub use noise::{Fractal, Noise, Worley};
+pub use geom::{conservative_advancement, epa_penetration, gjk_distance, gjk_intersects, sweep_and_prune, Aabb, Bvh, Capsule, ClosestPoint, Collide, Containment, Contact, ContactPoint, ConvexHull, DepthRange, Frustum, GeomError, Obb, Plane, Ray3, RayCast, RayHit, Segment3, Separation, SpatialHash, Sphere, SupportMap, Sweep, Toi, Triangle};
+
+/// Tolerance below which `f32` lengths are treated as zero by [`Quat`] and
+/// [`Mat4`] helpers.
+pub const EPSILON: f32 = 1e-6;
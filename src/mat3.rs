@@ -0,0 +1,97 @@
+use core::ops::Mul;
+
+use crate::scalar::Scalar;
+use crate::Vec3;
+
+/// Column-major 3×3 matrix over any scalar backend.
+///
+/// * Used for rotations and orientations (e.g. oriented box axes) in the same
+///   backend as the geometry they act on; [`Mat4`](crate::Mat4) stays the
+///   `f32` transform type.
+/// * `transform(v)` computes `M * v`, so column `i` is the image of axis `i`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Mat3<T: Scalar> {
+    cols: [Vec3<T>; 3],
+}
+
+impl<T: Scalar> Mat3<T> {
+    pub fn identity() -> Self {
+        let (o, z) = (T::ONE, T::ZERO);
+        Self::from_cols(Vec3::new(o, z, z), Vec3::new(z, o, z), Vec3::new(z, z, o))
+    }
+
+    /// Builds a matrix from its columns.
+    pub fn from_cols(x: Vec3<T>, y: Vec3<T>, z: Vec3<T>) -> Self {
+        Self { cols: [x, y, z] }
+    }
+
+    /// Returns column `i`.
+    ///
+    /// # Panics
+    /// Panics if `i >= 3`.
+    pub fn col(&self, i: usize) -> Vec3<T> {
+        self.cols[i]
+    }
+
+    /// Returns row `i`.
+    ///
+    /// # Panics
+    /// Panics if `i >= 3`.
+    pub fn row(&self, i: usize) -> Vec3<T> {
+        let [x, y, z] = self.cols.map(|c| c.to_array()[i]);
+        Vec3::new(x, y, z)
+    }
+
+    pub fn transpose(&self) -> Self {
+        Self::from_cols(self.row(0), self.row(1), self.row(2))
+    }
+
+    /// Computes `M * v`.
+    pub fn transform(&self, v: Vec3<T>) -> Vec3<T> {
+        self.cols[0] * v.x + self.cols[1] * v.y + self.cols[2] * v.z
+    }
+
+    /// Computes `Mᵀ * v`: the inverse transform when `M` is a rotation.
+    pub fn transform_transposed(&self, v: Vec3<T>) -> Vec3<T> {
+        Vec3::new(self.cols[0].dot(&v), self.cols[1].dot(&v), self.cols[2].dot(&v))
+    }
+
+    /// Matrix product `self * rhs`.
+    pub fn multiply(&self, rhs: &Self) -> Self {
+        Self { cols: rhs.cols.map(|c| self.transform(c)) }
+    }
+
+    pub fn determinant(&self) -> T {
+        self.cols[0].dot(&self.cols[1].cross(&self.cols[2]))
+    }
+}
+
+impl<T: Scalar> Mul for Mat3<T> {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.multiply(&rhs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scalar::DFix64;
+
+    fn v(x: f32, y: f32, z: f32) -> Vec3<DFix64> {
+        Vec3::new(DFix64::from_f32(x), DFix64::from_f32(y), DFix64::from_f32(z))
+    }
+
+    #[test]
+    fn column_major_products() {
+        // 90° about +Z followed by a scale of 2 on X.
+        let rot = Mat3::from_cols(v(0.0, 1.0, 0.0), v(-1.0, 0.0, 0.0), v(0.0, 0.0, 1.0));
+        let scale = Mat3::from_cols(v(2.0, 0.0, 0.0), v(0.0, 1.0, 0.0), v(0.0, 0.0, 1.0));
+        assert_eq!(rot.transform(v(1.0, 2.0, 3.0)), v(-2.0, 1.0, 3.0));
+        assert_eq!((scale * rot).transform(v(1.0, 2.0, 3.0)), v(-4.0, 1.0, 3.0));
+        assert_eq!(rot.transform_transposed(rot.transform(v(1.0, 2.0, 3.0))), v(1.0, 2.0, 3.0));
+        assert_eq!(rot * rot.transpose(), Mat3::identity());
+        assert_eq!(rot.row(0), v(0.0, -1.0, 0.0));
+        assert_eq!((scale * rot).determinant(), DFix64::from_f32(2.0));
+    }
+}
@@ -6,6 +6,7 @@ use crate::{Quat, Vec3};
 /// * Represents affine transforms; perspective terms are preserved but helper
 ///   methods treat them homogeneously (`w = 1` for points).
 #[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(transparent))]
 pub struct Mat4 {
     data: [f32; 16],
 }
@@ -120,8 +121,8 @@ impl Mat4 {
     /// The `axis` argument does not need to be pre‑normalised; it is
     /// normalised internally. If a zero‑length axis is supplied, the identity
     /// matrix is returned (behaviour delegated to
-    /// [`Quat::from_axis_angle`](crate::math::Quat::from_axis_angle)).
-    pub fn rotation_axis_angle(axis: Vec3, angle: f32) -> Self {
+    /// [`Quat::from_axis_angle`](crate::Quat::from_axis_angle)).
+    pub fn rotation_axis_angle(axis: Vec3<f32>, angle: f32) -> Self {
         Self::from_quat(&Quat::from_axis_angle(axis, angle))
     }
 
@@ -172,10 +173,10 @@ impl Mat4 {
     ///
     /// Translation components are applied and the resulting vector is returned
     /// with `w` implicitly equal to `1`.
-    pub fn transform_point(&self, point: &Vec3) -> Vec3 {
-        let x = point.component(0);
-        let y = point.component(1);
-        let z = point.component(2);
+    pub fn transform_point(&self, point: &Vec3<f32>) -> Vec3<f32> {
+        let x = point.x;
+        let y = point.y;
+        let z = point.z;
         let w = 1.0;
 
         let nx = self.at(0, 0) * x + self.at(0, 1) * y + self.at(0, 2) * z + self.at(0, 3) * w;
@@ -188,10 +189,10 @@ impl Mat4 {
     /// Transforms a direction vector (ignores translation, `w = 0`).
     ///
     /// Only the rotational and scaling parts of the matrix affect the result.
-    pub fn transform_direction(&self, direction: &Vec3) -> Vec3 {
-        let x = direction.component(0);
-        let y = direction.component(1);
-        let z = direction.component(2);
+    pub fn transform_direction(&self, direction: &Vec3<f32>) -> Vec3<f32> {
+        let x = direction.x;
+        let y = direction.y;
+        let z = direction.z;
 
         let nx = self.at(0, 0) * x + self.at(0, 1) * y + self.at(0, 2) * z;
         let ny = self.at(1, 0) * x + self.at(1, 1) * y + self.at(1, 2) * z;
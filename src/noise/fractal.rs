@@ -0,0 +1,105 @@
+//! Octave combinators.
+
+use super::Noise;
+use crate::scalar::DeterministicScalar;
+
+/// Sums octaves of a base noise at rising frequency and falling amplitude.
+///
+/// Octave `i` samples with [`Noise::octave`]`(i)` at frequency
+/// `lacunarity^i` and weight `gain^i`; the sum is divided by the total weight,
+/// so results keep the base noise's range. The base noise is supplied as a
+/// closure of `(octave source, frequency)`, which keeps the combinators
+/// independent of dimension and algorithm:
+///
+/// ```
+/// use echo_math::{DFix64, RealScalar, Scalar};
+/// use echo_math::noise::{Fractal, Noise};
+///
+/// let noise = Noise::new(7);
+/// let (x, y) = (DFix64::from_f32(1.25), DFix64::from_f32(-3.5));
+/// let height = Fractal::new(5).fbm(&noise, |n, f| n.simplex2(x * f, y * f));
+/// assert!(height.abs() <= DFix64::ONE);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Fractal<T> {
+    pub octaves: u32,
+    pub lacunarity: T,
+    pub gain: T,
+}
+
+impl<T: DeterministicScalar> Fractal<T> {
+    /// `octaves` layers with lacunarity 2 and gain 1/2.
+    pub fn new(octaves: u32) -> Self {
+        let two = T::ONE + T::ONE;
+        Self { octaves, lacunarity: two, gain: T::ONE / two }
+    }
+
+    /// Fractional Brownian motion: the weighted mean of the octaves, in the
+    /// base noise's range.
+    pub fn fbm(&self, noise: &Noise, sample: impl Fn(&Noise, T) -> T) -> T {
+        self.accumulate(noise, sample, |v| v)
+    }
+
+    /// Ridged multifractal: each octave is folded to `(1 - |v|)^2`, giving
+    /// sharp crests along the base noise's zero set. Result in `[0, 1]`.
+    pub fn ridged(&self, noise: &Noise, sample: impl Fn(&Noise, T) -> T) -> T {
+        self.accumulate(noise, sample, |v| {
+            let r = T::ONE - v.abs();
+            r * r
+        })
+    }
+
+    /// Turbulence: the weighted mean of `|v|`, giving billowy creases. Result
+    /// in `[0, 1]`.
+    pub fn turbulence(&self, noise: &Noise, sample: impl Fn(&Noise, T) -> T) -> T {
+        self.accumulate(noise, sample, |v| v.abs())
+    }
+
+    /// Zero octaves yield zero.
+    fn accumulate(&self, noise: &Noise, sample: impl Fn(&Noise, T) -> T, shape: impl Fn(T) -> T) -> T {
+        let (mut sum, mut weight) = (T::ZERO, T::ZERO);
+        let (mut frequency, mut amplitude) = (T::ONE, T::ONE);
+        for i in 0..self.octaves {
+            sum = sum + amplitude * shape(sample(&noise.octave(i), frequency));
+            weight = weight + amplitude;
+            frequency = frequency * self.lacunarity;
+            amplitude = amplitude * self.gain;
+        }
+        if self.octaves == 0 {
+            T::ZERO
+        } else {
+            sum / weight
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scalar::{DFix64, RealScalar, Scalar};
+
+    #[test]
+    fn single_octave_fbm_is_the_base_noise() {
+        let noise = Noise::new(3);
+        let (x, y) = (DFix64::from_f32(0.3), DFix64::from_f32(7.9));
+        let base = noise.octave(0).perlin2(x, y);
+        assert_eq!(Fractal::new(1).fbm(&noise, |n, f| n.perlin2(x * f, y * f)), base);
+        assert_eq!(Fractal::new(1).turbulence(&noise, |n, f| n.perlin2(x * f, y * f)), base.abs());
+        assert_eq!(Fractal::<DFix64>::new(0).fbm(&noise, |_, _| DFix64::ONE), DFix64::ZERO);
+    }
+
+    #[test]
+    fn combinators_stay_in_range() {
+        let noise = Noise::new(11);
+        let fractal = Fractal::new(6);
+        for i in 0..200 {
+            let x = DFix64::from_f32(i as f32 * 0.37 - 30.0);
+            let sample = |n: &Noise, f: DFix64| n.simplex3(x * f, DFix64::ONE, -x * f);
+            assert!(fractal.fbm(&noise, sample).abs() <= DFix64::ONE);
+            let r = fractal.ridged(&noise, sample);
+            assert!(DFix64::ZERO <= r && r <= DFix64::ONE);
+            let t = fractal.turbulence(&noise, sample);
+            assert!(DFix64::ZERO <= t && t <= DFix64::ONE);
+        }
+    }
+}
@@ -0,0 +1,145 @@
+//! Deterministic coherent noise for procedural content.
+//!
+//! * Perlin (`perlin`), simplex-lattice (`simplex`) and Worley/cellular
+//!   (`worley`) noise in 2D, 3D and 4D, generic over a [`DeterministicScalar`]:
+//!   every step runs in that backend's arithmetic, so a server and its clients
+//!   evaluating the same seed and coordinates get bit-identical results.
+//! * Lattice points are hashed from the seed with `SplitMix64` rather than a
+//!   256-entry permutation table, so patterns never tile.
+//! * [`Fractal`] layers octaves of any of them into fBm, ridged or turbulence
+//!   noise.
+//!
+//! Outputs for a given seed are pinned by golden tests; changing any of the
+//! algorithms here changes every generated world.
+
+mod fractal;
+mod perlin;
+mod simplex;
+mod worley;
+
+pub use fractal::Fractal;
+pub use worley::Worley;
+
+use crate::prng::{splitmix64, DeterministicRng, Prng};
+use crate::scalar::DeterministicScalar;
+
+/// Seeded noise source.
+///
+/// Holds only a 64-bit seed; the sampling methods are generic over the scalar
+/// backend, so one `Noise` serves every precision.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Noise {
+    seed: u64,
+}
+
+impl Noise {
+    /// Creates a noise source from an explicit seed.
+    pub fn new(seed: u64) -> Self {
+        Self { seed }
+    }
+
+    /// Draws the seed from `prng` with one `next_u64` call.
+    pub fn from_prng<R: DeterministicRng>(prng: &mut Prng<R>) -> Self {
+        Self::new(prng.next_u64())
+    }
+
+    /// Returns the seed.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Derives an independent source for octave `index`, so stacked octaves
+    /// do not share lattice features.
+    pub fn octave(&self, index: u32) -> Self {
+        let mut state = self.seed ^ u64::from(index);
+        Self::new(splitmix64(&mut state))
+    }
+
+    /// Hashes an integer lattice point under this seed.
+    #[allow(clippy::cast_sign_loss)]
+    fn hash<const N: usize>(&self, cell: [i64; N]) -> u64 {
+        cell.iter().fold(self.seed, |acc, &c| {
+            let mut state = acc ^ c as u64;
+            splitmix64(&mut state)
+        })
+    }
+}
+
+/// Splits `x` into its lattice cell and the offset within it, in `[0, 1)`.
+#[allow(clippy::cast_possible_truncation)]
+fn lattice<T: DeterministicScalar>(x: T) -> (i64, T) {
+    let floor = x.floor();
+    (floor.to_f64() as i64, x - floor)
+}
+
+/// Dot product of `d` with the lattice gradient selected by `hash`.
+///
+/// 2D picks one of the four axes or four diagonals. Higher dimensions pick a
+/// vector with one zero component and `±1` elsewhere (12 in 3D, 32 in 4D).
+#[allow(clippy::cast_possible_truncation)]
+fn gradient<T: DeterministicScalar, const N: usize>(hash: u64, d: [T; N]) -> T {
+    if N == 2 {
+        let (gx, gy): (i8, i8) = [(1, 1), (-1, 1), (1, -1), (-1, -1), (1, 0), (-1, 0), (0, 1), (0, -1)][(hash >> 61) as usize];
+        return signed(gx, d[0]) + signed(gy, d[1]);
+    }
+    let zero = ((hash >> 32) % N as u64) as usize;
+    (0..N).filter(|&a| a != zero).fold(T::ZERO, |acc, a| if (hash >> a) & 1 == 1 { acc - d[a] } else { acc + d[a] })
+}
+
+fn signed<T: DeterministicScalar>(sign: i8, x: T) -> T {
+    match sign {
+        1 => x,
+        -1 => -x,
+        _ => T::ZERO,
+    }
+}
+
+fn clamp_unit<T: DeterministicScalar>(x: T) -> T {
+    x.clamp(-T::ONE, T::ONE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scalar::{DFix64, F32Det, Scalar};
+
+    fn point() -> [DFix64; 4] {
+        [DFix64::from_f32(1.3), DFix64::from_f32(-2.7), DFix64::from_f32(0.45), DFix64::from_f32(3.9)]
+    }
+
+    #[test]
+    fn seeding_from_prng_takes_one_draw() {
+        let mut prng = Prng::from_seed(1, 2);
+        let mut replay = prng.clone();
+        assert_eq!(Noise::from_prng(&mut prng), Noise::new(replay.next_u64()));
+        assert_ne!(Noise::new(1).octave(0), Noise::new(1).octave(1));
+    }
+
+    #[test]
+    fn golden_regression() {
+        let noise = Noise::new(0x5EED);
+        let [x, y, z, w] = point();
+        assert_eq!(
+            [noise.perlin2(x, y), noise.perlin3(x, y, z), noise.perlin4(x, y, z, w)],
+            [DFix64(9_626), DFix64(5_318), DFix64(20_037)]
+        );
+        assert_eq!(
+            [noise.simplex2(x, y), noise.simplex3(x, y, z), noise.simplex4(x, y, z, w)],
+            [DFix64(-27_510), DFix64(-41_116), DFix64(-248)]
+        );
+        assert_eq!(noise.simplex2(F32Det(1.3), F32Det(-2.7)).0.to_bits(), 3_201_718_199);
+
+        let cells = [noise.worley2(x, y), noise.worley3(x, y, z), noise.worley4(x, y, z, w)];
+        assert_eq!(cells.map(|c| (c.f1, c.f2)), [
+            (DFix64(33_644), DFix64(53_810)),
+            (DFix64(53_424), DFix64(53_466)),
+            (DFix64(47_876), DFix64(53_376)),
+        ]);
+        assert_eq!(cells.map(|c| c.cell), [14_033_991_361_492_753_660, 14_513_009_475_250_149_572, 4_460_788_293_178_506_041]);
+
+        let fractal = Fractal::new(4);
+        assert_eq!(fractal.fbm(&noise, |n, f| n.perlin2(x * f, y * f)), DFix64(-4_383));
+        assert_eq!(fractal.ridged(&noise, |n, f| n.simplex3(x * f, y * f, z * f)), DFix64(29_408));
+        assert_eq!(fractal.turbulence(&noise, |n, f| n.perlin3(x * f, y * f, z * f)), DFix64(12_696));
+    }
+}
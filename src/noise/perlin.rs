@@ -0,0 +1,90 @@
+//! Improved Perlin gradient noise.
+
+use super::{clamp_unit, gradient, lattice, Noise};
+use crate::scalar::DeterministicScalar;
+
+/// Perlin's quintic fade `6t^5 - 15t^4 + 10t^3`.
+fn fade<T: DeterministicScalar>(t: T) -> T {
+    let six = T::from_f32(6.0);
+    let fifteen = T::from_f32(15.0);
+    let ten = T::from_f32(10.0);
+    t * t * t * (t * (t * six - fifteen) + ten)
+}
+
+impl Noise {
+    /// Perlin noise at `(x, y)`, clamped to `[-1, 1]`; zero at every integer
+    /// lattice point.
+    pub fn perlin2<T: DeterministicScalar>(&self, x: T, y: T) -> T {
+        self.perlin([x, y])
+    }
+
+    /// Perlin noise at `(x, y, z)`, clamped to `[-1, 1]`.
+    pub fn perlin3<T: DeterministicScalar>(&self, x: T, y: T, z: T) -> T {
+        self.perlin([x, y, z])
+    }
+
+    /// Perlin noise at `(x, y, z, w)`, clamped to `[-1, 1]`. Looping the
+    /// fourth axis animates a 3D field.
+    pub fn perlin4<T: DeterministicScalar>(&self, x: T, y: T, z: T, w: T) -> T {
+        self.perlin([x, y, z, w])
+    }
+
+    /// Blends the gradient ramps of the `2^N` cell corners with the fade
+    /// curve, one axis at a time.
+    fn perlin<T: DeterministicScalar, const N: usize>(&self, p: [T; N]) -> T {
+        let mut cell = [0i64; N];
+        let mut frac = [T::ZERO; N];
+        for axis in 0..N {
+            (cell[axis], frac[axis]) = lattice(p[axis]);
+        }
+
+        let mut ramps = [T::ZERO; 16];
+        for (corner, ramp) in ramps.iter_mut().enumerate().take(1 << N) {
+            let mut c = cell;
+            let mut d = frac;
+            for axis in 0..N {
+                if (corner >> axis) & 1 == 1 {
+                    c[axis] = c[axis].wrapping_add(1);
+                    d[axis] = d[axis] - T::ONE;
+                }
+            }
+            *ramp = gradient(self.hash(c), d);
+        }
+
+        let mut len = 1 << N;
+        for &f in &frac {
+            let t = fade(f);
+            len /= 2;
+            for i in 0..len {
+                ramps[i] = ramps[2 * i] + t * (ramps[2 * i + 1] - ramps[2 * i]);
+            }
+        }
+        clamp_unit(ramps[0])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scalar::{DFix64, F64Det, Scalar};
+
+    #[test]
+    fn vanishes_on_lattice_and_stays_in_range() {
+        let noise = Noise::new(9);
+        assert_eq!(noise.perlin3(DFix64::from_f32(3.0), DFix64::from_f32(-2.0), DFix64::ZERO), DFix64::ZERO);
+        for i in 0..500 {
+            let x = F64Det(f64::from(i) * 0.173 - 40.0);
+            let v = noise.perlin4(x, x * F64Det(0.7), F64Det(1.3) - x, x * F64Det(0.01));
+            assert!(v.0.abs() <= 1.0);
+        }
+    }
+
+    #[test]
+    fn is_continuous_across_cell_edges() {
+        let noise = Noise::new(4);
+        let (y, step) = (F64Det(0.37), F64Det(1e-9));
+        let edge = F64Det(5.0);
+        let jump = noise.perlin2(edge - step, y).0 - noise.perlin2(edge, y).0;
+        assert!(jump.abs() < 1e-7);
+    }
+}
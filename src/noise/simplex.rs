@@ -0,0 +1,123 @@
+//! Simplex-lattice gradient noise.
+//!
+//! Follows Gustavson's formulation: skew onto the simplex lattice, rank the
+//! offset components to find the enclosing simplex, then sum a radial
+//! `(r^2 - |d|^2)^4` kernel around each of its `N + 1` corners. The kernel
+//! radius is `r^2 = 1/2` in every dimension, so the field stays continuous
+//! (the classic `0.6` in 3D/4D leaves seams).
+
+use super::{clamp_unit, gradient, lattice, Noise};
+use crate::scalar::DeterministicScalar;
+
+/// `(skew, unskew)` factors `(sqrt(N + 1) - 1) / N` and
+/// `(1 - 1 / sqrt(N + 1)) / N`, followed by the output scale that maps the
+/// observed peak to roughly `±1`.
+fn constants(n: usize) -> (f64, f64, f64) {
+    match n {
+        2 => (0.366_025_403_784_438_6, 0.211_324_865_405_187_08, 70.0),
+        3 => (1.0 / 3.0, 1.0 / 6.0, 76.0),
+        _ => (0.309_016_994_374_947_45, 0.138_196_601_125_010_53, 62.0),
+    }
+}
+
+impl Noise {
+    /// Simplex noise at `(x, y)`, clamped to `[-1, 1]`.
+    ///
+    /// Cheaper than [`Noise::perlin2`] in higher dimensions (`N + 1` corners
+    /// instead of `2^N`) and without its axis-aligned artefacts.
+    pub fn simplex2<T: DeterministicScalar>(&self, x: T, y: T) -> T {
+        self.simplex([x, y])
+    }
+
+    /// Simplex noise at `(x, y, z)`, clamped to `[-1, 1]`.
+    pub fn simplex3<T: DeterministicScalar>(&self, x: T, y: T, z: T) -> T {
+        self.simplex([x, y, z])
+    }
+
+    /// Simplex noise at `(x, y, z, w)`, clamped to `[-1, 1]`.
+    pub fn simplex4<T: DeterministicScalar>(&self, x: T, y: T, z: T, w: T) -> T {
+        self.simplex([x, y, z, w])
+    }
+
+    fn simplex<T: DeterministicScalar, const N: usize>(&self, p: [T; N]) -> T {
+        let (skew, unskew, scale) = constants(N);
+        let (skew, unskew) = (T::from_f64(skew), T::from_f64(unskew));
+        let radius_sq = T::ONE / (T::ONE + T::ONE);
+
+        let s = p.iter().fold(T::ZERO, |acc, &x| acc + x) * skew;
+        let mut cell = [0i64; N];
+        let mut origin = [T::ZERO; N];
+        let mut base_sum = T::ZERO;
+        for axis in 0..N {
+            let (c, frac) = lattice(p[axis] + s);
+            cell[axis] = c;
+            let base = p[axis] + s - frac;
+            base_sum = base_sum + base;
+            origin[axis] = p[axis] - base;
+        }
+        let t = base_sum * unskew;
+        for d in &mut origin {
+            *d = *d + t;
+        }
+
+        // Rank of each component among the offsets; ties go to the later
+        // axis so every point has exactly one enclosing simplex.
+        let mut rank = [0usize; N];
+        for a in 0..N {
+            for b in a + 1..N {
+                if origin[a] > origin[b] {
+                    rank[a] += 1;
+                } else {
+                    rank[b] += 1;
+                }
+            }
+        }
+
+        let mut total = T::ZERO;
+        let mut corner_shift = T::ZERO;
+        for k in 0..=N {
+            let mut c = cell;
+            let mut d = origin;
+            for axis in 0..N {
+                if rank[axis] + k >= N {
+                    c[axis] = c[axis].wrapping_add(1);
+                    d[axis] = d[axis] - T::ONE;
+                }
+                d[axis] = d[axis] + corner_shift;
+            }
+            let falloff = radius_sq - d.iter().fold(T::ZERO, |acc, &x| acc + x * x);
+            if falloff > T::ZERO {
+                let f2 = falloff * falloff;
+                total = total + f2 * f2 * gradient(self.hash(c), d);
+            }
+            corner_shift = corner_shift + unskew;
+        }
+        clamp_unit(total * T::from_f64(scale))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scalar::{DFix64, F64Det, Scalar};
+
+    #[test]
+    fn is_continuous_and_in_range() {
+        let noise = Noise::new(21);
+        for i in 0..400 {
+            let x = F64Det(f64::from(i) * 0.0917 - 17.0);
+            let (y, z, w) = (x * F64Det(0.61) + F64Det(3.0), F64Det(0.25) - x, x * F64Det(1.7));
+            let step = F64Det(1e-9);
+            for (a, b) in [
+                (noise.simplex2(x, y), noise.simplex2(x + step, y)),
+                (noise.simplex3(x, y, z), noise.simplex3(x + step, y, z)),
+                (noise.simplex4(x, y, z, w), noise.simplex4(x + step, y, z, w)),
+            ] {
+                assert!(a.0.abs() <= 1.0);
+                assert!((a.0 - b.0).abs() < 1e-6);
+            }
+        }
+        let v = noise.simplex3(DFix64::from_f32(1.5), DFix64::from_f32(2.25), DFix64::from_f32(-0.5));
+        assert!(v.0.abs() <= 1 << 16);
+    }
+}
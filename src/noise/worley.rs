@@ -0,0 +1,106 @@
+//! Worley (cellular) noise.
+
+use super::{lattice, Noise};
+use crate::prng::splitmix64;
+use crate::scalar::DeterministicScalar;
+
+/// Result of a Worley query: Euclidean distances to the nearest and
+/// second-nearest feature points, plus an identifier of the nearest one's
+/// cell.
+///
+/// `f1` alone gives the classic cell pattern; `f2 - f1` traces cell borders;
+/// `cell` is stable per Voronoi region and can key per-region variation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Worley<T> {
+    pub f1: T,
+    pub f2: T,
+    pub cell: u64,
+}
+
+impl Noise {
+    /// Worley noise at `(x, y)`.
+    pub fn worley2<T: DeterministicScalar>(&self, x: T, y: T) -> Worley<T> {
+        self.worley([x, y])
+    }
+
+    /// Worley noise at `(x, y, z)`.
+    pub fn worley3<T: DeterministicScalar>(&self, x: T, y: T, z: T) -> Worley<T> {
+        self.worley([x, y, z])
+    }
+
+    /// Worley noise at `(x, y, z, w)`.
+    pub fn worley4<T: DeterministicScalar>(&self, x: T, y: T, z: T, w: T) -> Worley<T> {
+        self.worley([x, y, z, w])
+    }
+
+    /// One jittered feature point per unit cell, searched over the `3^N`
+    /// cells around `p`. Jitter uses [`Scalar::from_unit_bits`], so feature
+    /// positions are exact in every backend.
+    ///
+    /// [`Scalar::from_unit_bits`]: crate::scalar::Scalar::from_unit_bits
+    #[allow(clippy::cast_precision_loss)]
+    fn worley<T: DeterministicScalar, const N: usize>(&self, p: [T; N]) -> Worley<T> {
+        let mut cell = [0i64; N];
+        let mut frac = [T::ZERO; N];
+        for axis in 0..N {
+            (cell[axis], frac[axis]) = lattice(p[axis]);
+        }
+
+        // Every candidate lies within 2 units per axis.
+        let far = T::from_f32((4 * N + 1) as f32);
+        let (mut f1, mut f2, mut nearest) = (far, far, 0);
+        for neighbour in 0..3usize.pow(N as u32) {
+            let mut c = cell;
+            let mut dist_sq = T::ZERO;
+            let mut digits = neighbour;
+            let mut offset = [T::ZERO; N];
+            for axis in 0..N {
+                let step = (digits % 3) as i64 - 1;
+                digits /= 3;
+                c[axis] = c[axis].wrapping_add(step);
+                offset[axis] = T::from_f32(step as f32);
+            }
+            let id = self.hash(c);
+            let mut jitter = id;
+            for axis in 0..N {
+                let d = offset[axis] + T::from_unit_bits(splitmix64(&mut jitter)) - frac[axis];
+                dist_sq = dist_sq + d * d;
+            }
+            if dist_sq < f1 {
+                (f2, f1, nearest) = (f1, dist_sq, id);
+            } else if dist_sq < f2 {
+                f2 = dist_sq;
+            }
+        }
+        Worley { f1: f1.sqrt(), f2: f2.sqrt(), cell: nearest }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scalar::{DFix64, F64Det, Scalar};
+
+    #[test]
+    fn distances_are_ordered_and_bounded() {
+        let noise = Noise::new(5);
+        for i in 0..300 {
+            let x = F64Det(f64::from(i) * 0.131 - 20.0);
+            for w in [noise.worley2(x, x * F64Det(0.3)), noise.worley3(x, F64Det(1.0), -x), noise.worley4(x, x, F64Det(0.5), x)] {
+                assert!(F64Det(0.0) <= w.f1 && w.f1 <= w.f2);
+                assert!(w.f1.0 < 2.0);
+            }
+        }
+    }
+
+    #[test]
+    fn feature_points_have_zero_distance() {
+        let noise = Noise::new(5);
+        let id = noise.hash([3, -2]);
+        let mut jitter = id;
+        let x = DFix64::from_f32(3.0) + DFix64::from_unit_bits(splitmix64(&mut jitter));
+        let y = DFix64::from_f32(-2.0) + DFix64::from_unit_bits(splitmix64(&mut jitter));
+        let w = noise.worley2(x, y);
+        assert_eq!((w.f1, w.cell), (DFix64::ZERO, id));
+    }
+}
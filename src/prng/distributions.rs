@@ -0,0 +1,504 @@
+//! Non-uniform distributions: normal, exponential, Poisson and binomial.
+//!
+//! Samplers work in `f64` using only IEEE-754 basic operations (`+ - * /`,
+//! `sqrt`, `floor`), which are correctly rounded on every platform, plus the
+//! crate's own `ln`/`exp` kernels below in place of libm. Results are
+//! converted into the caller's [`DeterministicScalar`] with `from_f64`, so a
+//! given seed yields the same bits everywhere.
+//!
+//! Draw counts are documented per method. Rejection samplers consume a
+//! variable number of `next_u64` calls, but the count is itself a pure
+//! function of the state, so replays stay aligned.
+
+use super::ziggurat_tables::{EXP_F, EXP_R, EXP_X, NORM_F, NORM_R, NORM_X};
+use super::{DeterministicRng, Prng};
+use crate::scalar::DeterministicScalar;
+
+// ln(2) split so `k * LN2_HI` is exact for |k| < 2^11 (fdlibm).
+const LN2_HI: f64 = 6.931_471_803_691_238e-1;
+const LN2_LO: f64 = 1.908_214_929_270_587_7e-10;
+
+/// Natural logarithm from basic operations only; within 2 ulp of libm.
+/// `ln(0)` is `-inf`.
+fn ln(x: f64) -> f64 {
+    debug_assert!(x >= 0.0 && x.is_finite());
+    if x == 0.0 {
+        return f64::NEG_INFINITY;
+    }
+    let (x, mut e) = if x < f64::MIN_POSITIVE { (x * 18_014_398_509_481_984.0, -54) } else { (x, 0) };
+    let bits = x.to_bits();
+    e += ((bits >> 52) as i32) - 1023;
+    let mut m = f64::from_bits((bits & 0x000f_ffff_ffff_ffff) | 0x3ff0_0000_0000_0000);
+    if m > core::f64::consts::SQRT_2 {
+        m *= 0.5;
+        e += 1;
+    }
+    // ln(m) = 2 atanh(s); |s| <= 0.172 so eleven odd terms reach 1e-17.
+    let s = (m - 1.0) / (m + 1.0);
+    let s2 = s * s;
+    let mut p = 0.0;
+    for k in (0..=10).rev() {
+        p = p * s2 + 1.0 / f64::from(2 * k + 1);
+    }
+    let e = f64::from(e);
+    e * LN2_HI + (2.0 * s * p + e * LN2_LO)
+}
+
+/// `e^x` from basic operations only; within 2 ulp of libm. Flushes to zero
+/// below `-708` instead of producing subnormals.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn exp(x: f64) -> f64 {
+    if x < -708.0 {
+        return 0.0;
+    }
+    if x > 709.0 {
+        return f64::INFINITY;
+    }
+    let k = (x * core::f64::consts::LOG2_E + 0.5).floor();
+    let r = x - k * LN2_HI - k * LN2_LO;
+    // |r| <= ln(2) / 2; the degree-13 Taylor remainder is below 1e-17.
+    let mut p = 1.0;
+    for n in (1..=13).rev() {
+        p = 1.0 + r * p / f64::from(n);
+    }
+    p * f64::from_bits(((k as i64 + 1023) as u64) << 52)
+}
+
+/// `ln Γ(x)` for `x >= 1` (Stirling series, shifted up to `x >= 7`).
+fn ln_gamma(x: f64) -> f64 {
+    const A: [f64; 10] = [
+        8.333_333_333_333_333e-2,
+        -2.777_777_777_777_778e-3,
+        7.936_507_936_507_937e-4,
+        -5.952_380_952_380_952e-4,
+        8.417_508_417_508_418e-4,
+        -1.917_526_917_526_918e-3,
+        6.410_256_410_256_41e-3,
+        -2.955_065_359_477_124e-2,
+        1.796_443_723_688_307e-1,
+        -1.392_432_216_905_9,
+    ];
+    if x == 1.0 || x == 2.0 {
+        return 0.0;
+    }
+    let shift = if x < 7.0 { (7.0 - x).floor() } else { 0.0 };
+    let mut x0 = x + shift;
+    let x2 = 1.0 / (x0 * x0);
+    let mut series = A[9];
+    for &a in A[..9].iter().rev() {
+        series = series * x2 + a;
+    }
+    let mut gl = series / x0 + 0.5 * ln(core::f64::consts::TAU) + (x0 - 0.5) * ln(x0) - x0;
+    for _ in 0..shift as u32 {
+        gl -= ln(x0 - 1.0);
+        x0 -= 1.0;
+    }
+    gl
+}
+
+impl<R: DeterministicRng> Prng<R> {
+    /// Uniform in `(0, 1]`, one `next_u64`.
+    fn next_open01(&mut self) -> f64 {
+        1.0 - self.next_f64()
+    }
+
+    /// Ziggurat step shared by the normal (`symmetric`) and exponential
+    /// samplers: the top 8 bits pick the layer and the 52 bits below them
+    /// the position, leaving the weak low 4 bits unused.
+    fn ziggurat(
+        &mut self,
+        x_tab: &[f64; 257],
+        f_tab: &[f64; 257],
+        symmetric: bool,
+        pdf: fn(f64) -> f64,
+        tail: fn(&mut Self, f64) -> f64,
+    ) -> f64 {
+        loop {
+            let bits = self.next_u64();
+            let i = (bits >> 56) as usize;
+            let u = f64::from_bits(((bits >> 4) & 0x000f_ffff_ffff_ffff) | 0x3ff0_0000_0000_0000) - 1.0;
+            let u = if symmetric { 2.0 * u - 1.0 } else { u };
+            let x = u * x_tab[i];
+            if x.abs() < x_tab[i + 1] {
+                return x;
+            }
+            if i == 0 {
+                return tail(self, u);
+            }
+            if f_tab[i + 1] + (f_tab[i] - f_tab[i + 1]) * self.next_f64() < pdf(x) {
+                return x;
+            }
+        }
+    }
+
+    fn standard_normal(&mut self) -> f64 {
+        fn pdf(x: f64) -> f64 {
+            exp(-x * x / 2.0)
+        }
+        fn tail<R: DeterministicRng>(prng: &mut Prng<R>, u: f64) -> f64 {
+            let (mut x, mut y) = (1.0, 0.0);
+            while -2.0 * y < x * x {
+                x = ln(prng.next_open01()) / NORM_R;
+                y = ln(prng.next_open01());
+            }
+            if u < 0.0 {
+                x - NORM_R
+            } else {
+                NORM_R - x
+            }
+        }
+        self.ziggurat(&NORM_X, &NORM_F, true, pdf, tail::<R>)
+    }
+
+    fn standard_exponential(&mut self) -> f64 {
+        fn pdf(x: f64) -> f64 {
+            exp(-x)
+        }
+        fn tail<R: DeterministicRng>(prng: &mut Prng<R>, _: f64) -> f64 {
+            EXP_R - ln(prng.next_open01())
+        }
+        self.ziggurat(&EXP_X, &EXP_F, false, pdf, tail::<R>)
+    }
+
+    /// Returns a normally distributed value with the given mean and standard
+    /// deviation.
+    ///
+    /// 256-layer ziggurat over fixed tables. Each attempt consumes one
+    /// `next_u64`, plus one more when it lands in a layer's wedge; the rare
+    /// tail case (about 1 in 3900) consumes two per tail iteration. About
+    /// 98.5% of samples cost exactly one call. The standard normal is computed in
+    /// `f64`, converted with `T::from_f64`, then scaled in `T`.
+    pub fn next_normal<T: DeterministicScalar>(&mut self, mean: T, std_dev: T) -> T {
+        mean + std_dev * T::from_f64(self.standard_normal())
+    }
+
+    /// Returns an exponentially distributed value with the given rate (mean
+    /// `1 / rate`).
+    ///
+    /// Same ziggurat scheme and draw accounting as [`Prng::next_normal`],
+    /// except the tail (about 1 in 2200) takes a single call; about 97.8% of
+    /// samples cost exactly one call.
+    ///
+    /// # Panics
+    /// Panics if `rate <= 0`.
+    pub fn next_exponential<T: DeterministicScalar>(&mut self, rate: T) -> T {
+        assert!(rate > T::ZERO, "exponential rate must be positive");
+        T::from_f64(self.standard_exponential()) / rate
+    }
+
+    /// Returns a Poisson-distributed count with mean `lambda`.
+    ///
+    /// * `lambda == 0` returns 0 without drawing.
+    /// * `lambda < 10` multiplies uniforms until the product drops below
+    ///   `e^-lambda`, consuming `k + 1` calls for a result of `k`.
+    /// * Otherwise uses Hörmann's PTRS transformed rejection: two calls per
+    ///   attempt, with roughly 90% of attempts accepted.
+    ///
+    /// # Panics
+    /// Panics if `lambda` is negative.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss, clippy::cast_precision_loss)]
+    pub fn next_poisson<T: DeterministicScalar>(&mut self, lambda: T) -> u64 {
+        assert!(lambda >= T::ZERO, "poisson mean must be non-negative");
+        let lam = lambda.to_f64();
+        if lam == 0.0 {
+            return 0;
+        }
+        if lam < 10.0 {
+            let limit = exp(-lam);
+            let mut k = 0;
+            let mut prod = self.next_f64();
+            while prod > limit {
+                k += 1;
+                prod *= self.next_f64();
+            }
+            return k;
+        }
+
+        let slam = lam.sqrt();
+        let loglam = ln(lam);
+        let b = 0.931 + 2.53 * slam;
+        let a = -0.059 + 0.02483 * b;
+        let inv_alpha = 1.1239 + 1.1328 / (b - 3.4);
+        let vr = 0.9277 - 3.6224 / (b - 2.0);
+        loop {
+            let u = self.next_f64() - 0.5;
+            let v = self.next_f64();
+            let us = 0.5 - u.abs();
+            let k = ((2.0 * a / us + b) * u + lam + 0.43).floor() as i64;
+            if us >= 0.07 && v <= vr {
+                return k as u64;
+            }
+            if k < 0 || (us < 0.013 && v > us) {
+                continue;
+            }
+            let kf = k as f64;
+            if ln(v) + ln(inv_alpha) - ln(a / (us * us) + b) <= -lam + kf * loglam - ln_gamma(kf + 1.0) {
+                return k as u64;
+            }
+        }
+    }
+
+    /// Returns the number of successes in `n` trials with success probability
+    /// `p`.
+    ///
+    /// * `n == 0`, `p == 0` or `p == 1` return without drawing.
+    /// * When `n * min(p, 1 - p) <= 30`, inverts the CDF: one call, plus one
+    ///   per restart in the astronomically rare case the walk passes
+    ///   `n·p + 10σ`.
+    /// * Otherwise uses Kachitvichyanukul & Schmeiser's BTPE: two calls per
+    ///   attempt.
+    ///
+    /// # Panics
+    /// Panics if `p` is outside `[0, 1]`.
+    pub fn next_binomial<T: DeterministicScalar>(&mut self, n: u32, p: T) -> u32 {
+        assert!(T::ZERO <= p && p <= T::ONE, "binomial probability must be in [0, 1]");
+        let p = p.to_f64();
+        if n == 0 || p == 0.0 {
+            return 0;
+        }
+        if p == 1.0 {
+            return n;
+        }
+        let n64 = f64::from(n);
+        if p <= 0.5 {
+            if p * n64 <= 30.0 {
+                self.binomial_inversion(n, p)
+            } else {
+                self.binomial_btpe(n, p)
+            }
+        } else if (1.0 - p) * n64 <= 30.0 {
+            n - self.binomial_inversion(n, 1.0 - p)
+        } else {
+            self.binomial_btpe(n, p)
+        }
+    }
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn binomial_inversion(&mut self, n: u32, p: f64) -> u32 {
+        let n64 = f64::from(n);
+        let q = 1.0 - p;
+        let qn = exp(n64 * ln(q));
+        let np = n64 * p;
+        let bound = n64.min(np + 10.0 * (np * q + 1.0).sqrt()) as u32;
+        let mut x = 0;
+        let mut px = qn;
+        let mut u = self.next_f64();
+        while u > px {
+            x += 1;
+            if x > bound {
+                x = 0;
+                px = qn;
+                u = self.next_f64();
+            } else {
+                u -= px;
+                px = (f64::from(n - x + 1) * p * px) / (f64::from(x) * q);
+            }
+        }
+        x
+    }
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss, clippy::cast_precision_loss)]
+    #[allow(clippy::many_single_char_names, clippy::similar_names)]
+    fn binomial_btpe(&mut self, n: u32, p: f64) -> u32 {
+        fn stirling(x: f64) -> f64 {
+            let x2 = x * x;
+            (13680.0 - (462.0 - (132.0 - (99.0 - 140.0 / x2) / x2) / x2) / x2) / x / 166_320.0
+        }
+
+        let n64 = f64::from(n);
+        let r = p.min(1.0 - p);
+        let q = 1.0 - r;
+        let fm = n64 * r + r;
+        let m = fm.floor() as i64;
+        let mf = m as f64;
+        let p1 = (2.195 * (n64 * r * q).sqrt() - 4.6 * q).floor() + 0.5;
+        let xm = mf + 0.5;
+        let xl = xm - p1;
+        let xr = xm + p1;
+        let c = 0.134 + 20.5 / (15.3 + mf);
+        let a = (fm - xl) / (fm - xl * r);
+        let lam_l = a * (1.0 + a / 2.0);
+        let a = (xr - fm) / (xr * q);
+        let lam_r = a * (1.0 + a / 2.0);
+        let p2 = p1 * (1.0 + 2.0 * c);
+        let p3 = p2 + c / lam_l;
+        let p4 = p3 + c / lam_r;
+        let nrq = n64 * r * q;
+
+        let y = loop {
+            let u = self.next_f64() * p4;
+            let mut v = self.next_f64();
+            let y;
+            if u <= p1 {
+                // Triangular centre: accepted without a density test.
+                break (xm - p1 * v + u).floor() as i64;
+            } else if u <= p2 {
+                let x = xl + (u - p1) / c;
+                v = v * c + 1.0 - (mf - x + 0.5).abs() / p1;
+                if v > 1.0 {
+                    continue;
+                }
+                y = x.floor() as i64;
+            } else if u <= p3 {
+                y = (xl + ln(v) / lam_l).floor() as i64;
+                if y < 0 || v == 0.0 {
+                    continue;
+                }
+                v *= (u - p2) * lam_l;
+            } else {
+                y = (xr - ln(v) / lam_r).floor() as i64;
+                if y > i64::from(n) || v == 0.0 {
+                    continue;
+                }
+                v *= (u - p3) * lam_r;
+            }
+
+            let k = (y - m).abs();
+            let kf = k as f64;
+            if k <= 20 || kf >= nrq / 2.0 - 1.0 {
+                // Explicit density ratio f(y) / f(m).
+                let s = r / q;
+                let a = s * (n64 + 1.0);
+                let mut f = 1.0;
+                if m < y {
+                    for i in m + 1..=y {
+                        f *= a / i as f64 - s;
+                    }
+                } else {
+                    for i in y + 1..=m {
+                        f /= a / i as f64 - s;
+                    }
+                }
+                if v <= f {
+                    break y;
+                }
+                continue;
+            }
+
+            // Squeeze on ln(f(y) / f(m)), then the Stirling-corrected bound.
+            let rho = (kf / nrq) * ((kf * (kf / 3.0 + 0.625) + 0.166_666_666_666_666_66) / nrq + 0.5);
+            let t = -kf * kf / (2.0 * nrq);
+            let big_a = ln(v);
+            if big_a < t - rho {
+                break y;
+            }
+            if big_a > t + rho {
+                continue;
+            }
+            let yf = y as f64;
+            let x1 = yf + 1.0;
+            let f1 = mf + 1.0;
+            let z = n64 + 1.0 - mf;
+            let w = n64 - yf + 1.0;
+            let bound = xm * ln(f1 / x1)
+                + (n64 - mf + 0.5) * ln(z / w)
+                + (yf - mf) * ln(w * r / (x1 * q))
+                + stirling(f1)
+                + stirling(z)
+                + stirling(x1)
+                + stirling(w);
+            if big_a <= bound {
+                break y;
+            }
+        };
+
+        let y = y as u32;
+        if p > 0.5 {
+            n - y
+        } else {
+            y
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scalar::{DFix64, F64Det, Scalar};
+
+    fn moments(samples: impl Iterator<Item = f64>) -> (f64, f64) {
+        let v: Vec<f64> = samples.collect();
+        let n = v.len() as f64;
+        let mean = v.iter().sum::<f64>() / n;
+        let var = v.iter().map(|x| (x - mean) * (x - mean)).sum::<f64>() / n;
+        (mean, var)
+    }
+
+    #[test]
+    fn ln_and_exp_track_libm() {
+        for i in 1..2_000 {
+            let x = f64::from(i) * 0.013;
+            assert!((ln(x) - x.ln()).abs() <= 4.0 * f64::EPSILON * x.ln().abs().max(1.0), "ln({x})");
+            let y = x - 13.0;
+            assert!((exp(y) - y.exp()).abs() <= 4.0 * f64::EPSILON * y.exp(), "exp({y})");
+        }
+        assert_eq!(ln(1.0), 0.0);
+        assert_eq!(exp(0.0), 1.0);
+        assert_eq!(ln(0.0), f64::NEG_INFINITY);
+        assert!((ln(1e-310) - 1e-310f64.ln()).abs() < 1e-12);
+        assert!((ln_gamma(11.0) - 3_628_800f64.ln()).abs() < 1e-12);
+        assert!((ln_gamma(3.0) - 2f64.ln()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn normal_and_exponential_moments() {
+        let mut prng = Prng::from_seed(1, 2);
+        let (mean, var) = moments((0..50_000).map(|_| prng.next_normal(F64Det(3.0), F64Det(2.0)).0));
+        assert!((mean - 3.0).abs() < 0.05 && (var - 4.0).abs() < 0.1, "{mean} {var}");
+
+        let (mean, var) = moments((0..50_000).map(|_| prng.next_exponential(F64Det(4.0)).0));
+        assert!((mean - 0.25).abs() < 0.005 && (var - 0.0625).abs() < 0.005, "{mean} {var}");
+
+        let x = prng.next_normal(DFix64::from_f32(1.0), DFix64::from_f32(0.5));
+        assert!(x > DFix64::from_f32(-2.0) && x < DFix64::from_f32(4.0));
+    }
+
+    #[test]
+    #[allow(clippy::cast_precision_loss)]
+    fn poisson_moments_on_both_paths() {
+        let mut prng = Prng::from_seed(3, 4);
+        for lam in [0.5, 4.0, 12.0, 250.0] {
+            let (mean, var) = moments((0..40_000).map(|_| prng.next_poisson(F64Det(lam)) as f64));
+            assert!((mean - lam).abs() < 0.03 * lam.max(1.0), "lambda {lam}: mean {mean}");
+            assert!((var - lam).abs() < 0.06 * lam.max(1.0), "lambda {lam}: var {var}");
+        }
+        assert_eq!(prng.next_poisson(DFix64::ZERO), 0);
+    }
+
+    #[test]
+    fn binomial_moments_on_both_paths() {
+        let mut prng = Prng::from_seed(5, 6);
+        for (n, p) in [(10, 0.3), (40, 0.9), (1_000, 0.25), (500, 0.8)] {
+            let (mean, var) = moments((0..40_000).map(|_| f64::from(prng.next_binomial(n, F64Det(p)))));
+            let (em, ev) = (f64::from(n) * p, f64::from(n) * p * (1.0 - p));
+            assert!((mean - em).abs() < 0.02 * em, "B({n}, {p}): mean {mean}");
+            assert!((var - ev).abs() < 0.06 * ev, "B({n}, {p}): var {var}");
+        }
+        assert_eq!(prng.next_binomial(7, DFix64::ONE), 7);
+        assert_eq!(prng.next_binomial(7, DFix64::ZERO), 0);
+    }
+
+    #[test]
+    fn degenerate_parameters_do_not_draw() {
+        let mut prng = Prng::from_seed(7, 8);
+        let before = prng.clone();
+        prng.next_poisson(F64Det(0.0));
+        prng.next_binomial(0, F64Det(0.5));
+        prng.next_binomial(9, F64Det(1.0));
+        assert_eq!(prng, before);
+    }
+
+    #[cfg(feature = "golden_prng")]
+    #[test]
+    fn distributions_golden_regression() {
+        let mut prng = Prng::from_seed_u64(0x5EED);
+        let normals: Vec<u64> = (0..3).map(|_| prng.next_normal(F64Det(0.0), F64Det(1.0)).0.to_bits()).collect();
+        assert_eq!(normals, vec![0xbff4_3e98_9cda_36ca, 0x3fc1_d422_0042_b3e5, 0x3fe3_d39c_0965_8011]);
+        assert_eq!(prng.next_exponential(DFix64::ONE), DFix64(7_742));
+        assert_eq!(prng.next_poisson(F64Det(3.5)), 4);
+        assert_eq!(prng.next_poisson(F64Det(100.0)), 100);
+        assert_eq!(prng.next_binomial(20, F64Det(0.5)), 9);
+        assert_eq!(prng.next_binomial(1_000, F64Det(0.5)), 510);
+        assert_eq!(prng.next_u64(), 0x6d3a_5dc7_5d2b_a245);
+    }
+}
@@ -0,0 +1,270 @@
+//! Geometric sampling for spawners and emitters.
+//!
+//! Every helper is generic over a [`DeterministicScalar`] and only uses that
+//! backend's arithmetic, `sqrt` and trig, so results replay bit-for-bit under
+//! lockstep. Each method documents how many [`Prng::next_u64`] calls it makes;
+//! the fixed-cost ones always consume exactly that many.
+
+use super::{DeterministicRng, Prng};
+use crate::angle::RadAngle;
+use crate::scalar::DeterministicScalar;
+use crate::{Direction3, Point3, Quat, Vec3};
+
+fn tau<T: DeterministicScalar>() -> T {
+    T::from_f64(core::f64::consts::TAU)
+}
+
+/// Two unit vectors completing `n` (assumed unit length) to an orthonormal
+/// basis. The helper axis switches on `|n.x|`, so the choice is branch-stable
+/// for a given input.
+fn basis<T: DeterministicScalar>(n: Vec3<T>) -> (Vec3<T>, Vec3<T>) {
+    let helper = if n.x.abs() > T::from_f32(0.5) {
+        Vec3::new(T::ZERO, T::ONE, T::ZERO)
+    } else {
+        Vec3::new(T::ONE, T::ZERO, T::ZERO)
+    };
+    let t = helper.cross(&n).normalize();
+    (t, n.cross(&t))
+}
+
+impl<R: DeterministicRng> Prng<R> {
+    /// Returns a direction uniformly distributed on the unit sphere.
+    ///
+    /// Uses the cylinder projection (`z` uniform in `(-1, 1]`, azimuth uniform
+    /// in `[0, 2π)`), consuming exactly two `next_u64` calls.
+    pub fn next_unit_direction<T: DeterministicScalar>(&mut self) -> Direction3<T> {
+        let z = T::ONE - self.next_scalar::<T>() * T::from_f32(2.0);
+        let phi = tau::<T>() * self.next_scalar();
+        let r = (T::ONE - z * z).max(T::ZERO).sqrt();
+        Direction3::new(r * phi.cos(), r * phi.sin(), z)
+    }
+
+    /// Returns a point uniformly distributed inside the ball of `radius`
+    /// around `center`.
+    ///
+    /// Rejection-samples the enclosing cube: three `next_u64` calls per
+    /// attempt, with roughly 52% of attempts accepted.
+    pub fn next_in_sphere<T: DeterministicScalar>(&mut self, center: Point3<T>, radius: T) -> Point3<T> {
+        let two = T::from_f32(2.0);
+        loop {
+            let v = Vec3::new(
+                self.next_scalar::<T>() * two - T::ONE,
+                self.next_scalar::<T>() * two - T::ONE,
+                self.next_scalar::<T>() * two - T::ONE,
+            );
+            if v.length_sq() < T::ONE {
+                return Point3(center.0 + v * radius);
+            }
+        }
+    }
+
+    /// Returns a point uniformly distributed in the box `[min, max)`, one
+    /// [`Prng::next_range`] per axis (three `next_u64` calls).
+    pub fn next_in_box<T: DeterministicScalar>(&mut self, min: Point3<T>, max: Point3<T>) -> Point3<T> {
+        let x = self.next_range(min.0.x, max.0.x);
+        let y = self.next_range(min.0.y, max.0.y);
+        let z = self.next_range(min.0.z, max.0.z);
+        Point3::new(x, y, z)
+    }
+
+    /// Returns a point uniformly distributed on the disc of `radius` around
+    /// `center`, lying in the plane perpendicular to `normal`.
+    ///
+    /// `normal` need not be unit length but must be non-zero. Consumes exactly
+    /// two `next_u64` calls (radius² then angle).
+    pub fn next_in_disc<T: DeterministicScalar>(
+        &mut self,
+        center: Point3<T>,
+        normal: Direction3<T>,
+        radius: T,
+    ) -> Point3<T> {
+        let r = radius * self.next_scalar::<T>().sqrt();
+        let theta = tau::<T>() * self.next_scalar();
+        let (t, b) = basis(normal.0.normalize());
+        Point3(center.0 + t * (r * theta.cos()) + b * (r * theta.sin()))
+    }
+
+    /// Returns a point uniformly distributed inside the triangle `abc`.
+    ///
+    /// Draws two barycentric weights and folds pairs with `u + v > 1` back into
+    /// the triangle; consumes exactly two `next_u64` calls.
+    pub fn next_in_triangle<T: DeterministicScalar>(
+        &mut self,
+        a: Point3<T>,
+        b: Point3<T>,
+        c: Point3<T>,
+    ) -> Point3<T> {
+        let mut u = self.next_scalar::<T>();
+        let mut v = self.next_scalar::<T>();
+        if u + v > T::ONE {
+            u = T::ONE - u;
+            v = T::ONE - v;
+        }
+        Point3(a.0 + (b - a).0 * u + (c - a).0 * v)
+    }
+
+    /// Returns a unit direction uniformly distributed over the spherical cap
+    /// within `half_angle` of `axis`.
+    ///
+    /// `axis` need not be unit length but must be non-zero; a half-angle of `π`
+    /// covers the whole sphere. Consumes exactly two `next_u64` calls.
+    pub fn next_in_cone<T: DeterministicScalar>(
+        &mut self,
+        axis: Direction3<T>,
+        half_angle: RadAngle<T>,
+    ) -> Direction3<T> {
+        let cos_theta = T::ONE - self.next_scalar::<T>() * (T::ONE - half_angle.cos());
+        let phi = tau::<T>() * self.next_scalar();
+        let sin_theta = (T::ONE - cos_theta * cos_theta).max(T::ZERO).sqrt();
+        let n = axis.0.normalize();
+        let (t, b) = basis(n);
+        Direction3(t * (sin_theta * phi.cos()) + b * (sin_theta * phi.sin()) + n * cos_theta)
+    }
+
+    /// Returns a uniformly distributed rotation (Shoemake's method).
+    ///
+    /// The quaternion is computed in `T` and only rounded to `f32` at the end,
+    /// so it is bit-identical across peers for a given `T`. Consumes exactly
+    /// three `next_u64` calls.
+    pub fn next_quat<T: DeterministicScalar>(&mut self) -> Quat {
+        let u = self.next_scalar::<T>();
+        let theta1 = tau::<T>() * self.next_scalar();
+        let theta2 = tau::<T>() * self.next_scalar();
+        let s1 = (T::ONE - u).sqrt();
+        let s2 = u.sqrt();
+        Quat::new(
+            (s1 * theta1.sin()).to_f32(),
+            (s1 * theta1.cos()).to_f32(),
+            (s2 * theta2.sin()).to_f32(),
+            (s2 * theta2.cos()).to_f32(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scalar::{DFix64, F32Det, RealScalar, Scalar};
+
+    fn fix(x: f32) -> DFix64 {
+        DFix64::from_f32(x)
+    }
+
+    fn advanced(prng: &Prng, draws: usize) -> Prng {
+        let mut p = prng.clone();
+        for _ in 0..draws {
+            p.next_u64();
+        }
+        p
+    }
+
+    #[test]
+    fn unit_directions_have_unit_length() {
+        let mut prng = Prng::from_seed(1, 2);
+        for _ in 0..500 {
+            let d = prng.next_unit_direction::<DFix64>();
+            assert!((d.0.length_sq().to_f64() - 1.0).abs() < 1e-3);
+            let f = prng.next_unit_direction::<F32Det>();
+            assert!((f.0.length_sq().to_f64() - 1.0).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn volume_samples_stay_inside() {
+        let mut prng = Prng::from_seed(3, 4);
+        let center = Point3::new(fix(1.0), fix(-2.0), fix(0.5));
+        let lo = Point3::new(fix(-1.0), fix(0.0), fix(2.0));
+        let hi = Point3::new(fix(1.0), fix(0.5), fix(3.0));
+        for _ in 0..500 {
+            let p = prng.next_in_sphere(center, fix(2.0));
+            assert!((p - center).0.length_sq() < fix(4.0));
+
+            let p = prng.next_in_box(lo, hi);
+            assert!(lo.0.x <= p.0.x && p.0.x < hi.0.x);
+            assert!(lo.0.y <= p.0.y && p.0.y < hi.0.y);
+            assert!(lo.0.z <= p.0.z && p.0.z < hi.0.z);
+        }
+    }
+
+    #[test]
+    fn planar_samples_stay_in_their_shape() {
+        let mut prng = Prng::from_seed(5, 6);
+        let origin = Point3::new(DFix64::ZERO, DFix64::ZERO, DFix64::ZERO);
+        let normal = Direction3::new(fix(0.0), fix(3.0), fix(0.0));
+        let (b, c) = (Point3::new(fix(1.0), fix(0.0), fix(0.0)), Point3::new(fix(0.0), fix(1.0), fix(0.0)));
+        for _ in 0..500 {
+            let p = prng.next_in_disc(origin, normal, fix(1.5));
+            assert!(p.0.y.abs() <= fix(0.001));
+            assert!(p.0.length_sq() <= fix(2.25 + 0.01));
+
+            let p = prng.next_in_triangle(origin, b, c);
+            assert!(p.0.x >= DFix64::ZERO && p.0.y >= DFix64::ZERO);
+            assert!(p.0.x + p.0.y <= DFix64::ONE);
+        }
+    }
+
+    #[test]
+    fn cone_directions_respect_half_angle() {
+        let mut prng = Prng::from_seed(7, 8);
+        let axis = Direction3::new(fix(1.0), fix(1.0), fix(0.0));
+        let half = RadAngle::from_radians(fix(0.3));
+        let cos_half = 0.3f64.cos();
+        for _ in 0..500 {
+            let d = prng.next_in_cone(axis, half).0;
+            let along = (d.x + d.y).to_f64() * core::f64::consts::FRAC_1_SQRT_2;
+            assert!(along >= cos_half - 1e-3, "{along}");
+            assert!((d.length_sq().to_f64() - 1.0).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn random_rotations_are_unit_quaternions() {
+        let mut prng = Prng::from_seed(9, 10);
+        for _ in 0..500 {
+            let q = prng.next_quat::<F32Det>().to_array();
+            let len_sq: f32 = q.iter().map(|c| c * c).sum();
+            assert!((len_sq - 1.0).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn fixed_cost_helpers_consume_documented_draws() {
+        let prng = Prng::from_seed(11, 12);
+        let origin = Point3::new(DFix64::ZERO, DFix64::ZERO, DFix64::ZERO);
+        let up = Direction3::new(DFix64::ZERO, DFix64::ONE, DFix64::ZERO);
+
+        let mut p = prng.clone();
+        p.next_unit_direction::<DFix64>();
+        assert_eq!(p, advanced(&prng, 2));
+
+        let mut p = prng.clone();
+        p.next_in_box(origin, Point3(up.0));
+        assert_eq!(p, advanced(&prng, 3));
+
+        let mut p = prng.clone();
+        p.next_in_disc(origin, up, DFix64::ONE);
+        assert_eq!(p, advanced(&prng, 2));
+
+        let mut p = prng.clone();
+        p.next_in_triangle(origin, origin, origin);
+        assert_eq!(p, advanced(&prng, 2));
+
+        let mut p = prng.clone();
+        p.next_in_cone(up, RadAngle::from_radians(DFix64::ONE));
+        assert_eq!(p, advanced(&prng, 2));
+
+        let mut p = prng.clone();
+        p.next_quat::<DFix64>();
+        assert_eq!(p, advanced(&prng, 3));
+    }
+
+    #[cfg(feature = "golden_prng")]
+    #[test]
+    fn geometry_golden_regression() {
+        let mut prng = Prng::from_seed_u64(0x5EED);
+        let d = prng.next_unit_direction::<DFix64>().0;
+        assert_eq!((d.x.0, d.y.0, d.z.0), (54_000, -33_105, 16_824));
+        let q = prng.next_quat::<DFix64>().to_array().map(f32::to_bits);
+        assert_eq!(q, [0xbf1e_a100, 0x3f43_5f00, 0x3da6_2000, 0xbe28_5000]);
+    }
+}
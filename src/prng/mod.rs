@@ -0,0 +1,563 @@
+//! Stateful pseudo-random number generators for deterministic timelines.
+//!
+//! * Not cryptographically secure; use only for gameplay/state simulation.
+//! * Seeding controls reproducibility within a single process/run and matching
+//!   seeds yield identical sequences across supported platforms.
+//! * [`Prng`] wraps a [`DeterministicRng`] core: [`Xoroshiro128Plus`] by
+//!   default, or [`Pcg32`], [`Xoshiro256StarStar`] and [`SplitMix64`]. Every
+//!   helper below is written against the trait and works over any core.
+//! * [`Philox4x64`] is counter-based: [`random`] and [`Prng::for_entity`] are
+//!   pure functions of `(seed, entity_id, tick, channel)`, so parallel jobs
+//!   draw the same values regardless of scheduling or thread count.
+//! * [`Prng`] implements [`rand_core::RngCore`] and [`rand_core::SeedableRng`],
+//!   so it plugs into the `rand` ecosystem; the byte streams produced through
+//!   those traits are pinned by [`PRNG_ALGO_VERSION`] as well.
+//! * Geometric helpers (directions, points in volumes, rotations) live in
+//!   `geometry` and are computed entirely in a deterministic scalar backend.
+//! * Non-uniform distributions (`distributions`) and weighted choice
+//!   (`weighted`) document how many `next_u64` calls each sample consumes.
+//! * Shuffles and sampling without replacement (`sequence`) use fixed
+//!   algorithms, so spawn orders and card deals never change under a version.
+
+mod distributions;
+mod geometry;
+mod pcg32;
+mod philox;
+mod sequence;
+mod splitmix64;
+mod traits;
+mod weighted;
+mod xoroshiro128;
+mod xoshiro256;
+mod ziggurat_tables;
+
+pub use pcg32::Pcg32;
+pub use philox::{random, Philox4x64};
+pub use sequence::Reservoir;
+pub use splitmix64::SplitMix64;
+pub(crate) use splitmix64::splitmix64;
+pub use traits::DeterministicRng;
+pub use weighted::{AliasTable, WeightError};
+pub use xoroshiro128::Xoroshiro128Plus;
+pub use xoshiro256::Xoshiro256StarStar;
+
+use rand_core::{RngCore, SeedableRng};
+
+use crate::scalar::{DeterministicScalar, Scalar};
+
+/// Algorithm version for PRNG bit‑exact behavior.
+/// Bump this only when intentionally changing an algorithm, its seeding rules
+/// or a helper's sampling rules, and update any golden regression tests
+/// accordingly. Serialized snapshots (`Codec`, `serde`) embed it and refuse to
+/// restore under a different value.
+pub const PRNG_ALGO_VERSION: u32 = 1;
+
+/// Stateful PRNG instance over the core algorithm `R`.
+///
+/// `Prng` on its own names the default xoroshiro128+ generator, which also
+/// provides jumping, forking and snapshots. For rollback, snapshot with
+/// [`Prng::state`] (or the `Codec`/`serde` encodings, which also record
+/// [`PRNG_ALGO_VERSION`]) and restore with [`Prng::from_state`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Prng<R = Xoroshiro128Plus> {
+    core: R,
+}
+
+impl Prng {
+    /// Constructs a PRNG from two 64-bit seeds.
+    ///
+    /// Identical seeds produce identical sequences; the generator remains
+    /// deterministic as long as each process consumes random numbers in the
+    /// same order.
+    ///
+    /// If both `seed0` and `seed1` are zero, the implementation replaces them
+    /// with a fixed non-zero constant so the internal state is never all-zero
+    /// (avoids the xoroshiro128+ sink).
+    pub fn from_seed(seed0: u64, seed1: u64) -> Self {
+        Self::new(Xoroshiro128Plus::new(seed0, seed1))
+    }
+
+    /// Constructs a PRNG from a single 64-bit seed via `SplitMix64` expansion.
+    ///
+    /// Other cores are seeded the same way through
+    /// [`DeterministicRng::from_seed_u64`], e.g.
+    /// `Prng::new(Pcg32::from_seed_u64(seed))`.
+    pub fn from_seed_u64(seed: u64) -> Self {
+        Self::new(Xoroshiro128Plus::from_seed_u64(seed))
+    }
+
+    /// Restores a generator from a [`Prng::state`] snapshot.
+    ///
+    /// Unlike [`Prng::from_seed`], the all-zero state is not remapped: it can
+    /// never be produced by a live generator, so it returns `None`.
+    pub fn from_state(state: [u64; 2]) -> Option<Self> {
+        Xoroshiro128Plus::from_state(state).map(Self::new)
+    }
+
+    /// Returns the raw xoroshiro128+ state words.
+    pub fn state(&self) -> [u64; 2] {
+        self.core.state()
+    }
+
+    /// Advances the generator by `2^64` steps.
+    ///
+    /// Equivalent to `2^64` calls to [`Prng::next_u64`]; use it to carve one
+    /// seed into up to `2^64` non-overlapping subsequences.
+    pub fn jump(&mut self) {
+        self.core.jump();
+    }
+
+    /// Advances the generator by `2^96` steps.
+    ///
+    /// Use it to hand out up to `2^32` starting points, each of which can then
+    /// be subdivided further with [`Prng::jump`].
+    pub fn long_jump(&mut self) {
+        self.core.long_jump();
+    }
+
+    /// Splits off a child generator and moves `self` past it.
+    ///
+    /// The child starts at the parent's current position; the parent then
+    /// [jumps](Prng::jump) ahead `2^64` steps, so neither stream overlaps the
+    /// other until the child has drawn `2^64` values. Repeated splits hand out
+    /// consecutive `2^64`-long blocks of the same sequence.
+    pub fn split(&mut self) -> Prng {
+        let child = self.clone();
+        self.jump();
+        child
+    }
+
+    /// Derives an independent child generator for `stream_id` without
+    /// advancing `self`.
+    ///
+    /// The child depends only on the parent's current state and `stream_id`:
+    /// forking the same state with the same id always yields the same stream,
+    /// no matter what the parent draws afterwards. Each state word is
+    /// scrambled through `SplitMix64` with a key derived from `stream_id`, so
+    /// distinct parent states never collide for a given id.
+    pub fn fork(&self, stream_id: u64) -> Prng {
+        Self::new(self.core.fork(stream_id))
+    }
+}
+
+impl<R: DeterministicRng> Prng<R> {
+    /// Wraps an already-seeded core.
+    pub fn new(core: R) -> Self {
+        Self { core }
+    }
+
+    /// Returns the underlying core.
+    pub fn core(&self) -> &R {
+        &self.core
+    }
+
+    /// Unwraps the underlying core.
+    pub fn into_core(self) -> R {
+        self.core
+    }
+
+    /// Returns the next raw 64-bit output and advances the core by one step.
+    ///
+    /// Every other draw in this module is defined in terms of this call.
+    pub fn next_u64(&mut self) -> u64 {
+        self.core.next_u64()
+    }
+
+    /// Returns 32 random bits via [`DeterministicRng::next_u32`]: the high 32
+    /// bits of one [`Prng::next_u64`] call unless the core has a native
+    /// 32-bit output.
+    pub fn next_u32(&mut self) -> u32 {
+        self.core.next_u32()
+    }
+
+    /// Returns `true` or `false` with equal probability, from the top bit of
+    /// one `next_u64` call.
+    pub fn next_bool(&mut self) -> bool {
+        self.next_u64() >> 63 == 1
+    }
+
+    /// Returns the next float in `[0, 1)`.
+    ///
+    /// Uses the high 23 bits of one `next_u64` call to fill the mantissa,
+    /// ensuring uniform float32 sampling without relying on platform RNGs.
+    pub fn next_f32(&mut self) -> f32 {
+        f32::from_unit_bits(self.next_u64())
+    }
+
+    /// Returns the next `f64` in `[0, 1)`, filling the mantissa from the high
+    /// 52 bits of one `next_u64` call.
+    pub fn next_f64(&mut self) -> f64 {
+        f64::from_unit_bits(self.next_u64())
+    }
+
+    /// Returns a deterministic scalar in `[0, 1)` from one `next_u64` call.
+    ///
+    /// Fixed-point types take the top `FRAC_BITS` bits as their raw value and
+    /// float wrappers fill their mantissa, so the result never passes through
+    /// platform float conversion (see [`Scalar::from_unit_bits`]).
+    pub fn next_scalar<T: DeterministicScalar>(&mut self) -> T {
+        T::from_unit_bits(self.next_u64())
+    }
+
+    /// Returns a scalar in the half-open range `[min, max)` as
+    /// `min + (max - min) * u` with `u` drawn like [`Prng::next_scalar`].
+    ///
+    /// Always consumes exactly one `next_u64`. `min == max` yields `min`, as
+    /// does the rare float draw whose rounded result lands on `max`.
+    ///
+    /// # Panics
+    /// Panics if `min > max`. `max - min` must be representable in `T`.
+    pub fn next_range<T: Scalar>(&mut self, min: T, max: T) -> T {
+        assert!(min <= max, "invalid range: min > max");
+        let value = min + (max - min) * T::from_unit_bits(self.next_u64());
+        if value < max {
+            value
+        } else {
+            min
+        }
+    }
+
+    /// Returns the next integer in the inclusive range `[min, max]`.
+    ///
+    /// # Panics
+    /// Panics if `min > max`.
+    ///
+    /// Uses rejection sampling with a power-of-two fast path to avoid modulo
+    /// bias, and supports the full `i32` span.
+    #[allow(clippy::cast_sign_loss, clippy::cast_possible_truncation)]
+    pub fn next_int(&mut self, min: i32, max: i32) -> i32 {
+        assert!(min <= max, "invalid range: {min}..={max}");
+        let span = (i64::from(max) - i64::from(min)) as u64 + 1;
+        (i64::from(min) + self.next_below(span) as i64) as i32
+    }
+
+    /// Returns the next integer in the inclusive range `[min, max]`, sampled
+    /// exactly like [`Prng::next_int`]; the full `i64` span is one raw
+    /// `next_u64` call.
+    ///
+    /// # Panics
+    /// Panics if `min > max`.
+    #[allow(clippy::cast_sign_loss, clippy::cast_possible_wrap)]
+    pub fn next_i64(&mut self, min: i64, max: i64) -> i64 {
+        assert!(min <= max, "invalid range: {min}..={max}");
+        let span = max.wrapping_sub(min) as u64;
+        let offset = match span.checked_add(1) {
+            Some(span) => self.next_below(span),
+            None => self.next_u64(),
+        };
+        min.wrapping_add(offset as i64)
+    }
+
+    /// Uniform value in `[0, span)`; `span == 1` consumes nothing.
+    fn next_below(&mut self, span: u64) -> u64 {
+        if span == 1 {
+            0
+        } else if span.is_power_of_two() {
+            self.next_u64() & (span - 1)
+        } else {
+            let bound = u64::MAX - u64::MAX % span;
+            loop {
+                let candidate = self.next_u64();
+                if candidate < bound {
+                    break candidate % span;
+                }
+            }
+        }
+    }
+}
+
+/// `rand_core` integration.
+///
+/// * `next_u32` is [`Prng::next_u32`]: for the default core, the high 32 bits
+///   of one [`Prng::next_u64`] call (the low bits of xoroshiro128+ are its
+///   weakest).
+/// * `fill_bytes` writes the little-endian bytes of successive `next_u64`
+///   outputs; a trailing partial chunk takes the leading (low-order) bytes of
+///   one more output and discards the rest.
+impl<R: DeterministicRng> RngCore for Prng<R> {
+    fn next_u32(&mut self) -> u32 {
+        Prng::next_u32(self)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        Prng::next_u64(self)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        let mut chunks = dest.chunks_exact_mut(8);
+        for chunk in &mut chunks {
+            chunk.copy_from_slice(&Prng::next_u64(self).to_le_bytes());
+        }
+        let tail = chunks.into_remainder();
+        if !tail.is_empty() {
+            let bytes = Prng::next_u64(self).to_le_bytes();
+            tail.copy_from_slice(&bytes[..tail.len()]);
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+/// Seeding through `rand_core` follows the same rules as the inherent
+/// constructors: the 16-byte seed is read as two little-endian `u64`s and passed
+/// to [`Prng::from_seed`], and `seed_from_u64` is [`Prng::from_seed_u64`].
+impl SeedableRng for Prng {
+    type Seed = [u8; 16];
+
+    fn from_seed(seed: Self::Seed) -> Self {
+        let mut words = [0u64; 2];
+        for (word, chunk) in words.iter_mut().zip(seed.chunks_exact(8)) {
+            let mut le = [0u8; 8];
+            le.copy_from_slice(chunk);
+            *word = u64::from_le_bytes(le);
+        }
+        Prng::from_seed(words[0], words[1])
+    }
+
+    fn seed_from_u64(state: u64) -> Self {
+        Prng::from_seed_u64(state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scalar::{DFix128, DFix32, DFix64, F32Det};
+
+    #[test]
+    fn next_int_returns_single_value_for_equal_bounds() {
+        let mut prng = Prng::from_seed(42, 99);
+        assert_eq!(prng.next_int(7, 7), 7);
+    }
+
+    #[test]
+    fn next_int_deterministic_across_calls() {
+        let mut a = Prng::from_seed(123, 456);
+        let mut b = Prng::from_seed(123, 456);
+        for _ in 0..100 {
+            assert_eq!(a.next_int(-10, 10), b.next_int(-10, 10));
+        }
+    }
+
+    #[test]
+    fn next_int_respects_bounds() {
+        let mut prng = Prng::from_seed(42, 99);
+        for _ in 0..1_000 {
+            let v = prng.next_int(-10, 10);
+            assert!((-10..=10).contains(&v));
+        }
+        for _ in 0..1_000 {
+            let v = prng.next_int(i32::MIN, i32::MAX);
+            // All i32 are valid; this simply exercises the path.
+            let _ = v;
+        }
+    }
+
+    #[cfg(feature = "golden_prng")]
+    #[test]
+    fn next_int_golden_regression() {
+        let mut prng = Prng::from_seed(0xDEAD_BEEF, 0xFACE_FEED);
+        let values: Vec<i32> = (0..3).map(|_| prng.next_int(i32::MIN, i32::MAX)).collect();
+        assert_eq!(values, vec![1_501_347_292, 1_946_982_111, -117_316_573]);
+    }
+
+    #[test]
+    fn next_scalar_takes_top_fraction_bits() {
+        let mut a = Prng::from_seed(3, 5);
+        let mut b = a.clone();
+        let raw = b.next_u64();
+        assert_eq!(a.next_scalar::<DFix64>(), DFix64((raw >> 48) as i64));
+        let raw = b.next_u64();
+        assert_eq!(a.next_scalar::<DFix128>(), DFix128(i128::from(raw >> 32)));
+        assert_eq!(a.next_scalar::<F32Det>().0, b.next_f32());
+        let raw = b.next_u64();
+        assert_eq!(a.next_scalar::<DFix32>(), DFix32((raw >> 48) as i32));
+    }
+
+    #[test]
+    fn next_range_stays_in_half_open_range() {
+        let mut prng = Prng::from_seed(42, 99);
+        let (lo, hi) = (DFix64::from_f32(-1.5), DFix64::from_f32(0.25));
+        for _ in 0..1_000 {
+            let v = prng.next_range(lo, hi);
+            assert!(lo <= v && v < hi);
+            let f = prng.next_range(F32Det(1.0), F32Det(1.0 + f32::EPSILON));
+            assert_eq!(f, F32Det(1.0));
+        }
+        assert_eq!(prng.next_range(hi, hi), hi);
+    }
+
+    #[test]
+    fn next_i64_respects_bounds() {
+        let mut prng = Prng::from_seed(42, 99);
+        for _ in 0..1_000 {
+            assert!((-3..=3).contains(&prng.next_i64(-3, 3)));
+        }
+        let mut a = prng.clone();
+        assert_eq!(a.next_i64(i64::MIN, i64::MAX), i64::MIN.wrapping_add(prng.next_u64() as i64));
+        assert_eq!(a.next_i64(i64::MAX, i64::MAX), i64::MAX);
+        assert_eq!(a, prng);
+    }
+
+    #[cfg(feature = "golden_prng")]
+    #[test]
+    fn scalar_helpers_golden_regression() {
+        let mut prng = Prng::from_seed_u64(0x5EED);
+        assert_eq!(prng.next_scalar::<DFix64>(), DFix64(24_356));
+        assert_eq!(prng.next_scalar::<F32Det>().0.to_bits(), 0x3f69_9892);
+        assert_eq!(prng.next_scalar::<DFix128>(), DFix128(144_351_255));
+        assert_eq!(prng.next_scalar::<DFix32>(), DFix32(58_423));
+
+        let mut prng = Prng::from_seed_u64(0x5EED);
+        assert_eq!(prng.next_range(DFix64::from_f32(-2.0), DFix64::from_f32(3.0)), DFix64(-9_292));
+        assert_eq!(prng.next_i64(i64::MIN, i64::MAX), 7_608_993_730_858_004_038);
+        assert_eq!(prng.next_i64(i64::MIN, i64::MAX), -8_603_388_113_613_977_173);
+        let ints: Vec<i64> = (0..3).map(|_| prng.next_i64(-1_000, 1_000)).collect();
+        assert_eq!(ints, vec![-317, -42, 717]);
+        assert_eq!(prng.next_f64().to_bits(), 0x3fed_85c3_9a78_8506);
+    }
+
+    #[test]
+    fn state_round_trips_and_rejects_zero() {
+        let mut prng = Prng::from_seed_u64(77);
+        prng.next_u64();
+        let mut restored = Prng::from_state(prng.state()).unwrap();
+        assert_eq!(restored.next_u64(), prng.next_u64());
+        assert_eq!(Prng::from_state([0, 0]), None);
+        assert_eq!(Prng::from_state([0, 1]).map(|p| p.state()), Some([0, 1]));
+    }
+
+    #[test]
+    fn fill_bytes_is_little_endian_next_u64() {
+        let mut a = Prng::from_seed(7, 11);
+        let mut b = a.clone();
+        let mut bytes = [0u8; 19];
+        a.fill_bytes(&mut bytes);
+        let mut expected = Vec::new();
+        for _ in 0..3 {
+            expected.extend_from_slice(&b.next_u64().to_le_bytes());
+        }
+        assert_eq!(bytes[..], expected[..19]);
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn next_u32_takes_high_bits() {
+        let mut a = Prng::from_seed(7, 11);
+        let mut b = a.clone();
+        assert_eq!(u64::from(RngCore::next_u32(&mut a)), b.next_u64() >> 32);
+    }
+
+    #[test]
+    fn seedable_rng_matches_inherent_constructors() {
+        let mut seed = [0u8; 16];
+        seed[..8].copy_from_slice(&123u64.to_le_bytes());
+        seed[8..].copy_from_slice(&456u64.to_le_bytes());
+        assert_eq!(<Prng as SeedableRng>::from_seed(seed), Prng::from_seed(123, 456));
+        assert_eq!(<Prng as SeedableRng>::from_seed([0; 16]), Prng::from_seed(0, 0));
+        assert_eq!(Prng::seed_from_u64(99), Prng::from_seed_u64(99));
+    }
+
+    #[cfg(feature = "golden_prng")]
+    #[test]
+    fn fill_bytes_golden_regression() {
+        let mut prng = Prng::from_seed(0xDEAD_BEEF, 0xFACE_FEED);
+        let mut bytes = [0u8; 12];
+        prng.fill_bytes(&mut bytes);
+        assert_eq!(bytes, [220, 189, 124, 217, 1, 0, 0, 0, 223, 150, 12, 244]);
+        assert_eq!(RngCore::next_u32(&mut prng), 0x9e0b_6232);
+        let mut seeded = Prng::seed_from_u64(0x5EED);
+        assert_eq!(RngCore::next_u64(&mut seeded), 0x5f24_71b3_1fb0_2e29);
+    }
+
+    fn exercise_helpers<R: DeterministicRng>(seed: u64) {
+        let mut prng = Prng::new(R::from_seed_u64(seed));
+        let mut replay = prng.clone();
+        let raw = replay.next_u64();
+        assert_eq!(prng.next_scalar::<DFix64>(), DFix64((raw >> 48) as i64));
+        assert_eq!(prng.next_u64(), replay.next_u64());
+        for _ in 0..200 {
+            assert!((-10..=10).contains(&prng.next_int(-10, 10)));
+            let v = prng.next_range(DFix64::from_f32(-1.5), DFix64::from_f32(0.25));
+            assert!(DFix64::from_f32(-1.5) <= v && v < DFix64::from_f32(0.25));
+            let d = prng.next_unit_direction::<DFix64>();
+            assert!((d.0.length_sq().to_f64() - 1.0).abs() < 1e-3);
+            assert!(prng.next_normal(F32Det(0.0), F32Det(1.0)).0.is_finite());
+        }
+        let mut deck: Vec<u32> = (0..20).collect();
+        prng.shuffle(&mut deck);
+        deck.sort_unstable();
+        assert_eq!(deck, (0..20).collect::<Vec<_>>());
+        assert_eq!(prng.next_weighted_index(&[0, 5, 0]), Ok(1));
+
+        let mut bytes = [0u8; 8];
+        let mut copy = prng.clone();
+        prng.fill_bytes(&mut bytes);
+        assert_eq!(bytes, copy.next_u64().to_le_bytes());
+    }
+
+    #[test]
+    fn helpers_work_over_every_core() {
+        exercise_helpers::<Xoroshiro128Plus>(1);
+        exercise_helpers::<Pcg32>(2);
+        exercise_helpers::<Xoshiro256StarStar>(3);
+        exercise_helpers::<SplitMix64>(4);
+        exercise_helpers::<Philox4x64>(5);
+        assert_eq!(Prng::from_seed_u64(9), Prng::new(Xoroshiro128Plus::from_seed_u64(9)));
+    }
+
+    #[test]
+    fn algo_ids_are_distinct() {
+        let ids = [
+            Xoroshiro128Plus::ALGO_ID,
+            SplitMix64::ALGO_ID,
+            Pcg32::ALGO_ID,
+            Xoshiro256StarStar::ALGO_ID,
+            Philox4x64::ALGO_ID,
+        ];
+        for (i, a) in ids.iter().enumerate() {
+            assert!(ids[i + 1..].iter().all(|b| a != b));
+        }
+    }
+
+    #[test]
+    fn split_hands_out_consecutive_blocks() {
+        let mut parent = Prng::from_seed(42, 99);
+        let start = parent.clone();
+        let child = parent.split();
+        assert_eq!(child, start);
+        let mut jumped = start;
+        jumped.jump();
+        assert_eq!(parent, jumped);
+    }
+
+    #[test]
+    fn fork_ignores_later_parent_consumption() {
+        let mut parent = Prng::from_seed_u64(7);
+        let mut a = parent.fork(3);
+        for _ in 0..17 {
+            parent.next_u64();
+        }
+        let rewound = Prng::from_seed_u64(7);
+        let mut b = rewound.fork(3);
+        for _ in 0..100 {
+            assert_eq!(a.next_u64(), b.next_u64());
+        }
+        assert_ne!(rewound.fork(3), rewound.fork(4));
+        assert_ne!(parent.fork(3), rewound.fork(3));
+    }
+
+    #[cfg(feature = "golden_prng")]
+    #[test]
+    fn jump_and_fork_golden_regression() {
+        let mut prng = Prng::from_seed(0xDEAD_BEEF, 0xFACE_FEED);
+        prng.jump();
+        assert_eq!(prng.next_u64(), 0xabde_9172_6022_0542);
+        prng.long_jump();
+        assert_eq!(prng.next_u64(), 0x3677_f118_b0e5_e8cb);
+        assert_eq!(prng.fork(1).next_u64(), 0x9764_6338_30c4_acef);
+    }
+}
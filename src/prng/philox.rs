@@ -0,0 +1,140 @@
+use super::{DeterministicRng, Prng};
+
+const MULTIPLIERS: [u64; 2] = [0xd2e7_470e_e14c_6c93, 0xca5a_8263_9512_1157];
+const KEY_BUMPS: [u64; 2] = [0x9e37_79b9_7f4a_7c15, 0xbb67_ae85_84ca_a73b];
+const ROUNDS: usize = 10;
+
+/// The Philox4x64-10 bijection (Salmon et al., Random123): encrypts a 256-bit
+/// counter under a 128-bit key.
+#[allow(clippy::cast_possible_truncation)]
+fn philox4x64(mut counter: [u64; 4], mut key: [u64; 2]) -> [u64; 4] {
+    for round in 0..ROUNDS {
+        let p0 = u128::from(MULTIPLIERS[0]) * u128::from(counter[0]);
+        let p1 = u128::from(MULTIPLIERS[1]) * u128::from(counter[2]);
+        counter = [
+            (p1 >> 64) as u64 ^ counter[1] ^ key[0],
+            p1 as u64,
+            (p0 >> 64) as u64 ^ counter[3] ^ key[1],
+            p0 as u64,
+        ];
+        if round + 1 < ROUNDS {
+            key = [key[0].wrapping_add(KEY_BUMPS[0]), key[1].wrapping_add(KEY_BUMPS[1])];
+        }
+    }
+    counter
+}
+
+/// Returns the first 64-bit draw for `(seed, entity_id, tick, channel)`.
+///
+/// A pure function: the same arguments give the same value on every platform
+/// and thread, no matter how many other draws happened or in which order. It
+/// equals the first [`Prng::next_u64`] of [`Prng::for_entity`] with the same
+/// arguments; use that when a job needs more than one draw or a helper such as
+/// a range or direction.
+pub fn random(seed: u64, entity_id: u64, tick: u64, channel: u64) -> u64 {
+    philox4x64([entity_id, tick, channel, 0], [seed, 0])[0]
+}
+
+/// Counter-based Philox4x64-10 generator.
+///
+/// Output block `n` of a stream is the Philox encryption of
+/// `[entity_id, tick, channel, n]` under the key `[seed, 0]`, so any draw can
+/// be computed directly from its coordinates without replaying earlier ones.
+/// Give every parallel job its own stream (one per entity, tick and purpose)
+/// and results no longer depend on scheduling or thread count. Each block
+/// yields four `next_u64` outputs; a stream repeats after `2^66` draws.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Philox4x64 {
+    key: [u64; 2],
+    counter: [u64; 4],
+    block: [u64; 4],
+    used: usize,
+}
+
+impl Philox4x64 {
+    /// Opens the stream for `(seed, entity_id, tick, channel)` at its first
+    /// draw.
+    pub fn new(seed: u64, entity_id: u64, tick: u64, channel: u64) -> Self {
+        Self { key: [seed, 0], counter: [entity_id, tick, channel, 0], block: [0; 4], used: 4 }
+    }
+}
+
+impl DeterministicRng for Philox4x64 {
+    const ALGO_ID: u32 = 5;
+
+    /// Opens stream `(seed, 0, 0, 0)`.
+    fn from_seed_u64(seed: u64) -> Self {
+        Self::new(seed, 0, 0, 0)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        if self.used == 4 {
+            self.block = philox4x64(self.counter, self.key);
+            self.counter[3] = self.counter[3].wrapping_add(1);
+            self.used = 0;
+        }
+        self.used += 1;
+        self.block[self.used - 1]
+    }
+}
+
+impl Prng<Philox4x64> {
+    /// Opens the counter-based stream for `(seed, entity_id, tick, channel)`.
+    ///
+    /// The result depends only on its arguments, so jobs that each build their
+    /// own stream draw identical values however they are scheduled. All `Prng`
+    /// helpers are available and consume the stream as documented for them.
+    pub fn for_entity(seed: u64, entity_id: u64, tick: u64, channel: u64) -> Self {
+        Self::new(Philox4x64::new(seed, entity_id, tick, channel))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_reference_outputs() {
+        // Known-answer vectors from Random123's `kat_vectors`.
+        assert_eq!(
+            philox4x64([0; 4], [0; 2]),
+            [0x1655_4d9e_ca36_314c, 0xdb20_fe9d_672d_0fdc, 0xd7e7_72ce_e186_176b, 0x7e68_b68a_ec7b_a23b]
+        );
+        assert_eq!(
+            philox4x64([u64::MAX; 4], [u64::MAX; 2]),
+            [0x87b0_92c3_013f_e90b, 0x438c_3c67_be8d_0224, 0x9cc7_d7c6_9cd7_77b6, 0xa09c_aebf_594f_0ba0]
+        );
+        let counter = [0x243f_6a88_85a3_08d3, 0x1319_8a2e_0370_7344, 0xa409_3822_299f_31d0, 0x082e_fa98_ec4e_6c89];
+        assert_eq!(
+            philox4x64(counter, [0x4528_21e6_38d0_1377, 0xbe54_66cf_34e9_0c6c]),
+            [0xa528_f454_03e6_1d95, 0x38c7_2dbd_566e_9788, 0xa5a1_610e_72fd_18b5, 0x57bd_43b5_e52b_7fe6]
+        );
+    }
+
+    #[test]
+    fn streams_follow_the_counter() {
+        let mut stream = Prng::for_entity(7, 42, 1_000, 3);
+        assert_eq!(stream.next_u64(), random(7, 42, 1_000, 3));
+        let draws: Vec<u64> = (0..7).map(|_| stream.next_u64()).collect();
+        let block1 = philox4x64([42, 1_000, 3, 1], [7, 0]);
+        assert_eq!(draws[3..], block1[..]);
+        assert_ne!(random(7, 42, 1_000, 3), random(7, 42, 1_000, 4));
+        assert_ne!(random(7, 42, 1_000, 3), random(7, 43, 1_000, 3));
+        assert_ne!(random(7, 42, 1_000, 3), random(8, 42, 1_000, 3));
+    }
+
+    #[test]
+    fn draws_do_not_depend_on_evaluation_order() {
+        let forward: Vec<u64> = (0..64).map(|entity| random(1, entity, 9, 0)).collect();
+        let mut backward: Vec<u64> = (0..64).rev().map(|entity| random(1, entity, 9, 0)).collect();
+        backward.reverse();
+        assert_eq!(forward, backward);
+
+        let threads: Vec<_> = (0..4u64)
+            .map(|t| std::thread::spawn(move || (t..64).step_by(4).map(|e| (e, random(1, e, 9, 0))).collect::<Vec<_>>()))
+            .collect();
+        for (entity, value) in threads.into_iter().flat_map(|h| h.join().unwrap()) {
+            assert_eq!(forward[entity as usize], value);
+        }
+    }
+}
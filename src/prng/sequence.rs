@@ -0,0 +1,220 @@
+//! Shuffling and sampling over slices and streams.
+//!
+//! Every algorithm here is fixed and pinned by [`PRNG_ALGO_VERSION`] together
+//! with the generator itself, unlike `std`/`rand` helpers whose algorithms may
+//! change between releases. Each bounded draw uses the same rejection sampler
+//! as [`Prng::next_int`]: one `next_u64` call barring rare retries, and none
+//! for a span of 1.
+//!
+//! [`PRNG_ALGO_VERSION`]: super::PRNG_ALGO_VERSION
+
+use std::collections::BTreeMap;
+
+use super::{DeterministicRng, Prng};
+
+impl<R: DeterministicRng> Prng<R> {
+    /// Uniform index in `[0, len)`; `len` must be non-zero.
+    fn next_index(&mut self, len: usize) -> usize {
+        self.next_below(len as u64) as usize
+    }
+
+    /// Shuffles `slice` in place with the Fisher–Yates algorithm, walking from
+    /// the back: for `i = len - 1` down to `1`, swaps `i` with a uniform index
+    /// in `[0, i]`. Makes `len - 1` bounded draws.
+    pub fn shuffle<T>(&mut self, slice: &mut [T]) {
+        for i in (1..slice.len()).rev() {
+            let j = self.next_index(i + 1);
+            slice.swap(i, j);
+        }
+    }
+
+    /// Returns a uniformly chosen element, or `None` for an empty slice
+    /// without drawing. Makes one bounded draw.
+    pub fn choose<'a, T>(&mut self, slice: &'a [T]) -> Option<&'a T> {
+        if slice.is_empty() {
+            None
+        } else {
+            Some(&slice[self.next_index(slice.len())])
+        }
+    }
+
+    /// Mutable variant of [`Prng::choose`].
+    pub fn choose_mut<'a, T>(&mut self, slice: &'a mut [T]) -> Option<&'a mut T> {
+        if slice.is_empty() {
+            None
+        } else {
+            let i = self.next_index(slice.len());
+            Some(&mut slice[i])
+        }
+    }
+
+    /// Returns `k` distinct indices from `[0, n)` in random order.
+    ///
+    /// Runs the first `k` steps of a front-to-back Fisher–Yates shuffle over
+    /// the virtual array `0..n`, tracking only displaced entries, so it costs
+    /// `O(k log k)` regardless of `n`. Makes `k` bounded draws; the result is
+    /// a prefix of the shuffle for a larger `k`.
+    ///
+    /// # Panics
+    /// Panics if `k > n`.
+    pub fn sample_indices(&mut self, n: usize, k: usize) -> Vec<usize> {
+        assert!(k <= n, "cannot sample {k} of {n} indices");
+        let mut displaced = BTreeMap::new();
+        let mut out = Vec::with_capacity(k);
+        for i in 0..k {
+            let j = i + self.next_index(n - i);
+            let picked = displaced.get(&j).copied().unwrap_or(j);
+            let current = displaced.get(&i).copied().unwrap_or(i);
+            displaced.insert(j, current);
+            out.push(picked);
+        }
+        out
+    }
+
+    /// Draws `k` items uniformly without replacement from `iter` in a single
+    /// pass (see [`Reservoir`]). Returns every item when the stream holds `k`
+    /// or fewer.
+    pub fn reservoir_sample<I: IntoIterator>(&mut self, iter: I, k: usize) -> Vec<I::Item> {
+        let mut reservoir = Reservoir::new(k);
+        for item in iter {
+            reservoir.offer(self, item);
+        }
+        reservoir.into_vec()
+    }
+}
+
+/// Streaming uniform sample of up to `k` items (Algorithm R).
+///
+/// Items may arrive over many ticks; any generator can be passed to each
+/// [`Reservoir::offer`], so the sampler can live in simulation state next to
+/// the `Prng` that feeds it. The first `k` items are kept without drawing;
+/// item number `i` (zero-based, `i >= k`) makes one bounded draw in `[0, i]`
+/// and replaces that slot when the draw is below `k`. Slots are in
+/// replacement order, not shuffled.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Reservoir<T> {
+    items: Vec<T>,
+    capacity: usize,
+    seen: u64,
+}
+
+impl<T> Reservoir<T> {
+    /// Creates an empty reservoir keeping up to `k` items.
+    pub fn new(k: usize) -> Self {
+        Self { items: Vec::with_capacity(k), capacity: k, seen: 0 }
+    }
+
+    /// Offers the next stream item.
+    pub fn offer<R: DeterministicRng>(&mut self, prng: &mut Prng<R>, item: T) {
+        if self.items.len() < self.capacity {
+            self.items.push(item);
+        } else {
+            let j = prng.next_below(self.seen + 1);
+            if j < self.capacity as u64 {
+                self.items[j as usize] = item;
+            }
+        }
+        self.seen += 1;
+    }
+
+    /// Number of items offered so far.
+    pub fn seen(&self) -> u64 {
+        self.seen
+    }
+
+    /// The current sample.
+    pub fn as_slice(&self) -> &[T] {
+        &self.items
+    }
+
+    /// Consumes the reservoir, returning the sample.
+    pub fn into_vec(self) -> Vec<T> {
+        self.items
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shuffle_is_a_uniform_permutation() {
+        let mut prng = Prng::from_seed(1, 2);
+        let mut counts = BTreeMap::new();
+        for _ in 0..60_000 {
+            let mut v = [0, 1, 2];
+            prng.shuffle(&mut v);
+            *counts.entry(v).or_insert(0u32) += 1;
+        }
+        assert_eq!(counts.len(), 6);
+        assert!(counts.values().all(|&c| (9_500..10_500).contains(&c)), "{counts:?}");
+
+        let mut big: Vec<u32> = (0..1_000).collect();
+        prng.shuffle(&mut big);
+        big.sort_unstable();
+        assert!(big.iter().copied().eq(0..1_000));
+    }
+
+    #[test]
+    fn choose_handles_empty_and_draws_once() {
+        let mut prng = Prng::from_seed(3, 4);
+        let before = prng.clone();
+        assert_eq!(prng.choose::<u8>(&[]), None);
+        assert_eq!(prng, before);
+
+        let mut expected = prng.clone();
+        let items = [10, 20, 30];
+        let picked = *prng.choose(&items).unwrap();
+        assert_eq!(picked, items[(expected.next_u64() % 3) as usize]);
+        assert_eq!(prng, expected);
+
+        let mut items = items;
+        *prng.choose_mut(&mut items).unwrap() = 0;
+        assert_eq!(items.iter().filter(|&&x| x == 0).count(), 1);
+    }
+
+    #[test]
+    fn sample_indices_is_a_shuffle_prefix() {
+        let mut prng = Prng::from_seed(5, 6);
+        let mut full = prng.clone();
+        let picked = prng.sample_indices(50, 10);
+        let all = full.sample_indices(50, 50);
+        assert_eq!(picked[..], all[..10]);
+
+        let mut sorted = all;
+        sorted.sort_unstable();
+        assert!(sorted.iter().copied().eq(0..50));
+        assert!(prng.sample_indices(usize::MAX, 3).iter().all(|&i| i < usize::MAX));
+        assert!(prng.sample_indices(7, 0).is_empty());
+    }
+
+    #[test]
+    fn reservoir_is_uniform_over_the_stream() {
+        let mut prng = Prng::from_seed(7, 8);
+        let mut counts = [0u32; 10];
+        for _ in 0..20_000 {
+            for i in prng.reservoir_sample(0..10, 3) {
+                counts[i] += 1;
+            }
+        }
+        assert!(counts.iter().all(|&c| (5_700..6_300).contains(&c)), "{counts:?}");
+
+        let mut reservoir = Reservoir::new(4);
+        for i in 0..3 {
+            reservoir.offer(&mut prng, i);
+        }
+        assert_eq!((reservoir.as_slice(), reservoir.seen()), (&[0, 1, 2][..], 3));
+    }
+
+    #[cfg(feature = "golden_prng")]
+    #[test]
+    fn sequence_golden_regression() {
+        let mut prng = Prng::from_seed_u64(0x5EED);
+        let mut deck: Vec<u8> = (0..10).collect();
+        prng.shuffle(&mut deck);
+        assert_eq!(deck, vec![9, 4, 5, 2, 0, 8, 1, 3, 6, 7]);
+        assert_eq!(prng.choose(&["a", "b", "c", "d"]), Some(&"d"));
+        assert_eq!(prng.sample_indices(1_000, 5), vec![268, 435, 883, 764, 389]);
+        assert_eq!(prng.reservoir_sample(100..200, 4), vec![108, 167, 171, 164]);
+    }
+}
@@ -0,0 +1,32 @@
+/// Core generator algorithm behind [`Prng`](super::Prng).
+///
+/// An implementation only supplies raw 64-bit outputs; every helper (ranges,
+/// scalars, shuffles, geometric sampling, distributions) lives on `Prng<R>`
+/// and is defined purely in terms of [`DeterministicRng::next_u64`], so it
+/// behaves identically over any algorithm.
+///
+/// Implementations must be pure functions of their state: the same state
+/// yields the same outputs on every platform, with no floating point and no
+/// platform-sized integers.
+pub trait DeterministicRng: Clone {
+    /// Stable identifier of the algorithm, its seeding and its output rules.
+    ///
+    /// Distinct per implementation so snapshots and logs can name the stream;
+    /// a change to any of those rules also bumps
+    /// [`PRNG_ALGO_VERSION`](super::PRNG_ALGO_VERSION).
+    const ALGO_ID: u32;
+
+    /// Expands one 64-bit seed into a full state (through `SplitMix64` for
+    /// the recurrence-based cores). Never returns a state the algorithm
+    /// cannot leave.
+    fn from_seed_u64(seed: u64) -> Self;
+
+    /// Returns the next raw 64-bit output and advances the state.
+    fn next_u64(&mut self) -> u64;
+
+    /// Returns 32 random bits; the high half of one `next_u64` unless the
+    /// algorithm has a native 32-bit output.
+    fn next_u32(&mut self) -> u32 {
+        (self.next_u64() >> 32) as u32
+    }
+}
@@ -0,0 +1,210 @@
+//! Weighted choice over integer or `DFix64` weights.
+//!
+//! Weights are integers (fixed-point weights use their raw value), so every
+//! probability is an exact rational and sampling involves no rounding at all.
+
+use core::fmt;
+
+use super::{DeterministicRng, Prng};
+use crate::scalar::DFix64;
+
+/// Reasons a weight list is rejected.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum WeightError {
+    /// The list is empty.
+    Empty,
+    /// Every weight is zero.
+    AllZero,
+    /// A fixed-point weight is negative.
+    Negative,
+    /// The weights sum past `u64::MAX`.
+    Overflow,
+}
+
+impl fmt::Display for WeightError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Empty => "no weights given",
+            Self::AllZero => "all weights are zero",
+            Self::Negative => "negative weight",
+            Self::Overflow => "weights sum past u64::MAX",
+        })
+    }
+}
+
+impl std::error::Error for WeightError {}
+
+fn total(weights: &[u64]) -> Result<u64, WeightError> {
+    if weights.is_empty() {
+        return Err(WeightError::Empty);
+    }
+    let total = weights
+        .iter()
+        .try_fold(0u64, |acc, &w| acc.checked_add(w))
+        .ok_or(WeightError::Overflow)?;
+    if total == 0 {
+        return Err(WeightError::AllZero);
+    }
+    Ok(total)
+}
+
+fn raw_weights(weights: &[DFix64]) -> Result<Vec<u64>, WeightError> {
+    weights.iter().map(|w| u64::try_from(w.0).map_err(|_| WeightError::Negative)).collect()
+}
+
+/// Walker/Vose alias table for O(1) weighted sampling.
+///
+/// Built with exact integer arithmetic: column `i` keeps itself when a
+/// uniform draw in `[0, total)` falls below `threshold[i]` and yields
+/// `alias[i]` otherwise, so index `i` is chosen with probability exactly
+/// `weights[i] / total`. Construction order is fixed, so the same weights
+/// always give the same table.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AliasTable {
+    threshold: Vec<u64>,
+    alias: Vec<usize>,
+    total: u64,
+}
+
+impl AliasTable {
+    /// Builds a table from integer weights. Zero weights are allowed and are
+    /// never chosen.
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn new(weights: &[u64]) -> Result<Self, WeightError> {
+        let total = total(weights)?;
+        let n = weights.len() as u128;
+        let big = u128::from(total);
+        // Scaled so that the average column holds exactly `total`.
+        let mut scaled: Vec<u128> = weights.iter().map(|&w| u128::from(w) * n).collect();
+        let (mut small, mut large): (Vec<usize>, Vec<usize>) = (0..weights.len()).partition(|&i| scaled[i] < big);
+        let mut threshold = vec![total; weights.len()];
+        let mut alias: Vec<usize> = (0..weights.len()).collect();
+
+        while let (Some(s), Some(&l)) = (small.pop(), large.last()) {
+            threshold[s] = scaled[s] as u64;
+            alias[s] = l;
+            scaled[l] = scaled[l] + scaled[s] - big;
+            if scaled[l] < big {
+                large.pop();
+                small.push(l);
+            }
+        }
+        Ok(Self { threshold, alias, total })
+    }
+
+    /// Builds a table from non-negative fixed-point weights, using their raw
+    /// values.
+    pub fn from_dfix64(weights: &[DFix64]) -> Result<Self, WeightError> {
+        Self::new(&raw_weights(weights)?)
+    }
+
+    /// Number of entries (including zero-weight ones).
+    pub fn len(&self) -> usize {
+        self.threshold.len()
+    }
+
+    /// Always `false`: empty weight lists are rejected at construction.
+    pub fn is_empty(&self) -> bool {
+        self.threshold.is_empty()
+    }
+}
+
+impl<R: DeterministicRng> Prng<R> {
+    /// Returns an index into the table's weights with probability
+    /// proportional to its weight.
+    ///
+    /// Makes two bounded draws (column, then threshold), each one `next_u64`
+    /// call barring rejection retries (probability below `span / 2^64`); a
+    /// span of 1 consumes nothing.
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn next_weighted(&mut self, table: &AliasTable) -> usize {
+        let column = self.next_below(table.len() as u64) as usize;
+        if self.next_below(table.total) < table.threshold[column] {
+            column
+        } else {
+            table.alias[column]
+        }
+    }
+
+    /// One-shot weighted choice by cumulative scan, for lists sampled too
+    /// rarely to be worth an [`AliasTable`]. Makes one bounded draw in
+    /// `[0, total)`.
+    pub fn next_weighted_index(&mut self, weights: &[u64]) -> Result<usize, WeightError> {
+        let mut r = self.next_below(total(weights)?);
+        for (i, &w) in weights.iter().enumerate() {
+            if r < w {
+                return Ok(i);
+            }
+            r -= w;
+        }
+        unreachable!("draw is below the weight total")
+    }
+
+    /// [`Prng::next_weighted_index`] over non-negative fixed-point weights.
+    pub fn next_weighted_index_dfix64(&mut self, weights: &[DFix64]) -> Result<usize, WeightError> {
+        self.next_weighted_index(&raw_weights(weights)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scalar::Scalar;
+
+    /// Reassembles each index's probability mass (scaled by `n * total`).
+    fn mass(table: &AliasTable) -> Vec<u128> {
+        let mut mass = vec![0u128; table.len()];
+        for i in 0..table.len() {
+            mass[i] += u128::from(table.threshold[i]);
+            mass[table.alias[i]] += u128::from(table.total - table.threshold[i]);
+        }
+        mass
+    }
+
+    #[test]
+    fn alias_table_is_exact() {
+        let weights = [7, 0, 1, 13, 2, 2, 0, 40];
+        let table = AliasTable::new(&weights).unwrap();
+        let n = weights.len() as u128;
+        let expected: Vec<u128> = weights.iter().map(|&w| u128::from(w) * n).collect();
+        assert_eq!(mass(&table), expected);
+
+        let big = AliasTable::new(&[u64::MAX - 1, 1]).unwrap();
+        assert_eq!(mass(&big), vec![2 * u128::from(u64::MAX - 1), 2]);
+    }
+
+    #[test]
+    fn zero_weights_are_never_chosen() {
+        let table = AliasTable::from_dfix64(&[DFix64::ZERO, DFix64::ONE, DFix64::ZERO, DFix64(3)]).unwrap();
+        let mut prng = Prng::from_seed(1, 2);
+        let mut counts = [0u32; 4];
+        for _ in 0..10_000 {
+            counts[prng.next_weighted(&table)] += 1;
+            counts[prng.next_weighted_index(&[0, 1, 0, 1]).unwrap()] += 1;
+        }
+        assert_eq!((counts[0], counts[2]), (0, 0));
+        assert!(counts[1] > 4_000 && counts[3] > 4_000);
+    }
+
+    #[test]
+    fn invalid_weights_are_rejected() {
+        assert_eq!(AliasTable::new(&[]), Err(WeightError::Empty));
+        assert_eq!(AliasTable::new(&[0, 0]), Err(WeightError::AllZero));
+        assert_eq!(AliasTable::new(&[u64::MAX, 1]), Err(WeightError::Overflow));
+        assert_eq!(AliasTable::from_dfix64(&[DFix64::ONE, -DFix64::ONE]), Err(WeightError::Negative));
+        let mut prng = Prng::from_seed(1, 2);
+        let before = prng.clone();
+        assert_eq!(prng.next_weighted_index(&[0]), Err(WeightError::AllZero));
+        assert_eq!(prng, before);
+    }
+
+    #[cfg(feature = "golden_prng")]
+    #[test]
+    fn weighted_golden_regression() {
+        let mut prng = Prng::from_seed_u64(0x5EED);
+        let table = AliasTable::new(&[5, 1, 3, 0, 11]).unwrap();
+        let picks: Vec<usize> = (0..8).map(|_| prng.next_weighted(&table)).collect();
+        assert_eq!(picks, vec![2, 0, 4, 4, 0, 4, 0, 4]);
+        assert_eq!(prng.next_weighted_index(&[5, 1, 3, 0, 11]), Ok(4));
+    }
+}
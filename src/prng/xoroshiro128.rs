@@ -0,0 +1,116 @@
+use super::splitmix64::splitmix64;
+use super::DeterministicRng;
+
+/// Jump polynomial advancing xoroshiro128+ (55/14/36) by `2^64` steps.
+const JUMP: [u64; 2] = [0xbeac_0467_eba5_facb, 0xd86b_048b_86aa_9922];
+
+/// Jump polynomial advancing xoroshiro128+ (55/14/36) by `2^96` steps.
+const LONG_JUMP: [u64; 2] = [0x18f7_c399_cceb_da8d, 0xf2de_ac28_bef3_bb07];
+
+/// Replacement for the all-zero state, which xoroshiro128+ can never leave.
+const NONZERO_STATE: [u64; 2] = [0x9e37_79b9_7f4a_7c15, 0];
+
+/// Blackman & Vigna's xoroshiro128+ (55/14/36), the default [`Prng`](super::Prng)
+/// core.
+///
+/// Fast with 128-bit state and `2^128 - 1` period; its lowest bits are weak,
+/// which every helper avoids by drawing from the high bits. Supports
+/// [jumping](Xoroshiro128Plus::jump) for non-overlapping substreams.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Xoroshiro128Plus {
+    state: [u64; 2],
+}
+
+impl Xoroshiro128Plus {
+    /// Constructs a generator from two state words, replacing the all-zero
+    /// pair with a fixed non-zero constant.
+    pub fn new(seed0: u64, seed1: u64) -> Self {
+        let state = [seed0, seed1];
+        Self { state: if state == [0, 0] { NONZERO_STATE } else { state } }
+    }
+
+    /// Restores a generator from its state words; `None` for the all-zero
+    /// state.
+    pub fn from_state(state: [u64; 2]) -> Option<Self> {
+        (state != [0, 0]).then_some(Self { state })
+    }
+
+    /// Returns the raw state words.
+    pub fn state(&self) -> [u64; 2] {
+        self.state
+    }
+
+    /// Advances the generator by `2^64` steps.
+    pub fn jump(&mut self) {
+        self.jump_by(JUMP);
+    }
+
+    /// Advances the generator by `2^96` steps.
+    pub fn long_jump(&mut self) {
+        self.jump_by(LONG_JUMP);
+    }
+
+    /// Applies the jump polynomial `poly` (low word first).
+    fn jump_by(&mut self, poly: [u64; 2]) {
+        let mut acc = [0u64; 2];
+        for word in poly {
+            for bit in 0..64 {
+                if word & (1 << bit) != 0 {
+                    acc[0] ^= self.state[0];
+                    acc[1] ^= self.state[1];
+                }
+                self.next_u64();
+            }
+        }
+        self.state = acc;
+    }
+
+    /// Scrambles each state word through `SplitMix64` keyed by `stream_id`.
+    pub(super) fn fork(&self, stream_id: u64) -> Self {
+        let mut keys = stream_id;
+        let mut sm0 = self.state[0] ^ splitmix64(&mut keys);
+        let mut sm1 = self.state[1] ^ splitmix64(&mut keys);
+        Self::new(splitmix64(&mut sm0), splitmix64(&mut sm1))
+    }
+}
+
+impl DeterministicRng for Xoroshiro128Plus {
+    const ALGO_ID: u32 = 1;
+
+    /// Takes two `SplitMix64(seed)` outputs as the state words.
+    fn from_seed_u64(seed: u64) -> Self {
+        let mut sm = seed;
+        let seed0 = splitmix64(&mut sm);
+        Self::new(seed0, splitmix64(&mut sm))
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let s0 = self.state[0];
+        let mut s1 = self.state[1];
+        let result = s0.wrapping_add(s1);
+
+        s1 ^= s0;
+        self.state[0] = s0.rotate_left(55) ^ s1 ^ (s1 << 14);
+        self.state[1] = s1.rotate_left(36);
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn jump_polynomial_matches_stepping() {
+        // x^1000 mod the characteristic polynomial: exercises the jump
+        // machinery with a distance that can be checked by brute force.
+        let mut jumped = Xoroshiro128Plus::new(0x1234, 0x5678);
+        let mut stepped = jumped.clone();
+        jumped.jump_by([0x31c6_7b39_4c3a_8d9a, 0xde67_95fb_74e8_03c9]);
+        for _ in 0..1_000 {
+            stepped.next_u64();
+        }
+        assert_eq!(jumped, stepped);
+    }
+}
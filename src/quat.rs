@@ -5,6 +5,7 @@ use crate::{Mat4, Vec3, EPSILON};
 /// * All angles are expressed in radians.
 /// * Normalisation clamps to `f32` to match runtime behaviour.
 #[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(transparent))]
 pub struct Quat {
     data: [f32; 4],
 }
@@ -39,20 +40,20 @@ impl Quat {
     ///
     /// Returns the identity quaternion when the axis length is ≤ `EPSILON` to avoid
     /// undefined orientations and preserve deterministic behaviour. No small-angle approximation is applied.
-    pub fn from_axis_angle(axis: Vec3, angle: f32) -> Self {
-        let len_sq = axis.length_squared();
+    pub fn from_axis_angle(axis: Vec3<f32>, angle: f32) -> Self {
+        let len_sq = axis.length_sq();
         if len_sq <= EPSILON * EPSILON {
             return Self::identity();
         }
         let len = len_sq.sqrt();
-        let norm_axis = axis.scale(1.0 / len);
+        let norm_axis = axis * (1.0 / len);
         let half = angle * 0.5;
         let (sin_half, cos_half) = half.sin_cos();
-        let scaled = norm_axis.scale(sin_half);
+        let scaled = norm_axis * sin_half;
         Self::new(
-            scaled.component(0),
-            scaled.component(1),
-            scaled.component(2),
+            scaled.x,
+            scaled.y,
+            scaled.z,
             cos_half,
         )
     }
@@ -73,7 +74,8 @@ impl Quat {
     /// # Examples
     /// ```
     /// use core::f32::consts::FRAC_PI_2;
-    /// use echo_math::{Quat, Vec3};    /// // Compose: 90° pitch around X, then 90° yaw around Y
+    /// use echo_math::{Quat, Vec3};
+    /// // Compose: 90° pitch around X, then 90° yaw around Y
     /// let pitch = Quat::from_axis_angle(Vec3::from([1.0, 0.0, 0.0]), FRAC_PI_2);
     /// let yaw = Quat::from_axis_angle(Vec3::from([0.0, 1.0, 0.0]), FRAC_PI_2);
     /// let composed = yaw.multiply(&pitch); // pitch first, then yaw
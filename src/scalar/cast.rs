@@ -0,0 +1,82 @@
+use super::{DeterministicScalar, NondetScalar, Scalar};
+
+/// Lossy conversion into another scalar backend.
+///
+/// Implemented from every deterministic scalar into every non-deterministic
+/// one (the usual simulation → rendering hand-off) and between `f32` and
+/// `f64`. Values are routed through `f64`, so:
+///
+/// * `DFix128` magnitudes above `2^53` lose their low integer bits, and any
+///   fixed-point value keeps at most 53 significant bits;
+/// * casting into `f32` rounds to 24 significant bits.
+///
+/// Going the other way is intentionally not a cast; see [`Quantize`].
+pub trait ScalarCast<U: Scalar>: Scalar {
+    fn cast(self) -> U;
+}
+
+impl<T: DeterministicScalar, U: NondetScalar> ScalarCast<U> for T {
+    fn cast(self) -> U { U::from_f64(self.to_f64()) }
+}
+
+impl ScalarCast<f32> for f32 { fn cast(self) -> f32 { self } }
+impl ScalarCast<f64> for f32 { fn cast(self) -> f64 { f64::from(self) } }
+impl ScalarCast<f32> for f64 { fn cast(self) -> f32 { self as f32 } }
+impl ScalarCast<f64> for f64 { fn cast(self) -> f64 { self } }
+
+/// Explicit conversion from a non-deterministic scalar into a deterministic one.
+///
+/// The conversion itself is bit-exact for a given input, but the input is
+/// usually the result of platform-dependent float math. Quantize only at
+/// well-defined boundaries (authored content, configuration, UI input) so every
+/// peer feeds the simulation the same bits. Fixed-point targets truncate toward
+/// zero, saturate at their range limits and map `NaN` to zero.
+pub trait Quantize<U: DeterministicScalar>: NondetScalar {
+    fn quantize(self) -> U;
+}
+
+impl<T: NondetScalar, U: DeterministicScalar> Quantize<U> for T {
+    fn quantize(self) -> U { U::from_f64(self.to_f64()) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scalar::{DFix128, DFix64, F32Det};
+    use crate::{Point3, Vec3};
+
+    #[test]
+    fn deterministic_to_render_scalars() {
+        let x = DFix64::from_f32(-2.75);
+        let a: f32 = x.cast();
+        let b: f64 = x.cast();
+        assert_eq!(a, -2.75);
+        assert_eq!(b, -2.75);
+        assert_eq!(ScalarCast::<f64>::cast(F32Det(0.1)), f64::from(0.1f32));
+    }
+
+    #[test]
+    fn wide_fixed_point_loses_low_bits_in_f64() {
+        let big = DFix128((1i128 << 60) + 1);
+        let out: f64 = big.cast();
+        assert_eq!(out, (1u64 << 28) as f64);
+    }
+
+    #[test]
+    fn quantize_truncates_and_saturates() {
+        let q: DFix64 = 1.999_999_9f64.quantize();
+        assert_eq!(q, DFix64(131_071));
+        let sat: DFix64 = f64::INFINITY.quantize();
+        assert_eq!(sat, DFix64(i64::MAX));
+        let nan: DFix64 = f32::NAN.quantize();
+        assert_eq!(nan, DFix64(0));
+    }
+
+    #[test]
+    fn vectors_cast_and_quantize_componentwise() {
+        let sim = Point3::new(DFix64::from_f32(1.5), DFix64::ZERO, DFix64::from_f32(-0.25));
+        assert_eq!(sim.cast::<f32>(), Point3::new(1.5, 0.0, -0.25));
+        let authored = Vec3::new(1.5f32, 0.0, -0.25);
+        assert_eq!(authored.quantize::<DFix64>(), sim.0);
+    }
+}
@@ -0,0 +1,96 @@
+use core::fmt;
+use core::ops::{Add, Sub, Mul, Div, Neg};
+use core::str::FromStr;
+
+use super::{fixed, ParseFixedError, RealScalar, Scalar, TrigScalar};
+
+/// Wide Q96.32 fixed-point scalar for large-world coordinates.
+///
+/// Range is roughly `±3.9e28` with a resolution of `2^-32`. Products and
+/// quotients use a 256-bit intermediate, so they never lose precision to the
+/// widening step.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub struct DFix128(pub i128);
+
+const FRAC_BITS: u32 = 32;
+const ONE: i128 = 1 << FRAC_BITS;
+
+impl From<i128> for DFix128 {
+    fn from(value: i128) -> Self {
+        Self(value)
+    }
+}
+
+impl fmt::Display for DFix128 {
+    /// Writes the exact decimal value, e.g. `-1.5`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fixed::write_decimal(self.0, FRAC_BITS, f)
+    }
+}
+
+impl FromStr for DFix128 {
+    type Err = ParseFixedError;
+
+    /// Parses a plain decimal literal, rounding to the nearest representable value.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        fixed::parse_decimal(s, FRAC_BITS).and_then(fixed::narrow).map(Self)
+    }
+}
+
+impl Add for DFix128 { type Output = Self; fn add(self, rhs: Self) -> Self { Self(self.0 + rhs.0) } }
+impl Sub for DFix128 { type Output = Self; fn sub(self, rhs: Self) -> Self { Self(self.0 - rhs.0) } }
+impl Mul for DFix128 { type Output = Self; fn mul(self, rhs: Self) -> Self { Self(fixed::mul_shr_i128(self.0, rhs.0, FRAC_BITS)) } }
+impl Div for DFix128 { type Output = Self; fn div(self, rhs: Self) -> Self { Self(fixed::shl_div_i128(self.0, rhs.0, FRAC_BITS)) } }
+impl Neg for DFix128 { type Output = Self; fn neg(self) -> Self { Self(-self.0) } }
+
+impl Scalar for DFix128 {
+    const ZERO: Self = Self(0);
+    const ONE: Self = Self(ONE);
+    fn epsilon() -> Self { Self(1) }
+    fn from_f32(x: f32) -> Self { Self((x * (ONE as f32)) as i128) }
+    fn to_f32(self) -> f32 { (self.0 as f32) / (ONE as f32) }
+    fn from_f64(x: f64) -> Self { Self((x * (ONE as f64)) as i128) }
+    fn to_f64(self) -> f64 { (self.0 as f64) / (ONE as f64) }
+    fn from_unit_bits(bits: u64) -> Self { Self(i128::from(bits >> (64 - FRAC_BITS))) }
+}
+
+impl RealScalar for DFix128 {
+    fn abs(self) -> Self { Self(self.0.abs()) }
+    fn sqrt(self) -> Self { Self(fixed::sqrt(self.0, FRAC_BITS)) }
+    fn rsqrt(self) -> Self { Self(fixed::rsqrt(self.0, FRAC_BITS)) }
+    fn min(self, other: Self) -> Self { Self(self.0.min(other.0)) }
+    fn max(self, other: Self) -> Self { Self(self.0.max(other.0)) }
+    fn clamp(self, min: Self, max: Self) -> Self { Self(self.0.clamp(min.0, max.0)) }
+    fn floor(self) -> Self { Self(self.0 & !(ONE - 1)) }
+}
+
+impl TrigScalar for DFix128 {
+    fn sin(self) -> Self { Self(fixed::sin(self.0, FRAC_BITS)) }
+    fn cos(self) -> Self { Self(fixed::cos(self.0, FRAC_BITS)) }
+    fn tan(self) -> Self { Self(fixed::tan(self.0, FRAC_BITS)) }
+    fn atan2(self, other: Self) -> Self { Self(fixed::atan2(self.0, other.0, FRAC_BITS)) }
+    fn asin(self) -> Self { Self(fixed::asin(self.0, FRAC_BITS)) }
+    fn acos(self) -> Self { Self(fixed::acos(self.0, FRAC_BITS)) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn products_beyond_64_bits_are_exact() {
+        // 1 AU in meters, squared, does not fit in any 64-bit intermediate.
+        let au = DFix128(149_597_870_700 << FRAC_BITS);
+        let sq = au * au;
+        assert_eq!(sq.0 >> FRAC_BITS, 149_597_870_700i128 * 149_597_870_700);
+        assert_eq!(sq / au, au);
+        assert_eq!(sq.sqrt(), au);
+    }
+
+    #[test]
+    fn negative_products_round_toward_negative_infinity() {
+        let tiny = DFix128(1);
+        assert_eq!((-tiny) * DFix128(ONE / 2), DFix128(-1));
+        assert_eq!(DFix128(-3 * ONE) / DFix128(2 * ONE), DFix128(-3 * ONE / 2));
+    }
+}
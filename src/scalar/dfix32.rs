@@ -0,0 +1,104 @@
+use core::fmt;
+use core::ops::{Add, Sub, Mul, Div, Neg};
+use core::str::FromStr;
+
+use super::{fixed, ParseFixedError, RealScalar, Scalar, TrigScalar};
+
+/// Compact Q16.16 fixed-point scalar for bulk simulations (particles, crowds).
+///
+/// Range is roughly `±32768` with a resolution of `2^-16`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub struct DFix32(pub i32);
+
+const FRAC_BITS: u32 = 16;
+const ONE: i32 = 1 << FRAC_BITS;
+
+impl DFix32 {
+    fn from_wide(raw: i128) -> Self {
+        Self(raw.clamp(i128::from(i32::MIN), i128::from(i32::MAX)) as i32)
+    }
+
+    fn wide(self) -> i128 {
+        i128::from(self.0)
+    }
+}
+
+impl From<i32> for DFix32 {
+    fn from(value: i32) -> Self {
+        Self(value)
+    }
+}
+
+impl fmt::Display for DFix32 {
+    /// Writes the exact decimal value, e.g. `-1.5`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fixed::write_decimal(self.wide(), FRAC_BITS, f)
+    }
+}
+
+impl FromStr for DFix32 {
+    type Err = ParseFixedError;
+
+    /// Parses a plain decimal literal, rounding to the nearest representable value.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        fixed::parse_decimal(s, FRAC_BITS).and_then(fixed::narrow).map(Self)
+    }
+}
+
+impl Add for DFix32 { type Output = Self; fn add(self, rhs: Self) -> Self { Self(self.0 + rhs.0) } }
+impl Sub for DFix32 { type Output = Self; fn sub(self, rhs: Self) -> Self { Self(self.0 - rhs.0) } }
+impl Mul for DFix32 { type Output = Self; fn mul(self, rhs: Self) -> Self { Self(((i64::from(self.0) * i64::from(rhs.0)) >> FRAC_BITS) as i32) } }
+impl Div for DFix32 { type Output = Self; fn div(self, rhs: Self) -> Self { Self(((i64::from(self.0) << FRAC_BITS) / i64::from(rhs.0)) as i32) } }
+impl Neg for DFix32 { type Output = Self; fn neg(self) -> Self { Self(-self.0) } }
+
+impl Scalar for DFix32 {
+    const ZERO: Self = Self(0);
+    const ONE: Self = Self(ONE);
+    fn epsilon() -> Self { Self(1) }
+    fn from_f32(x: f32) -> Self { Self((x * (ONE as f32)) as i32) }
+    fn to_f32(self) -> f32 { (self.0 as f32) / (ONE as f32) }
+    fn from_f64(x: f64) -> Self { Self((x * f64::from(ONE)) as i32) }
+    fn to_f64(self) -> f64 { f64::from(self.0) / f64::from(ONE) }
+    fn from_unit_bits(bits: u64) -> Self { Self((bits >> (64 - FRAC_BITS)) as i32) }
+}
+
+impl RealScalar for DFix32 {
+    fn abs(self) -> Self { Self(self.0.abs()) }
+    fn sqrt(self) -> Self { Self::from_wide(fixed::sqrt(self.wide(), FRAC_BITS)) }
+    fn rsqrt(self) -> Self { Self::from_wide(fixed::rsqrt(self.wide(), FRAC_BITS)) }
+    fn min(self, other: Self) -> Self { Self(self.0.min(other.0)) }
+    fn max(self, other: Self) -> Self { Self(self.0.max(other.0)) }
+    fn clamp(self, min: Self, max: Self) -> Self { Self(self.0.clamp(min.0, max.0)) }
+    fn floor(self) -> Self { Self(self.0 & !(ONE - 1)) }
+}
+
+impl TrigScalar for DFix32 {
+    fn sin(self) -> Self { Self::from_wide(fixed::sin(self.wide(), FRAC_BITS)) }
+    fn cos(self) -> Self { Self::from_wide(fixed::cos(self.wide(), FRAC_BITS)) }
+    fn tan(self) -> Self { Self::from_wide(fixed::tan(self.wide(), FRAC_BITS)) }
+    fn atan2(self, other: Self) -> Self { Self::from_wide(fixed::atan2(self.wide(), other.wide(), FRAC_BITS)) }
+    fn asin(self) -> Self { Self::from_wide(fixed::asin(self.wide(), FRAC_BITS)) }
+    fn acos(self) -> Self { Self::from_wide(fixed::acos(self.wide(), FRAC_BITS)) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arithmetic_round_trips_through_f32() {
+        let a = DFix32::from_f32(1.5);
+        let b = DFix32::from_f32(-2.25);
+        assert_eq!((a * b).to_f32(), -3.375);
+        assert_eq!((b / a).to_f32(), -1.5);
+        assert_eq!(DFix32::from_f32(6.25).sqrt().to_f32(), 2.5);
+    }
+
+    #[test]
+    fn trig_stays_within_resolution() {
+        let half_pi = DFix32::from_f32(core::f32::consts::FRAC_PI_2);
+        assert!((half_pi.sin().to_f32() - 1.0).abs() <= 2.0 / ONE as f32);
+        assert!(half_pi.cos().to_f32().abs() <= 2.0 / ONE as f32);
+        assert_eq!(DFix32::ONE.atan2(DFix32::ZERO), half_pi.asin());
+    }
+}
@@ -1,11 +1,27 @@
-use crate::scalar::traits::*;
+use core::fmt;
+use core::ops::{Add, Sub, Mul, Div, Neg};
+use core::str::FromStr;
+
+use super::{fixed, ParseFixedError, RealScalar, Scalar, TrigScalar};
 
-// Placeholder for deterministic fixed-point 64-bit
-#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Default)]
+/// Q48.16 fixed-point scalar, the default deterministic backend.
+///
+/// Range is roughly `±1.4e14` with a resolution of `2^-16`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Default)]
 pub struct DFix64(pub i64);
 
-const FIXED_POINT_SHIFT: i64 = 16;
-const FIXED_POINT_ONE: i64 = 1 << FIXED_POINT_SHIFT;
+const FRAC_BITS: u32 = 16;
+const ONE: i64 = 1 << FRAC_BITS;
+
+impl DFix64 {
+    fn from_wide(raw: i128) -> Self {
+        Self(raw.clamp(i128::from(i64::MIN), i128::from(i64::MAX)) as i64)
+    }
+
+    fn wide(self) -> i128 {
+        i128::from(self.0)
+    }
+}
 
 impl From<i64> for DFix64 {
     fn from(value: i64) -> Self {
@@ -13,36 +29,54 @@ impl From<i64> for DFix64 {
     }
 }
 
+impl fmt::Display for DFix64 {
+    /// Writes the exact decimal value, e.g. `-1.5`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fixed::write_decimal(self.wide(), FRAC_BITS, f)
+    }
+}
+
+impl FromStr for DFix64 {
+    type Err = ParseFixedError;
+
+    /// Parses a plain decimal literal, rounding to the nearest representable value.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        fixed::parse_decimal(s, FRAC_BITS).and_then(fixed::narrow).map(Self)
+    }
+}
+
+impl Add for DFix64 { type Output = Self; fn add(self, rhs: Self) -> Self { Self(self.0 + rhs.0) } }
+impl Sub for DFix64 { type Output = Self; fn sub(self, rhs: Self) -> Self { Self(self.0 - rhs.0) } }
+impl Mul for DFix64 { type Output = Self; fn mul(self, rhs: Self) -> Self { Self(((self.wide() * rhs.wide()) >> FRAC_BITS) as i64) } }
+impl Div for DFix64 { type Output = Self; fn div(self, rhs: Self) -> Self { Self(((self.wide() << FRAC_BITS) / rhs.wide()) as i64) } }
+impl Neg for DFix64 { type Output = Self; fn neg(self) -> Self { Self(-self.0) } }
+
 impl Scalar for DFix64 {
     const ZERO: Self = Self(0);
-    const ONE: Self = Self(FIXED_POINT_ONE);
+    const ONE: Self = Self(ONE);
     fn epsilon() -> Self { Self(1) }
-    fn from_f32(x: f32) -> Self { Self((x * (FIXED_POINT_ONE as f32)) as i64) }
-    fn to_f32(self) -> f32 { (self.0 as f32) / (FIXED_POINT_ONE as f32) }
+    fn from_f32(x: f32) -> Self { Self((x * (ONE as f32)) as i64) }
+    fn to_f32(self) -> f32 { (self.0 as f32) / (ONE as f32) }
+    fn from_f64(x: f64) -> Self { Self((x * (ONE as f64)) as i64) }
+    fn to_f64(self) -> f64 { (self.0 as f64) / (ONE as f64) }
+    fn from_unit_bits(bits: u64) -> Self { Self((bits >> (64 - FRAC_BITS)) as i64) }
 }
 
 impl RealScalar for DFix64 {
     fn abs(self) -> Self { Self(self.0.abs()) }
-    fn sqrt(self) -> Self { Self(0) } // Placeholder
-    fn rsqrt(self) -> Self { Self(0) } // Placeholder
+    fn sqrt(self) -> Self { Self::from_wide(fixed::sqrt(self.wide(), FRAC_BITS)) }
+    fn rsqrt(self) -> Self { Self::from_wide(fixed::rsqrt(self.wide(), FRAC_BITS)) }
     fn min(self, other: Self) -> Self { Self(self.0.min(other.0)) }
     fn max(self, other: Self) -> Self { Self(self.0.max(other.0)) }
     fn clamp(self, min: Self, max: Self) -> Self { Self(self.0.clamp(min.0, max.0)) }
+    fn floor(self) -> Self { Self(self.0 & !(ONE - 1)) }
 }
 
 impl TrigScalar for DFix64 {
-    fn sin(self) -> Self { Self(0) } // Placeholder
-    fn cos(self) -> Self { Self(0) } // Placeholder
-    fn tan(self) -> Self { Self(0) } // Placeholder
-    fn atan2(self, _other: Self) -> Self { Self(0) } // Placeholder
-    fn asin(self) -> Self { Self(0) } // Placeholder
-    fn acos(self) -> Self { Self(0) } // Placeholder
+    fn sin(self) -> Self { Self::from_wide(fixed::sin(self.wide(), FRAC_BITS)) }
+    fn cos(self) -> Self { Self::from_wide(fixed::cos(self.wide(), FRAC_BITS)) }
+    fn tan(self) -> Self { Self::from_wide(fixed::tan(self.wide(), FRAC_BITS)) }
+    fn atan2(self, other: Self) -> Self { Self::from_wide(fixed::atan2(self.wide(), other.wide(), FRAC_BITS)) }
+    fn asin(self) -> Self { Self::from_wide(fixed::asin(self.wide(), FRAC_BITS)) }
+    fn acos(self) -> Self { Self::from_wide(fixed::acos(self.wide(), FRAC_BITS)) }
 }
-
-// Basic ops
-use core::ops::{Add, Sub, Mul, Div, Neg};
-impl Add for DFix64 { type Output = Self; fn add(self, rhs: Self) -> Self { Self(self.0 + rhs.0) } }
-impl Sub for DFix64 { type Output = Self; fn sub(self, rhs: Self) -> Self { Self(self.0 - rhs.0) } }
-impl Mul for DFix64 { type Output = Self; fn mul(self, rhs: Self) -> Self { Self((self.0 * rhs.0) >> FIXED_POINT_SHIFT) } }
-impl Div for DFix64 { type Output = Self; fn div(self, rhs: Self) -> Self { Self((self.0 << FIXED_POINT_SHIFT) / rhs.0) } }
-impl Neg for DFix64 { type Output = Self; fn neg(self) -> Self { Self(-self.0) } }
\ No newline at end of file
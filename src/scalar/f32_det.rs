@@ -1,7 +1,13 @@
-use crate::scalar::traits::*;
+use core::ops::{Add, Sub, Mul, Div, Neg};
+
+use super::{RealScalar, Scalar, TrigScalar};
 
-// Placeholder for deterministic f32 wrapper
-#[derive(Debug, Copy, Clone, PartialEq, PartialOrd, Default)]
+/// Deterministic `f32` wrapper.
+///
+/// Arithmetic and `sqrt` are correctly rounded IEEE-754 operations and
+/// therefore bit-exact everywhere; trig delegates to the platform math library.
+#[derive(Copy, Clone, Debug, PartialEq, PartialOrd, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(transparent))]
 pub struct F32Det(pub f32);
 
 impl From<f32> for F32Det {
@@ -10,36 +16,38 @@ impl From<f32> for F32Det {
     }
 }
 
+impl Add for F32Det { type Output = Self; fn add(self, rhs: Self) -> Self { Self(self.0 + rhs.0) } }
+impl Sub for F32Det { type Output = Self; fn sub(self, rhs: Self) -> Self { Self(self.0 - rhs.0) } }
+impl Mul for F32Det { type Output = Self; fn mul(self, rhs: Self) -> Self { Self(self.0 * rhs.0) } }
+impl Div for F32Det { type Output = Self; fn div(self, rhs: Self) -> Self { Self(self.0 / rhs.0) } }
+impl Neg for F32Det { type Output = Self; fn neg(self) -> Self { Self(-self.0) } }
+
 impl Scalar for F32Det {
     const ZERO: Self = Self(0.0);
     const ONE: Self = Self(1.0);
     fn epsilon() -> Self { Self(f32::EPSILON) }
     fn from_f32(x: f32) -> Self { Self(x) }
     fn to_f32(self) -> f32 { self.0 }
+    fn from_f64(x: f64) -> Self { Self(x as f32) }
+    fn to_f64(self) -> f64 { f64::from(self.0) }
+    fn from_unit_bits(bits: u64) -> Self { Self(f32::from_unit_bits(bits)) }
 }
 
 impl RealScalar for F32Det {
     fn abs(self) -> Self { Self(self.0.abs()) }
-    fn sqrt(self) -> Self { Self(self.0.sqrt()) } // Not deterministic
-    fn rsqrt(self) -> Self { Self(1.0 / self.0.sqrt()) } // Not deterministic
+    fn sqrt(self) -> Self { Self(self.0.sqrt()) }
+    fn rsqrt(self) -> Self { Self(1.0 / self.0.sqrt()) }
     fn min(self, other: Self) -> Self { Self(self.0.min(other.0)) }
     fn max(self, other: Self) -> Self { Self(self.0.max(other.0)) }
     fn clamp(self, min: Self, max: Self) -> Self { Self(self.0.clamp(min.0, max.0)) }
+    fn floor(self) -> Self { Self(self.0.floor()) }
 }
 
 impl TrigScalar for F32Det {
-    fn sin(self) -> Self { Self(self.0.sin()) } // Not deterministic
-    fn cos(self) -> Self { Self(self.0.cos()) } // Not deterministic
-    fn tan(self) -> Self { Self(self.0.tan()) } // Not deterministic
-    fn atan2(self, other: Self) -> Self { Self(self.0.atan2(other.0)) } // Not deterministic
-    fn asin(self) -> Self { Self(self.0.asin()) } // Not deterministic
-    fn acos(self) -> Self { Self(self.0.acos()) } // Not deterministic
+    fn sin(self) -> Self { Self(self.0.sin()) }
+    fn cos(self) -> Self { Self(self.0.cos()) }
+    fn tan(self) -> Self { Self(self.0.tan()) }
+    fn atan2(self, other: Self) -> Self { Self(self.0.atan2(other.0)) }
+    fn asin(self) -> Self { Self(self.0.asin()) }
+    fn acos(self) -> Self { Self(self.0.acos()) }
 }
-
-// Basic ops
-use core::ops::{Add, Sub, Mul, Div, Neg};
-impl Add for F32Det { type Output = Self; fn add(self, rhs: Self) -> Self { Self(self.0 + rhs.0) } }
-impl Sub for F32Det { type Output = Self; fn sub(self, rhs: Self) -> Self { Self(self.0 - rhs.0) } }
-impl Mul for F32Det { type Output = Self; fn mul(self, rhs: Self) -> Self { Self(self.0 * rhs.0) } }
-impl Div for F32Det { type Output = Self; fn div(self, rhs: Self) -> Self { Self(self.0 / rhs.0) } }
-impl Neg for F32Det { type Output = Self; fn neg(self) -> Self { Self(-self.0) } }
\ No newline at end of file
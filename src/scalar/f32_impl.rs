@@ -1,4 +1,4 @@
-use super::traits::*;
+use super::{RealScalar, Scalar, TrigScalar};
 
 impl Scalar for f32 {
     const ZERO: Self = 0.0;
@@ -8,6 +8,11 @@ impl Scalar for f32 {
 
     fn from_f32(x: f32) -> Self { x }
     fn to_f32(self) -> f32 { self }
+
+    fn from_f64(x: f64) -> Self { x as f32 }
+    fn to_f64(self) -> f64 { f64::from(self) }
+
+    fn from_unit_bits(bits: u64) -> Self { f32::from_bits(((bits >> 41) as u32) | 0x3f80_0000) - 1.0 }
 }
 
 impl RealScalar for f32 {
@@ -17,6 +22,7 @@ impl RealScalar for f32 {
     fn min(self, other: Self) -> Self { f32::min(self, other) }
     fn max(self, other: Self) -> Self { f32::max(self, other) }
     fn clamp(self, min: Self, max: Self) -> Self { f32::clamp(self, min, max) }
+    fn floor(self) -> Self { f32::floor(self) }
 }
 
 impl TrigScalar for f32 {
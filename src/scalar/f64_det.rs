@@ -0,0 +1,53 @@
+use core::ops::{Add, Sub, Mul, Div, Neg};
+
+use super::{RealScalar, Scalar, TrigScalar};
+
+/// Deterministic `f64` wrapper, the double-precision sibling of `F32Det`.
+///
+/// Arithmetic and `sqrt` are correctly rounded IEEE-754 operations and
+/// therefore bit-exact everywhere; trig delegates to the platform math library.
+#[derive(Copy, Clone, Debug, PartialEq, PartialOrd, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(transparent))]
+pub struct F64Det(pub f64);
+
+impl From<f64> for F64Det {
+    fn from(value: f64) -> Self {
+        Self(value)
+    }
+}
+
+impl Add for F64Det { type Output = Self; fn add(self, rhs: Self) -> Self { Self(self.0 + rhs.0) } }
+impl Sub for F64Det { type Output = Self; fn sub(self, rhs: Self) -> Self { Self(self.0 - rhs.0) } }
+impl Mul for F64Det { type Output = Self; fn mul(self, rhs: Self) -> Self { Self(self.0 * rhs.0) } }
+impl Div for F64Det { type Output = Self; fn div(self, rhs: Self) -> Self { Self(self.0 / rhs.0) } }
+impl Neg for F64Det { type Output = Self; fn neg(self) -> Self { Self(-self.0) } }
+
+impl Scalar for F64Det {
+    const ZERO: Self = Self(0.0);
+    const ONE: Self = Self(1.0);
+    fn epsilon() -> Self { Self(f64::EPSILON) }
+    fn from_f32(x: f32) -> Self { Self(f64::from(x)) }
+    fn to_f32(self) -> f32 { self.0 as f32 }
+    fn from_f64(x: f64) -> Self { Self(x) }
+    fn to_f64(self) -> f64 { self.0 }
+    fn from_unit_bits(bits: u64) -> Self { Self(f64::from_unit_bits(bits)) }
+}
+
+impl RealScalar for F64Det {
+    fn abs(self) -> Self { Self(self.0.abs()) }
+    fn sqrt(self) -> Self { Self(self.0.sqrt()) }
+    fn rsqrt(self) -> Self { Self(1.0 / self.0.sqrt()) }
+    fn min(self, other: Self) -> Self { Self(self.0.min(other.0)) }
+    fn max(self, other: Self) -> Self { Self(self.0.max(other.0)) }
+    fn clamp(self, min: Self, max: Self) -> Self { Self(self.0.clamp(min.0, max.0)) }
+    fn floor(self) -> Self { Self(self.0.floor()) }
+}
+
+impl TrigScalar for F64Det {
+    fn sin(self) -> Self { Self(self.0.sin()) }
+    fn cos(self) -> Self { Self(self.0.cos()) }
+    fn tan(self) -> Self { Self(self.0.tan()) }
+    fn atan2(self, other: Self) -> Self { Self(self.0.atan2(other.0)) }
+    fn asin(self) -> Self { Self(self.0.asin()) }
+    fn acos(self) -> Self { Self(self.0.acos()) }
+}
@@ -0,0 +1,35 @@
+use super::{RealScalar, Scalar, TrigScalar};
+
+impl Scalar for f64 {
+    const ZERO: Self = 0.0;
+    const ONE: Self = 1.0;
+
+    fn epsilon() -> Self { f64::EPSILON }
+
+    fn from_f32(x: f32) -> Self { f64::from(x) }
+    fn to_f32(self) -> f32 { self as f32 }
+
+    fn from_f64(x: f64) -> Self { x }
+    fn to_f64(self) -> f64 { self }
+
+    fn from_unit_bits(bits: u64) -> Self { f64::from_bits((bits >> 12) | 0x3ff0_0000_0000_0000) - 1.0 }
+}
+
+impl RealScalar for f64 {
+    fn abs(self) -> Self { f64::abs(self) }
+    fn sqrt(self) -> Self { f64::sqrt(self) }
+    fn rsqrt(self) -> Self { 1.0 / f64::sqrt(self) }
+    fn min(self, other: Self) -> Self { f64::min(self, other) }
+    fn max(self, other: Self) -> Self { f64::max(self, other) }
+    fn clamp(self, min: Self, max: Self) -> Self { f64::clamp(self, min, max) }
+    fn floor(self) -> Self { f64::floor(self) }
+}
+
+impl TrigScalar for f64 {
+    fn sin(self) -> Self { f64::sin(self) }
+    fn cos(self) -> Self { f64::cos(self) }
+    fn tan(self) -> Self { f64::tan(self) }
+    fn atan2(self, other: Self) -> Self { f64::atan2(self, other) }
+    fn asin(self) -> Self { f64::asin(self) }
+    fn acos(self) -> Self { f64::acos(self) }
+}
@@ -0,0 +1,480 @@
+//! Integer kernels shared by the fixed-point backends (`DFix32`, `DFix64`,
+//! `DFix128`).
+//!
+//! Every routine works on raw two's-complement values widened to `i128` plus
+//! the number of fractional bits of the calling type. Transcendentals are
+//! evaluated in a common Q32.32 working format, so all fixed-point widths run
+//! the exact same integer instruction sequence and produce bit-identical
+//! results on every platform. No floating point is involved anywhere.
+
+/// Fractional bits of the internal working format.
+const WORK_FRAC: u32 = 32;
+const WORK_ONE: i64 = 1 << WORK_FRAC;
+
+// Q32.32 constants, rounded to nearest.
+const PI: i64 = 13_493_037_705;
+const FRAC_PI_2: i64 = 6_746_518_852;
+const FRAC_PI_4: i64 = 3_373_259_426;
+const FRAC_PI_6: i64 = 2_248_839_617;
+const TAU: i64 = 26_986_075_409;
+const SQRT_3: i64 = 7_439_101_574;
+/// `tan(π/12) = 2 - √3`, the threshold for the atan argument reduction.
+const TAN_PI_12: i64 = 1_150_833_018;
+
+/// Taylor coefficients of `sin(x)/x` in `x²`, highest order first.
+const SIN_COEFFS: [i64; 6] = [-108, 11_836, -852_176, 35_791_394, -715_827_883, WORK_ONE];
+/// Taylor coefficients of `cos(x)` in `x²`, highest order first.
+const COS_COEFFS: [i64; 7] = [9, -1_184, 106_522, -5_965_232, 178_956_971, -2_147_483_648, WORK_ONE];
+/// Taylor coefficients of `atan(x)/x` in `x²`, highest order first.
+const ATAN_COEFFS: [i64; 8] = [
+    -286_331_153,
+    330_382_100,
+    -390_451_572,
+    477_218_588,
+    -613_566_757,
+    858_993_459,
+    -1_431_655_765,
+    WORK_ONE,
+];
+
+/// Rounded Q32.32 product.
+fn mul(a: i64, b: i64) -> i64 {
+    ((i128::from(a) * i128::from(b) + (1 << (WORK_FRAC - 1))) >> WORK_FRAC) as i64
+}
+
+/// Truncating Q32.32 quotient; `b` must be non-zero.
+fn div(a: i64, b: i64) -> i64 {
+    ((i128::from(a) << WORK_FRAC) / i128::from(b)) as i64
+}
+
+/// Evaluates `x * p(x²)` (odd) or `p(x²)` (even) with Horner's scheme.
+fn horner(coeffs: &[i64], x2: i64) -> i64 {
+    coeffs[1..].iter().fold(coeffs[0], |acc, &c| c + mul(acc, x2))
+}
+
+/// Converts a raw value with `frac` fractional bits (`frac <= 32`) to Q32.32.
+fn to_work(raw: i128, frac: u32) -> i128 {
+    raw << (WORK_FRAC - frac)
+}
+
+/// Converts a Q32.32 value back to `frac` fractional bits, rounding to nearest.
+fn from_work(work: i128, frac: u32) -> i128 {
+    let shift = WORK_FRAC - frac;
+    if shift == 0 {
+        work
+    } else {
+        (work + (1 << (shift - 1))) >> shift
+    }
+}
+
+/// Floor square root of a `u128`, computed digit by digit.
+pub(crate) fn isqrt(n: u128) -> u128 {
+    if n < 2 {
+        return n;
+    }
+    let mut rem = n;
+    let mut root = 0u128;
+    let mut bit = 1u128 << ((127 - n.leading_zeros()) & !1);
+    while bit != 0 {
+        if rem >= root + bit {
+            rem -= root + bit;
+            root = (root >> 1) + bit;
+        } else {
+            root >>= 1;
+        }
+        bit >>= 2;
+    }
+    root
+}
+
+/// Floor square root of a raw fixed-point value. Non-positive inputs yield zero.
+///
+/// For very large `DFix128` inputs the pre-shift is reduced so the radicand
+/// stays within 128 bits; the result then loses low fractional bits but keeps
+/// at least 64 significant bits.
+pub(crate) fn sqrt(raw: i128, frac: u32) -> i128 {
+    if raw <= 0 {
+        return 0;
+    }
+    let r = raw as u128;
+    let lz = r.leading_zeros();
+    if lz >= frac {
+        isqrt(r << frac) as i128
+    } else {
+        let k = lz & !1;
+        (isqrt(r << k) << ((frac - k) / 2)) as i128
+    }
+}
+
+/// `1 / sqrt(x)` on raw values. Non-positive inputs saturate to `i128::MAX`;
+/// callers clamp to their own range.
+pub(crate) fn rsqrt(raw: i128, frac: u32) -> i128 {
+    let root = sqrt(raw, frac);
+    if root == 0 {
+        i128::MAX
+    } else {
+        (1i128 << (2 * frac)) / root
+    }
+}
+
+/// Returns `(sin(a), cos(a))` for a Q32.32 angle in radians.
+///
+/// The angle is reduced modulo a Q32.32 approximation of `2π`, so huge angles
+/// accumulate the representation error of that constant.
+fn sin_cos_work(angle: i128) -> (i64, i64) {
+    let a = angle.rem_euclid(i128::from(TAU)) as i64;
+    let quadrant = (a + FRAC_PI_4) / FRAC_PI_2;
+    let r = a - quadrant * FRAC_PI_2;
+    let r2 = mul(r, r);
+    let s = mul(r, horner(&SIN_COEFFS, r2));
+    let c = horner(&COS_COEFFS, r2);
+    match quadrant & 3 {
+        0 => (s, c),
+        1 => (c, -s),
+        2 => (-s, -c),
+        _ => (-c, s),
+    }
+}
+
+pub(crate) fn sin(raw: i128, frac: u32) -> i128 {
+    from_work(i128::from(sin_cos_work(to_work(raw, frac)).0), frac)
+}
+
+pub(crate) fn cos(raw: i128, frac: u32) -> i128 {
+    from_work(i128::from(sin_cos_work(to_work(raw, frac)).1), frac)
+}
+
+/// Tangent on raw values. Poles saturate to `i128::MAX`/`i128::MIN`; callers
+/// clamp to their own range.
+pub(crate) fn tan(raw: i128, frac: u32) -> i128 {
+    let (s, c) = sin_cos_work(to_work(raw, frac));
+    if c == 0 {
+        return if s >= 0 { i128::MAX } else { i128::MIN };
+    }
+    from_work((i128::from(s) << WORK_FRAC) / i128::from(c), frac)
+}
+
+/// `atan(z)` for a Q32.32 `z` in `[0, 1]`.
+fn atan_unit(z: i64) -> i64 {
+    let (base, t) = if z > TAN_PI_12 {
+        // atan(z) = π/6 + atan((√3·z − 1) / (√3 + z)), |t| ≤ 2 − √3.
+        (FRAC_PI_6, div(mul(SQRT_3, z) - WORK_ONE, SQRT_3 + z))
+    } else {
+        (0, z)
+    };
+    base + mul(t, horner(&ATAN_COEFFS, mul(t, t)))
+}
+
+/// Four-quadrant arctangent as a Q32.32 angle. `y` and `x` only need to share
+/// a scale, so raw values of any width can be passed directly.
+fn atan2_work(y: i128, x: i128) -> i64 {
+    if y == 0 && x == 0 {
+        return 0;
+    }
+    let mut ay = y.unsigned_abs();
+    let mut ax = x.unsigned_abs();
+    // Keep the Q32.32 ratio computation within 128 bits.
+    let excess = (128 - ay.max(ax).leading_zeros()).saturating_sub(94);
+    ay >>= excess;
+    ax >>= excess;
+    let angle = if ay <= ax {
+        atan_unit(((ay << WORK_FRAC) / ax) as i64)
+    } else {
+        FRAC_PI_2 - atan_unit(((ax << WORK_FRAC) / ay) as i64)
+    };
+    let angle = if x < 0 { PI - angle } else { angle };
+    if y < 0 {
+        -angle
+    } else {
+        angle
+    }
+}
+
+pub(crate) fn atan2(y: i128, x: i128, frac: u32) -> i128 {
+    from_work(i128::from(atan2_work(y, x)), frac)
+}
+
+/// `(x, sqrt(1 - x²))` in Q32.32 with `x` clamped to `[-1, 1]`.
+fn unit_leg(raw: i128, frac: u32) -> (i128, i128) {
+    let one = 1i128 << frac;
+    let x = to_work(raw.clamp(-one, one), frac) as i64;
+    let leg = sqrt(i128::from(WORK_ONE - mul(x, x)), WORK_FRAC);
+    (i128::from(x), leg)
+}
+
+/// Arcsine on raw values; inputs outside `[-1, 1]` are clamped.
+pub(crate) fn asin(raw: i128, frac: u32) -> i128 {
+    let (x, leg) = unit_leg(raw, frac);
+    from_work(i128::from(atan2_work(x, leg)), frac)
+}
+
+/// Arccosine on raw values; inputs outside `[-1, 1]` are clamped.
+pub(crate) fn acos(raw: i128, frac: u32) -> i128 {
+    let (x, leg) = unit_leg(raw, frac);
+    from_work(i128::from(atan2_work(leg, x)), frac)
+}
+
+/// Signed `(a * b) >> shift` with a 256-bit intermediate, rounding toward
+/// negative infinity like an arithmetic shift. Bits above 128 are discarded.
+pub(crate) fn mul_shr_i128(a: i128, b: i128, shift: u32) -> i128 {
+    let negative = (a < 0) != (b < 0);
+    let (hi, lo) = mul_u128(a.unsigned_abs(), b.unsigned_abs());
+    let mag = (lo >> shift) | (hi << (128 - shift));
+    let dropped = lo & ((1u128 << shift) - 1);
+    if negative {
+        let mag = (mag as i128).wrapping_neg();
+        if dropped != 0 {
+            mag.wrapping_sub(1)
+        } else {
+            mag
+        }
+    } else {
+        mag as i128
+    }
+}
+
+/// Signed `(a << shift) / b` with a 256-bit intermediate, truncating toward
+/// zero like integer division. Quotient bits above 128 are discarded.
+///
+/// # Panics
+/// Panics if `b` is zero.
+pub(crate) fn shl_div_i128(a: i128, b: i128, shift: u32) -> i128 {
+    assert!(b != 0, "attempt to divide by zero");
+    let negative = (a < 0) != (b < 0);
+    let n = a.unsigned_abs();
+    let d = b.unsigned_abs();
+    let hi = (n >> (128 - shift)) % d;
+    let lo = n << shift;
+    let mut rem = hi;
+    let mut quot = 0u128;
+    for i in (0..128).rev() {
+        // `rem < d <= 2^127`, so the shift cannot overflow.
+        rem = (rem << 1) | ((lo >> i) & 1);
+        quot <<= 1;
+        if rem >= d {
+            rem -= d;
+            quot |= 1;
+        }
+    }
+    if negative {
+        (quot as i128).wrapping_neg()
+    } else {
+        quot as i128
+    }
+}
+
+/// Error returned when parsing a fixed-point scalar from a decimal string.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ParseFixedError {
+    kind: ParseFixedErrorKind,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum ParseFixedErrorKind {
+    Invalid,
+    Overflow,
+}
+
+impl core::fmt::Display for ParseFixedError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self.kind {
+            ParseFixedErrorKind::Invalid => f.write_str("invalid fixed-point literal"),
+            ParseFixedErrorKind::Overflow => f.write_str("fixed-point literal out of range"),
+        }
+    }
+}
+
+impl std::error::Error for ParseFixedError {}
+
+/// Writes the exact decimal expansion of a raw fixed-point value.
+///
+/// Every value with `frac` fractional bits has a terminating expansion of at
+/// most `frac` digits, so the output round-trips through [`parse_decimal`].
+pub(crate) fn write_decimal(raw: i128, frac: u32, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    use core::fmt::Write;
+
+    let mag = raw.unsigned_abs();
+    let mask = (1u128 << frac) - 1;
+    if raw < 0 {
+        f.write_char('-')?;
+    }
+    write!(f, "{}", mag >> frac)?;
+    let mut rem = mag & mask;
+    if rem != 0 {
+        f.write_char('.')?;
+        while rem != 0 {
+            rem *= 10;
+            f.write_char(char::from(b'0' + (rem >> frac) as u8))?;
+            rem &= mask;
+        }
+    }
+    Ok(())
+}
+
+/// Parses `[-+]digits[.digits]` into a raw value with `frac` fractional bits,
+/// rounding to nearest. Fraction digits beyond the 38th are ignored.
+///
+/// The caller narrows the result to its storage width.
+pub(crate) fn parse_decimal(s: &str, frac: u32) -> Result<i128, ParseFixedError> {
+    const INVALID: ParseFixedError = ParseFixedError { kind: ParseFixedErrorKind::Invalid };
+    const OVERFLOW: ParseFixedError = ParseFixedError { kind: ParseFixedErrorKind::Overflow };
+
+    let (negative, body) = match s.as_bytes().first() {
+        Some(b'-') => (true, &s[1..]),
+        Some(b'+') => (false, &s[1..]),
+        _ => (false, s),
+    };
+    let (int_part, frac_part) = match body.find('.') {
+        Some(dot) => (&body[..dot], &body[dot + 1..]),
+        None => (body, ""),
+    };
+    if int_part.is_empty() && frac_part.is_empty() {
+        return Err(INVALID);
+    }
+    let digits = |part: &str| part.bytes().all(|b| b.is_ascii_digit());
+    if !digits(int_part) || !digits(frac_part) {
+        return Err(INVALID);
+    }
+
+    let mut int = 0u128;
+    for b in int_part.bytes() {
+        int = int
+            .checked_mul(10)
+            .and_then(|v| v.checked_add(u128::from(b - b'0')))
+            .ok_or(OVERFLOW)?;
+    }
+    let mut num = 0i128;
+    let mut den = 1i128;
+    for b in frac_part.bytes().take(38) {
+        num = num * 10 + i128::from(b - b'0');
+        den *= 10;
+    }
+    // Round half up: floor(2x) + 1, halved.
+    let frac_raw = (shl_div_i128(num, den, frac + 1) + 1) >> 1;
+
+    let mag = int
+        .checked_mul(1 << frac)
+        .and_then(|v| v.checked_add(frac_raw as u128))
+        .ok_or(OVERFLOW)?;
+    if negative {
+        if mag > i128::MIN.unsigned_abs() {
+            return Err(OVERFLOW);
+        }
+        Ok((mag as i128).wrapping_neg())
+    } else {
+        i128::try_from(mag).map_err(|_| OVERFLOW)
+    }
+}
+
+/// Narrows a parsed raw value, reporting overflow instead of saturating.
+pub(crate) fn narrow<T: TryFrom<i128>>(raw: i128) -> Result<T, ParseFixedError> {
+    T::try_from(raw).map_err(|_| ParseFixedError { kind: ParseFixedErrorKind::Overflow })
+}
+
+/// Full 128×128 → 256-bit unsigned product as `(hi, lo)`.
+fn mul_u128(a: u128, b: u128) -> (u128, u128) {
+    const MASK: u128 = u64::MAX as u128;
+    let (a1, a0) = (a >> 64, a & MASK);
+    let (b1, b0) = (b >> 64, b & MASK);
+    let p00 = a0 * b0;
+    let p01 = a0 * b1;
+    let p10 = a1 * b0;
+    let p11 = a1 * b1;
+    let mid = (p00 >> 64) + (p01 & MASK) + (p10 & MASK);
+    let lo = (p00 & MASK) | (mid << 64);
+    let hi = p11 + (p01 >> 64) + (p10 >> 64) + (mid >> 64);
+    (hi, lo)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn to_f64(work: i128) -> f64 {
+        work as f64 / WORK_ONE as f64
+    }
+
+    fn from_f64(x: f64) -> i128 {
+        (x * WORK_ONE as f64).round() as i128
+    }
+
+    #[test]
+    fn isqrt_is_floor_root() {
+        for n in [0u128, 1, 2, 3, 4, 15, 16, 17, 1 << 64, u128::MAX] {
+            let r = isqrt(n);
+            assert!(r * r <= n);
+            assert!((r + 1).checked_mul(r + 1).map_or(true, |sq| sq > n));
+        }
+    }
+
+    #[test]
+    fn sin_cos_track_reference() {
+        for i in -2_000..=2_000 {
+            let x = f64::from(i) * 0.005;
+            let raw = from_f64(x);
+            assert!((to_f64(sin(raw, 32)) - x.sin()).abs() < 1e-8, "sin({x})");
+            assert!((to_f64(cos(raw, 32)) - x.cos()).abs() < 1e-8, "cos({x})");
+        }
+    }
+
+    #[test]
+    fn atan2_covers_all_quadrants() {
+        for i in 0..360 {
+            let t = f64::from(i).to_radians();
+            let (y, x) = (t.sin() * 3.0, t.cos() * 3.0);
+            let got = to_f64(atan2(from_f64(y), from_f64(x), 32));
+            assert!((got - y.atan2(x)).abs() < 1e-8, "atan2({y}, {x})");
+        }
+        assert_eq!(atan2(0, 0, 32), 0);
+    }
+
+    #[test]
+    fn asin_acos_clamp_out_of_range() {
+        let one = 1i128 << 32;
+        assert_eq!(asin(2 * one, 32), asin(one, 32));
+        assert_eq!(acos(-2 * one, 32), acos(-one, 32));
+        assert!((to_f64(acos(-one, 32)) - core::f64::consts::PI).abs() < 1e-8);
+    }
+
+    struct Decimal(i128, u32);
+
+    impl core::fmt::Display for Decimal {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            write_decimal(self.0, self.1, f)
+        }
+    }
+
+    #[test]
+    fn decimal_round_trips_exactly() {
+        for &raw in &[0i128, 1, -1, 98_304, -98_304, i128::from(i64::MAX), i128::from(i64::MIN), i128::MIN, i128::MAX] {
+            for frac in [16, 32] {
+                let text = Decimal(raw, frac).to_string();
+                assert_eq!(parse_decimal(&text, frac), Ok(raw), "{text}");
+            }
+        }
+        assert_eq!(Decimal(-98_304, 16).to_string(), "-1.5");
+        assert_eq!(Decimal(3 << 16, 16).to_string(), "3");
+    }
+
+    #[test]
+    fn decimal_parse_rounds_and_rejects() {
+        assert_eq!(parse_decimal("0.00001", 16), Ok(1));
+        assert_eq!(parse_decimal("+.5", 16), Ok(1 << 15));
+        assert_eq!(parse_decimal("7.", 16), Ok(7 << 16));
+        for bad in ["", "-", ".", "1.2.3", "1e5", " 1", "--1", "0x10"] {
+            assert!(parse_decimal(bad, 16).is_err(), "{bad:?}");
+        }
+        assert!(parse_decimal("99999999999999999999999999999999999999999", 16).is_err());
+    }
+
+    #[test]
+    fn wide_mul_div_match_narrow_arithmetic() {
+        for &(a, b) in &[(3i128 << 32, 5i128 << 32), (-7 << 31, 3 << 30), (-1, 1), (12_345, -678_901)] {
+            assert_eq!(mul_shr_i128(a, b, 32), (a * b) >> 32);
+            assert_eq!(shl_div_i128(a, b, 32), (a << 32) / b);
+        }
+        let big = 1i128 << 100;
+        assert_eq!(mul_shr_i128(big, 1 << 40, 32), big << 8);
+        assert_eq!(shl_div_i128(big, 1 << 40, 32), big >> 8);
+    }
+}
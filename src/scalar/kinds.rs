@@ -1,14 +1,17 @@
-use super::traits::TrigScalar;
+use super::{DFix128, DFix32, DFix64, F32Det, F64Det, TrigScalar};
 
+/// Scalars whose results are reproducible bit-for-bit across peers.
 pub trait DeterministicScalar: TrigScalar {}
-pub trait NondetScalar: TrigScalar {}
 
-// implementations
-// These will be fleshed out later.
-use crate::scalar::f32_det::F32Det;
-use crate::scalar::dfix64::DFix64;
+/// Scalars backed by platform floating point; fine for rendering, never for
+/// simulation state.
+pub trait NondetScalar: TrigScalar {}
 
 impl DeterministicScalar for F32Det {}
+impl DeterministicScalar for F64Det {}
+impl DeterministicScalar for DFix32 {}
 impl DeterministicScalar for DFix64 {}
+impl DeterministicScalar for DFix128 {}
 
 impl NondetScalar for f32 {}
+impl NondetScalar for f64 {}
@@ -2,13 +2,19 @@ use core::ops::{
     Add, Sub, Mul, Div, Neg,
 };
 
+use super::{DFix128, DFix32, DFix64, F32Det, F64Det};
+
 pub(crate) mod sealed {
     pub trait Sealed {}
 }
 
 impl sealed::Sealed for f32 {}
-impl sealed::Sealed for super::f32_det::F32Det {}
-impl sealed::Sealed for super::dfix64::DFix64 {}
+impl sealed::Sealed for f64 {}
+impl sealed::Sealed for F32Det {}
+impl sealed::Sealed for F64Det {}
+impl sealed::Sealed for DFix32 {}
+impl sealed::Sealed for DFix64 {}
+impl sealed::Sealed for DFix128 {}
 
 
 /// Minimal field-like scalar: add/mul/div, constants, conversions.
@@ -28,6 +34,16 @@ pub trait Scalar:
 
     fn from_f32(x: f32) -> Self;
     fn to_f32(self) -> f32;
+
+    /// Converts from `f64`, saturating fixed-point types at their range limits.
+    fn from_f64(x: f64) -> Self;
+    fn to_f64(self) -> f64;
+
+    /// Maps 64 uniformly random bits onto `[0, 1)` at the type's native
+    /// resolution: fixed-point types take the top fractional bits directly and
+    /// floats fill their mantissa, so no rounding is involved. Backs
+    /// `Prng::next_scalar`; the mapping is pinned by `PRNG_ALGO_VERSION`.
+    fn from_unit_bits(bits: u64) -> Self;
 }
 
 /// Scalars that support "real" operations like sqrt.
@@ -38,6 +54,8 @@ pub trait RealScalar: Scalar {
     fn min(self, other: Self) -> Self;
     fn max(self, other: Self) -> Self;
     fn clamp(self, min: Self, max: Self) -> Self;
+    /// Largest integer value not greater than `self`.
+    fn floor(self) -> Self;
 }
 
 /// Scalars that support trig.
@@ -0,0 +1,255 @@
+//! Hand-written `serde` impls for types whose wire format is not a plain derive.
+//!
+//! Fixed-point scalars pick their encoding from the format: human-readable
+//! formats (JSON, TOML, RON) get the exact decimal string, e.g. `"-1.5"`, while
+//! compact formats (bincode, postcard) get the raw two's-complement integer.
+//! Float-backed types always serialize their IEEE-754 value, which compact
+//! formats store bit-for-bit.
+
+use core::fmt;
+use core::marker::PhantomData;
+use core::str::FromStr;
+
+use serde::de::{self, Deserializer, Unexpected, Visitor};
+use serde::ser::{SerializeStruct, Serializer};
+use serde::{Deserialize, Serialize};
+
+use crate::angle::{Angle, AngleUnit};
+use crate::scalar::{DFix128, DFix32, DFix64, TrigScalar};
+use crate::{Prng, PRNG_ALGO_VERSION};
+
+struct DecimalVisitor<T>(PhantomData<T>);
+
+impl<'de, T> Visitor<'de> for DecimalVisitor<T>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    type Value = T;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a decimal string")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<T, E> {
+        v.parse().map_err(E::custom)
+    }
+}
+
+macro_rules! fixed_serde {
+    ($($ty:ident($raw:ty)),*) => {$(
+        impl Serialize for $ty {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                if serializer.is_human_readable() {
+                    serializer.collect_str(self)
+                } else {
+                    self.0.serialize(serializer)
+                }
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $ty {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                if deserializer.is_human_readable() {
+                    deserializer.deserialize_str(DecimalVisitor(PhantomData))
+                } else {
+                    <$raw>::deserialize(deserializer).map($ty)
+                }
+            }
+        }
+    )*};
+}
+
+fixed_serde!(DFix32(i32), DFix64(i64), DFix128(i128));
+
+/// Angles carry their unit so a degree value can never be read back as
+/// radians (or vice versa); a mismatched unit is a deserialization error.
+impl<T: TrigScalar + Serialize, U: AngleUnit> Serialize for Angle<T, U> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut s = serializer.serialize_struct("Angle", 2)?;
+        s.serialize_field("value", &self.value)?;
+        s.serialize_field("unit", U::NAME)?;
+        s.end()
+    }
+}
+
+impl<'de, T: TrigScalar + Deserialize<'de>, U: AngleUnit> Deserialize<'de> for Angle<T, U> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(rename = "Angle")]
+        struct Repr<T> {
+            value: T,
+            unit: String,
+        }
+
+        let repr = Repr::<T>::deserialize(deserializer)?;
+        if repr.unit != U::NAME {
+            return Err(de::Error::invalid_value(Unexpected::Str(&repr.unit), &U::NAME));
+        }
+        Ok(Angle::from_value(repr.value))
+    }
+}
+
+/// Snapshots record [`PRNG_ALGO_VERSION`] next to the state.
+impl Serialize for Prng {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut s = serializer.serialize_struct("Prng", 2)?;
+        s.serialize_field("version", &PRNG_ALGO_VERSION)?;
+        s.serialize_field("state", &self.state())?;
+        s.end()
+    }
+}
+
+/// Rejects snapshots from another [`PRNG_ALGO_VERSION`], which would silently
+/// diverge, and the all-zero state instead of remapping it like
+/// [`Prng::from_seed`], so a corrupted snapshot fails loudly.
+impl<'de> Deserialize<'de> for Prng {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(rename = "Prng")]
+        struct Repr {
+            version: u32,
+            state: [u64; 2],
+        }
+
+        let Repr { version, state } = Repr::deserialize(deserializer)?;
+        if version != PRNG_ALGO_VERSION {
+            return Err(de::Error::invalid_value(
+                Unexpected::Unsigned(version.into()),
+                &"this build's PRNG_ALGO_VERSION",
+            ));
+        }
+        Prng::from_state(state).ok_or_else(|| {
+            de::Error::invalid_value(Unexpected::Other("all-zero state"), &"a non-zero xoroshiro128+ state")
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_test::{assert_de_tokens_error, assert_tokens, Configure, Token};
+
+    use crate::*;
+
+    #[test]
+    fn fixed_point_is_decimal_when_readable_and_raw_when_compact() {
+        let x = DFix64::from_f32(-1.5);
+        assert_tokens(&x.readable(), &[Token::Str("-1.5")]);
+        assert_tokens(&x.compact(), &[Token::I64(-98_304)]);
+        assert_tokens(&DFix32(1).readable(), &[Token::Str("0.0000152587890625")]);
+        assert_tokens(&DFix32(1).compact(), &[Token::I32(1)]);
+    }
+
+    #[test]
+    fn fixed_point_rejects_malformed_decimals() {
+        assert_de_tokens_error::<serde_test::Readable<DFix64>>(
+            &[Token::Str("1.5e3")],
+            "invalid fixed-point literal",
+        );
+        assert_de_tokens_error::<serde_test::Readable<DFix32>>(
+            &[Token::Str("40000")],
+            "fixed-point literal out of range",
+        );
+    }
+
+    #[test]
+    fn float_wrappers_are_transparent() {
+        assert_tokens(&F32Det(0.25), &[Token::F32(0.25)]);
+        assert_tokens(&F64Det(-0.5), &[Token::F64(-0.5)]);
+    }
+
+    #[test]
+    fn vectors_round_trip() {
+        let v = Vec3::new(DFix64::ONE, DFix64::ZERO, -DFix64::ONE);
+        let vec_tokens = [
+            Token::Struct { name: "Vec3", len: 3 },
+            Token::Str("x"),
+            Token::Str("1"),
+            Token::Str("y"),
+            Token::Str("0"),
+            Token::Str("z"),
+            Token::Str("-1"),
+            Token::StructEnd,
+        ];
+        assert_tokens(&v.readable(), &vec_tokens);
+
+        let mut point_tokens = vec![Token::NewtypeStruct { name: "Point3" }];
+        point_tokens.extend_from_slice(&vec_tokens);
+        assert_tokens(&Point3(v).readable(), &point_tokens);
+
+        point_tokens[0] = Token::NewtypeStruct { name: "Direction3" };
+        assert_tokens(&Direction3(v).readable(), &point_tokens);
+    }
+
+    #[test]
+    fn angles_preserve_their_unit() {
+        let tokens = [
+            Token::Struct { name: "Angle", len: 2 },
+            Token::Str("value"),
+            Token::F32(45.0),
+            Token::Str("unit"),
+            Token::Str("deg"),
+            Token::StructEnd,
+        ];
+        assert_tokens(&DegAngle::from_degrees(45.0f32), &tokens);
+        assert_de_tokens_error::<RadAngle<f32>>(
+            &tokens,
+            "invalid value: string \"deg\", expected rad",
+        );
+    }
+
+    #[test]
+    fn quat_and_mat4_are_flat_arrays() {
+        let q = Quat::new(0.0, 0.5, 0.0, 1.0);
+        assert_tokens(
+            &q,
+            &[
+                Token::Tuple { len: 4 },
+                Token::F32(0.0),
+                Token::F32(0.5),
+                Token::F32(0.0),
+                Token::F32(1.0),
+                Token::TupleEnd,
+            ],
+        );
+
+        let m = Mat4::translation(1.0, 2.0, 3.0);
+        let mut tokens = vec![Token::Tuple { len: 16 }];
+        tokens.extend(m.to_array().iter().map(|&v| Token::F32(v)));
+        tokens.push(Token::TupleEnd);
+        assert_tokens(&m, &tokens);
+    }
+
+    fn prng_tokens(version: u32, state: [u64; 2]) -> [Token; 9] {
+        [
+            Token::Struct { name: "Prng", len: 2 },
+            Token::Str("version"),
+            Token::U32(version),
+            Token::Str("state"),
+            Token::Tuple { len: 2 },
+            Token::U64(state[0]),
+            Token::U64(state[1]),
+            Token::TupleEnd,
+            Token::StructEnd,
+        ]
+    }
+
+    #[test]
+    fn prng_state_round_trips_and_rejects_zero() {
+        let prng = Prng::from_seed(1, 2);
+        assert_tokens(&prng, &prng_tokens(PRNG_ALGO_VERSION, [1, 2]));
+        assert_de_tokens_error::<Prng>(
+            &prng_tokens(PRNG_ALGO_VERSION, [0, 0]),
+            "invalid value: all-zero state, expected a non-zero xoroshiro128+ state",
+        );
+    }
+
+    #[test]
+    fn prng_rejects_other_algo_versions() {
+        let other = PRNG_ALGO_VERSION + 1;
+        assert_de_tokens_error::<Prng>(
+            &prng_tokens(other, [1, 2]),
+            &format!("invalid value: integer `{other}`, expected this build's PRNG_ALGO_VERSION"),
+        );
+    }
+}
@@ -1,22 +1,56 @@
-use crate::{Scalar, Vec3};
-use core::ops::{Add, Sub, Mul};
+use crate::{DeterministicScalar, Quantize, Scalar, ScalarCast, Vec3};
+use core::ops::{Add, Sub, Mul, Neg};
 
 #[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Point3<T: Scalar>(pub Vec3<T>);
 
 #[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Direction3<T: Scalar>(pub Vec3<T>);
 
 impl<T: Scalar> Point3<T> {
     pub fn new(x: T, y: T, z: T) -> Self {
         Point3(Vec3::new(x, y, z))
     }
+
+    /// See [`Vec3::cast`].
+    pub fn cast<U: Scalar>(self) -> Point3<U>
+    where
+        T: ScalarCast<U>,
+    {
+        Point3(self.0.cast())
+    }
+
+    /// See [`Vec3::quantize`].
+    pub fn quantize<U: DeterministicScalar>(self) -> Point3<U>
+    where
+        T: Quantize<U>,
+    {
+        Point3(self.0.quantize())
+    }
 }
 
 impl<T: Scalar> Direction3<T> {
     pub fn new(x: T, y: T, z: T) -> Self {
         Direction3(Vec3::new(x, y, z))
     }
+
+    /// See [`Vec3::cast`].
+    pub fn cast<U: Scalar>(self) -> Direction3<U>
+    where
+        T: ScalarCast<U>,
+    {
+        Direction3(self.0.cast())
+    }
+
+    /// See [`Vec3::quantize`].
+    pub fn quantize<U: DeterministicScalar>(self) -> Direction3<U>
+    where
+        T: Quantize<U>,
+    {
+        Direction3(self.0.quantize())
+    }
 }
 
 // Operators for Point3 and Direction3
@@ -47,4 +81,11 @@ impl<T: Scalar> Mul<T> for Direction3<T> {
     fn mul(self, rhs: T) -> Self::Output {
         Direction3(self.0 * rhs)
     }
-}
\ No newline at end of file
+}
+
+impl<T: Scalar> Neg for Direction3<T> {
+    type Output = Self;
+    fn neg(self) -> Self::Output {
+        Direction3(-self.0)
+    }
+}
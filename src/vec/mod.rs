@@ -1,2 +0,0 @@
-pub mod vec3;
-pub mod point3;
@@ -1,44 +0,0 @@
-use crate::scalar::RealScalar;
-use core::ops::{Add, Sub, Mul};
-
-#[derive(Copy, Clone, Debug, PartialEq)]
-pub struct Vec3<T: RealScalar> {
-    pub x: T,
-    pub y: T,
-    pub z: T,
-}
-
-impl<T: RealScalar> Vec3<T> {
-    pub fn new(x: T, y: T, z: T) -> Self {
-        Self { x, y, z }
-    }
-
-    pub fn splat(v: T) -> Self {
-        Self { x: v, y: v, z: v }
-    }
-
-    pub fn dot(&self, other: &Self) -> T {
-        self.x * other.x + self.y * other.y + self.z * other.z
-    }
-}
-
-impl<T: RealScalar> Add for Vec3<T> {
-    type Output = Self;
-    fn add(self, rhs: Self) -> Self::Output {
-        Self::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
-    }
-}
-
-impl<T: RealScalar> Sub for Vec3<T> {
-    type Output = Self;
-    fn sub(self, rhs: Self) -> Self::Output {
-        Self::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
-    }
-}
-
-impl<T: RealScalar> Mul<T> for Vec3<T> {
-    type Output = Self;
-    fn mul(self, rhs: T) -> Self::Output {
-        Self::new(self.x * rhs, self.y * rhs, self.z * rhs)
-    }
-}
\ No newline at end of file
@@ -1,7 +1,8 @@
-use crate::scalar::{RealScalar, Scalar};
-use core::ops::{Add, Sub, Mul};
+use crate::scalar::{DeterministicScalar, Quantize, RealScalar, Scalar, ScalarCast};
+use core::ops::{Add, Sub, Mul, Neg};
 
 #[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Vec3<T: Scalar> {
     pub x: T,
     pub y: T,
@@ -17,9 +18,40 @@ impl<T: Scalar> Vec3<T> {
         Self { x: v, y: v, z: v }
     }
 
+    /// Returns the components as `[x, y, z]`.
+    pub fn to_array(self) -> [T; 3] {
+        [self.x, self.y, self.z]
+    }
+
     pub fn dot(&self, other: &Self) -> T {
         self.x * other.x + self.y * other.y + self.z * other.z
     }
+
+    pub fn cross(&self, other: &Self) -> Self {
+        Self::new(
+            self.y * other.z - self.z * other.y,
+            self.z * other.x - self.x * other.z,
+            self.x * other.y - self.y * other.x,
+        )
+    }
+
+    /// Converts each component into another scalar backend; see [`ScalarCast`]
+    /// for precision loss.
+    pub fn cast<U: Scalar>(self) -> Vec3<U>
+    where
+        T: ScalarCast<U>,
+    {
+        Vec3::new(self.x.cast(), self.y.cast(), self.z.cast())
+    }
+
+    /// Explicitly quantizes each component into a deterministic backend; see
+    /// [`Quantize`].
+    pub fn quantize<U: DeterministicScalar>(self) -> Vec3<U>
+    where
+        T: Quantize<U>,
+    {
+        Vec3::new(self.x.quantize(), self.y.quantize(), self.z.quantize())
+    }
 }
 
 impl<T: RealScalar> Vec3<T> {
@@ -41,6 +73,12 @@ impl<T: RealScalar> Vec3<T> {
     }
 }
 
+impl<T: Scalar> From<[T; 3]> for Vec3<T> {
+    fn from(value: [T; 3]) -> Self {
+        Self::new(value[0], value[1], value[2])
+    }
+}
+
 impl<T: Scalar> Add for Vec3<T> {
     type Output = Self;
     fn add(self, rhs: Self) -> Self::Output {
@@ -60,4 +98,11 @@ impl<T: Scalar> Mul<T> for Vec3<T> {
     fn mul(self, rhs: T) -> Self::Output {
         Self::new(self.x * rhs, self.y * rhs, self.z * rhs)
     }
-}
\ No newline at end of file
+}
+
+impl<T: Scalar> Neg for Vec3<T> {
+    type Output = Self;
+    fn neg(self) -> Self::Output {
+        Self::new(-self.x, -self.y, -self.z)
+    }
+}
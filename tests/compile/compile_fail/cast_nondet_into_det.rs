@@ -0,0 +1,6 @@
+use echo_math::*;
+
+fn main() {
+    let v = Vec3::<f32>::new(1.0, 2.0, 3.0);
+    let _d = v.cast::<DFix64>(); // should fail; use quantize
+}
@@ -0,0 +1,97 @@
+//! Exercises every scalar backend purely through the public trait hierarchy.
+
+use echo_math::scalar::*;
+
+fn close<T: Scalar>(actual: T, expected: f64, tol: f64) -> bool {
+    (actual.to_f64() - expected).abs() <= tol
+}
+
+fn check_scalar<T: Scalar + core::fmt::Debug>() {
+    let two = T::ONE + T::ONE;
+    let three = two + T::ONE;
+    assert_eq!(T::ZERO + T::ONE, T::ONE);
+    assert_eq!(three - T::ONE, two);
+    assert_eq!(-(-two), two);
+    assert!(close(two * three, 6.0, 0.0));
+    assert!(close(three / two, 1.5, 0.0));
+    assert!(T::epsilon() > T::ZERO);
+    assert!(T::ZERO < T::ONE);
+    assert_eq!(T::from_f32(-0.75).to_f32(), -0.75);
+    assert_eq!(T::from_f64(-0.75).to_f64(), -0.75);
+}
+
+fn check_real<T: RealScalar + core::fmt::Debug>(tol: f64) {
+    let a = T::from_f32(-2.5);
+    let b = T::from_f32(4.0);
+    assert_eq!(a.abs(), T::from_f32(2.5));
+    assert_eq!(a.min(b), a);
+    assert_eq!(a.max(b), b);
+    assert_eq!(T::from_f32(7.0).clamp(a, b), b);
+    assert_eq!(a.floor(), T::from_f32(-3.0));
+    assert_eq!(T::from_f32(2.75).floor(), T::from_f32(2.0));
+    assert_eq!(b.floor(), b);
+    assert!(close(b.sqrt(), 2.0, tol));
+    assert!(close(b.rsqrt(), 0.5, tol));
+    assert!(close(T::from_f32(2.0).sqrt(), core::f64::consts::SQRT_2, tol));
+}
+
+fn check_trig<T: TrigScalar + core::fmt::Debug>(tol: f64) {
+    for i in -12..=12 {
+        let x = f64::from(i) * 0.25;
+        let t = T::from_f64(x);
+        let x = t.to_f64();
+        assert!(close(t.sin(), x.sin(), tol), "sin({x})");
+        assert!(close(t.cos(), x.cos(), tol), "cos({x})");
+        if x.cos().abs() > 0.1 {
+            assert!(close(t.tan(), x.tan(), tol * 100.0), "tan({x})");
+        }
+    }
+    for i in -4..=4 {
+        let x = f64::from(i) * 0.25;
+        let t = T::from_f64(x);
+        assert!(close(t.asin(), x.asin(), tol), "asin({x})");
+        assert!(close(t.acos(), x.acos(), tol), "acos({x})");
+    }
+    let (y, x) = (T::from_f32(-1.0), T::from_f32(-1.0));
+    assert!(close(y.atan2(x), -3.0 * core::f64::consts::FRAC_PI_4, tol));
+}
+
+macro_rules! scalar_suite {
+    ($($name:ident: $ty:ty, $tol:expr;)*) => {$(
+        mod $name {
+            use super::*;
+
+            #[test]
+            fn scalar() { check_scalar::<$ty>(); }
+
+            #[test]
+            fn real() { check_real::<$ty>($tol); }
+
+            #[test]
+            fn trig() { check_trig::<$ty>($tol); }
+        }
+    )*};
+}
+
+scalar_suite! {
+    f32_scalar: f32, 1e-6;
+    f64_scalar: f64, 1e-12;
+    f32_det: F32Det, 1e-6;
+    f64_det: F64Det, 1e-12;
+    dfix32: DFix32, 1e-4;
+    dfix64: DFix64, 1e-4;
+    dfix128: DFix128, 1e-8;
+}
+
+#[test]
+fn markers_partition_the_backends() {
+    fn det<T: DeterministicScalar>() {}
+    fn nondet<T: NondetScalar>() {}
+    det::<F32Det>();
+    det::<F64Det>();
+    det::<DFix32>();
+    det::<DFix64>();
+    det::<DFix128>();
+    nondet::<f32>();
+    nondet::<f64>();
+}